@@ -0,0 +1,92 @@
+//! Times tokenizing and parsing a generated file of a million lines.
+//!
+//! Run with `cargo bench --bench tokenize`.
+use std::time::{Duration, Instant};
+
+use gedcom::lexer::Lexer;
+use gedcom::parser::Parser;
+use gedcom::tokenizer::{Token, Tokenizer};
+
+const LINES: usize = 1_000_000;
+
+/// Builds a file of `LINES` lines out of repeated individual records.
+fn generate() -> String {
+    let record = [
+        "0 @I{}@ INDI",
+        "1 NAME John /Doe{}/",
+        "2 GIVN John",
+        "2 SURN Doe{}",
+        "1 SEX M",
+        "1 BIRT",
+        "2 DATE 1 JAN 1900",
+        "2 PLAC Somewhere, Some County, Some State, USA",
+        "1 FAMC @F{}@",
+        "1 NOTE A note that is long enough to look like a real one, {}",
+    ];
+    let mut source =
+        String::from("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n2 FORM LINEAGE-LINKED\n1 CHAR UTF-8\n");
+    let mut lines = 5;
+    let mut id = 0;
+    while lines < LINES - 1 {
+        for line in &record {
+            source.push_str(&line.replace("{}", &id.to_string()));
+            source.push('\n');
+        }
+        lines += record.len();
+        id += 1;
+    }
+    source.push_str("0 TRLR\n");
+    source
+}
+
+fn time<F: FnMut() -> usize>(name: &str, mut run: F) {
+    // warm up, then keep the fastest of a few runs
+    let count = run();
+    let mut best = Duration::MAX;
+    for _ in 0..3 {
+        let start = Instant::now();
+        run();
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "{:<28} {:>10} items {:>10.1} ms",
+        name,
+        count,
+        best.as_secs_f64() * 1000.0
+    );
+}
+
+fn main() {
+    let source = generate();
+    println!(
+        "input: {} lines, {} bytes",
+        source.lines().count(),
+        source.len()
+    );
+
+    time("lexer (borrowed tokens)", || {
+        let mut lexer = Lexer::new(&source);
+        let mut count = 0;
+        while lexer.next_token().unwrap().is_some() {
+            count += 1;
+        }
+        count
+    });
+
+    time("tokenizer (owned tokens)", || {
+        let mut tokenizer = Tokenizer::new(source.chars());
+        let mut count = 0;
+        loop {
+            tokenizer.next_token().unwrap();
+            if tokenizer.current_token == Token::EOF {
+                break count;
+            }
+            count += 1;
+        }
+    });
+
+    time("parser", || {
+        let data = Parser::new(source.chars()).try_parse().unwrap();
+        data.individuals.len()
+    });
+}
@@ -1,8 +1,7 @@
 use gedcom::parser::Parser;
-use gedcom::GedcomData;
 use std::env;
-use std::fs;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -18,25 +17,33 @@ fn main() {
         usage("");
     }
 
-    let data: GedcomData;
-
-    let res = read_relative(&filename);
-    if let Ok(contents) = res {
-        let mut parser = Parser::new(contents.chars());
-        data = parser.parse_record();
-
-        println!("Parsing complete!");
-        // println!("\n\n{:#?}", data);
-        data.stats();
-    } else if let Err(io_err) = res{
-        exit_with_error(&format!("Could not open`{}` : {}", filename, io_err.to_string()));
+    if filename == "-" {
+        parse(filename, io::stdin().lock());
+    } else {
+        match File::open(filename) {
+            Ok(file) => parse(filename, BufReader::new(file)),
+            Err(io_err) => {
+                exit_with_error(&format!("Could not open`{}` : {}", filename, io_err));
+            }
+        }
     }
 }
 
-fn read_relative(path: &str) -> Result<String, std::io::Error> {
-    let path_buf: PathBuf = PathBuf::from(path);
-    let absolute_path: PathBuf = fs::canonicalize(path_buf)?;
-    fs::read_to_string(absolute_path)
+fn parse(filename: &str, reader: impl BufRead) {
+    let mut parser = Parser::from_reader(reader);
+    match parser.try_parse() {
+        Ok(data) => {
+            for diagnostic in parser.diagnostics() {
+                println!("{}", diagnostic);
+            }
+            println!("Parsing complete!");
+            // println!("\n\n{:#?}", data);
+            data.stats();
+        }
+        Err(parse_err) => {
+            exit_with_error(&format!("Could not parse `{}` : {}", filename, parse_err));
+        }
+    }
 }
 
 fn usage(msg: &str) {
@@ -44,6 +51,7 @@ fn usage(msg: &str) {
         println!("{}", msg);
     }
     println!("Usage: parse_gedcom ./path/to/gedcom.ged");
+    println!("       parse_gedcom - < ./path/to/gedcom.ged");
     std::process::exit(0x0100);
 }
 
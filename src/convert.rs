@@ -0,0 +1,1226 @@
+//! Conversion of GEDCOM data between GEDCOM 5.5.1 and GEDCOM 7.0
+//!
+//! A `Converter` takes the records of a `GedcomData` the way `GedcomWriter::write_data` does
+//! and rewrites their structures for the target version: `CONC` lines are merged, shared
+//! notes become `SNOTE` records, dates and ages are normalized, and so on. Where the target
+//! version cannot hold something, it is dropped or changed as little as possible and a
+//! `DiagnosticKind::LossyConversion` diagnostic says so.
+//!
+//! ```rust
+//! use gedcom::convert::Converter;
+//! use gedcom::types::GedcomVersion;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n2 FORM LINEAGE-LINKED\n1 CHAR UTF-8\n\
+//!     0 @N1@ NOTE A note that go\n1 CONC es on\n0 TRLR";
+//! let data = gedcom::parse(source.chars());
+//!
+//! let mut output = Vec::new();
+//! let mut converter = Converter::new(GedcomVersion::V7);
+//! converter.write(&data, &mut output).unwrap();
+//!
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @N1@ SNOTE A note that goes on\n0 TRLR\n"
+//! );
+//! assert!(converter.diagnostics().is_empty());
+//! ```
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::io::{self, Write};
+
+use crate::diagnostic::{Diagnostic, DiagnosticKind, Severity};
+use crate::node::{is_pointer, GedcomNode};
+use crate::parser::unescape;
+use crate::tree::GedcomData;
+use crate::types::{AgeDuration, AgeValue, GedcomVersion};
+use crate::writer::{escape, GedcomWriter, WriterOptions};
+
+/// Options controlling the output of a `Converter`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConverterOptions {
+    /// How to write the converted file. Pass `Parser::writer_options` to keep the line
+    /// endings of a parsed file. Its `version` is set to the target of the conversion.
+    pub writer: WriterOptions,
+    /// The base of the URIs declaring the extension tags of a file converted to GEDCOM 7,
+    /// _ie._ `https://example.com/tags/` declares `_HAIR` as `https://example.com/tags/_HAIR`.
+    /// Without one, extension tags are left undeclared, which is reported for each tag.
+    pub extension_base_uri: Option<String>,
+}
+
+impl Default for ConverterOptions {
+    fn default() -> Self {
+        ConverterOptions {
+            writer: WriterOptions {
+                keep_header: true,
+                ..WriterOptions::default()
+            },
+            extension_base_uri: None,
+        }
+    }
+}
+
+/// Converts GEDCOM data to GEDCOM 7.0, or back to GEDCOM 5.5.1 as best it can.
+///
+/// Records already in the target version are kept as they are. Records built from edited
+/// typed data follow GEDCOM 5.5.1, whatever version the file they were read from follows.
+pub struct Converter {
+    target: GedcomVersion,
+    options: ConverterOptions,
+    /// Problems found so far
+    diagnostics: Vec<Diagnostic>,
+    /// Xref of the top-level record being converted
+    record_xref: Option<String>,
+    /// Tags of the structures enclosing the line being converted, and its own tag
+    tag_path: Vec<String>,
+    /// The xrefs in use, so records split off from others get new ones
+    xrefs: HashSet<String>,
+    /// Records split off from the records being converted, like embedded multimedia
+    hoisted: Vec<GedcomNode>,
+    /// The extension tags used by the converted records
+    extensions: BTreeSet<String>,
+}
+
+impl Converter {
+    /// Creates a converter to the `target` version
+    #[must_use]
+    pub fn new(target: GedcomVersion) -> Converter {
+        Converter::with_options(target, ConverterOptions::default())
+    }
+
+    /// Creates a converter to the `target` version that follows the given `ConverterOptions`
+    #[must_use]
+    pub fn with_options(target: GedcomVersion, options: ConverterOptions) -> Converter {
+        Converter {
+            target,
+            options,
+            diagnostics: Vec::new(),
+            record_xref: None,
+            tag_path: Vec::new(),
+            xrefs: HashSet::new(),
+            hoisted: Vec::new(),
+            extensions: BTreeSet::new(),
+        }
+    }
+
+    /// The data lost in the conversions so far, in the order it was found.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the data lost in the conversions so far.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Converts the records of `data`, returning the header followed by the other records,
+    /// without the trailer. Values are escaped for the target version, and the `line` of
+    /// each converted node is 0.
+    pub fn convert(&mut self, data: &GedcomData) -> Vec<GedcomNode> {
+        let mut writer = GedcomWriter::with_options(io::sink(), self.options.writer.clone());
+        let nodes = writer.record_nodes(data);
+        let source = data.header.version();
+        self.xrefs = nodes.iter().filter_map(|node| node.xref.clone()).collect();
+        self.extensions.clear();
+
+        let mut records = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            // records built from typed data follow GEDCOM 5.5.1
+            let version = if node.tag == "HEAD" {
+                header_version(&node)
+            } else if let Cow::Borrowed(_) = node {
+                source
+            } else {
+                GedcomVersion::V5
+            };
+            let node = node.into_owned();
+            if version == self.target {
+                records.push(node);
+                continue;
+            }
+            self.record_xref.clone_from(&node.xref);
+            self.tag_path.clear();
+            let converted = match self.target {
+                GedcomVersion::V7 => self.upgrade(node, ""),
+                GedcomVersion::V5 => self.downgrade(node, ""),
+            };
+            if let Some(mut node) = converted {
+                renumber(&mut node, 0);
+                records.push(node);
+            }
+        }
+        records.append(&mut self.hoisted);
+        if self.target == GedcomVersion::V7 {
+            self.declare_extensions(&mut records);
+        }
+        records
+    }
+
+    /// Converts `data` and writes it as a whole file, trailer included.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `out`.
+    pub fn write(&mut self, data: &GedcomData, out: impl Write) -> io::Result<()> {
+        let records = self.convert(data);
+        let options = WriterOptions {
+            version: self.target,
+            ..self.options.writer.clone()
+        };
+        let mut writer = GedcomWriter::with_options(out, options);
+        for record in &records {
+            writer.write_node(record)?;
+        }
+        writer.write_trailer()
+    }
+
+    /// Converts a GEDCOM 5.5.1 line and its subordinate lines to GEDCOM 7, returning
+    /// `None` when it has no place in GEDCOM 7
+    fn upgrade(&mut self, mut node: GedcomNode, parent: &str) -> Option<GedcomNode> {
+        self.tag_path.push(node.tag.clone());
+        join_continuations(&mut node, GedcomVersion::V5);
+        let tag = node.tag.clone();
+        node.children = std::mem::take(&mut node.children)
+            .into_iter()
+            .filter_map(|child| self.upgrade(child, &tag))
+            .collect();
+        let kept = self.upgrade_line(&mut node, parent);
+        if kept
+            && node.is_custom()
+            && self.extensions.insert(node.tag.clone())
+            && self.options.extension_base_uri.is_none()
+        {
+            self.report(
+                &node,
+                DiagnosticKind::UndeclaredExtension,
+                format!("Extension tag {} is not declared in the header", node.tag),
+            );
+        }
+        self.finish_line(&mut node);
+        self.tag_path.pop();
+        kept.then_some(node)
+    }
+
+    /// Converts a single line for GEDCOM 7, once its subordinate lines are converted
+    fn upgrade_line(&mut self, node: &mut GedcomNode, parent: &str) -> bool {
+        let pointer = node.value.as_deref().is_some_and(is_pointer);
+        match (node.tag.as_str(), parent) {
+            ("HEAD", "") => upgrade_header(node),
+            ("NOTE", "") => node.tag = "SNOTE".to_string(),
+            ("NOTE", _) if pointer => node.tag = "SNOTE".to_string(),
+            ("SUBN", "" | "HEAD") => {
+                self.lose(node, "GEDCOM 7 has no submission record, dropping it");
+                return false;
+            }
+            ("FILE", "HEAD") => {
+                self.lose(node, "GEDCOM 7 has no file name in the header, dropping it");
+                return false;
+            }
+            ("CHAR", "HEAD") => return false,
+            ("DATE", _) => self.upgrade_date(node),
+            ("AGE", _) => self.upgrade_age(node),
+            ("LANG", _) => self.upgrade_language(node),
+            ("RELA", "ASSO") => {
+                node.tag = "ROLE".to_string();
+                let relation = node.value.replace("OTHER".to_string());
+                self.push_phrase(node, relation);
+            }
+            ("ROLE", _) => self.upgrade_role(node),
+            ("AFN" | "RFN" | "RIN", _) => {
+                let id_type = format!("https://gedcom.io/terms/v7/{}", node.tag);
+                node.tag = "EXID".to_string();
+                node.children.push(self.text_node("TYPE", &id_type));
+            }
+            ("_UID", _) => node.tag = "UID".to_string(),
+            ("ROMN", "NAME" | "PLAC") => {
+                node.tag = "TRAN".to_string();
+                if let Some(method) = take_child(node, "TYPE") {
+                    self.lose(
+                        &method,
+                        "GEDCOM 7 translations have no romanization method, dropping it",
+                    );
+                }
+                node.children.insert(0, self.text_node("LANG", "und-Latn"));
+            }
+            ("FONE", "NAME" | "PLAC") => {
+                self.lose(node, "GEDCOM 7 has no phonetic variations, dropping it");
+                return false;
+            }
+            ("BLOB", "OBJE") => {
+                self.lose(
+                    node,
+                    "GEDCOM 7 has no embedded multimedia data, dropping it",
+                );
+                return false;
+            }
+            ("OBJE", "") => restructure_media(node),
+            ("OBJE", _) if !pointer => self.hoist_media(node),
+            ("SOUR", _) if !pointer && !parent.is_empty() && parent != "HEAD" => {
+                self.upgrade_source_description(node);
+            }
+            ("FORM", "FILE" | "OBJE") => self.upgrade_media_type(node),
+            ("TYPE", "FORM") => {
+                node.tag = "MEDI".to_string();
+                self.upgrade_enumeration(node, MEDIA);
+            }
+            ("MEDI", _) => self.upgrade_enumeration(node, MEDIA),
+            ("PEDI", _) => self.upgrade_enumeration(node, PEDIGREES),
+            ("TYPE", "NAME") => self.upgrade_enumeration(node, NAME_TYPES),
+            ("RESN", _) => {
+                if let Some(value) = &mut node.value {
+                    *value = value.to_uppercase();
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Converts a source described in place to a citation of an unknown source, as
+    /// GEDCOM 7 only cites source records
+    fn upgrade_source_description(&self, node: &mut GedcomNode) {
+        let description = node.value.replace("@VOID@".to_string());
+        let mut data = take_child(node, "DATA").unwrap_or_else(|| GedcomNode::new(0, "DATA"));
+        while let Some(text) = take_child(node, "TEXT") {
+            data.children.push(text);
+        }
+        if !data.children.is_empty() {
+            node.children.insert(0, data);
+        }
+        if let Some(description) = description.filter(|text| !text.is_empty()) {
+            node.children
+                .insert(0, self.text_node("PAGE", &description));
+        }
+    }
+
+    /// Converts the value of a `DATE` to a GEDCOM 7 date and `PHRASE`
+    fn upgrade_date(&mut self, node: &mut GedcomNode) {
+        let text = node.value.take().unwrap_or_default();
+        let text = text.trim();
+        let (date, mut phrase) = match text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            Some(phrase) => (String::new(), Some(phrase.to_string())),
+            None => match interpreted_date(text) {
+                Some((date, phrase)) => (date.to_string(), Some(phrase.to_string())),
+                None => (text.to_string(), None),
+            },
+        };
+        let Some((value, dual_year)) = date_tokens_v7(&date) else {
+            self.lose(
+                node,
+                &format!("{text} is not a GEDCOM 7 date, keeping it as a phrase"),
+            );
+            node.value = Some(String::new());
+            self.push_phrase(node, Some(text.to_string()));
+            return;
+        };
+        if dual_year {
+            phrase.get_or_insert_with(|| text.to_string());
+            self.lose(
+                node,
+                &format!("GEDCOM 7 has no dual years, {text} is dated by the later year"),
+            );
+        }
+        node.value = Some(value);
+        self.push_phrase(node, phrase);
+    }
+
+    /// Converts the value of an `AGE` to a GEDCOM 7 age and `PHRASE`
+    fn upgrade_age(&mut self, node: &mut GedcomNode) {
+        let text = node.value.take().unwrap_or_default();
+        let (value, phrase) = match text.trim().to_uppercase().as_str() {
+            "CHILD" => ("< 8y".to_string(), Some("Child".to_string())),
+            "INFANT" => ("< 1y".to_string(), Some("Infant".to_string())),
+            "STILLBORN" => ("0y".to_string(), Some("Stillborn".to_string())),
+            age => {
+                if let Some(duration) = age_duration(age).and_then(weeks_as_days) {
+                    (format_age(duration, true), None)
+                } else {
+                    self.lose(
+                        node,
+                        &format!("{text} is not a GEDCOM 7 age, keeping it as a phrase"),
+                    );
+                    (String::new(), Some(text.trim().to_string()))
+                }
+            }
+        };
+        node.value = Some(value);
+        self.push_phrase(node, phrase);
+    }
+
+    /// Converts the language name of a `LANG` to a language tag
+    fn upgrade_language(&mut self, node: &mut GedcomNode) {
+        let name = node.value.take().unwrap_or_default();
+        let tag = LANGUAGES
+            .iter()
+            .find(|(language, _)| language.eq_ignore_ascii_case(name.trim()))
+            .map(|(_, tag)| *tag);
+        if tag.is_none() {
+            self.lose(
+                node,
+                &format!("Unknown language {name}, writing it as undetermined"),
+            );
+        }
+        node.value = Some(tag.unwrap_or("und").to_string());
+    }
+
+    /// Converts a `ROLE` of an event to the roles of GEDCOM 7, with any role
+    /// described in parentheses kept as a `PHRASE`
+    fn upgrade_role(&mut self, node: &mut GedcomNode) {
+        let text = node.value.take().unwrap_or_default();
+        let role = text.trim().to_uppercase();
+        if ROLES_V5.contains(&role.as_str()) {
+            node.value = Some(role);
+        } else {
+            let description = text.trim().trim_start_matches('(').trim_end_matches(')');
+            node.value = Some("OTHER".to_string());
+            self.push_phrase(node, Some(description.to_string()));
+        }
+    }
+
+    /// Converts the file format of a multimedia `FORM` to a media type
+    fn upgrade_media_type(&mut self, node: &mut GedcomNode) {
+        let format = node.value.take().unwrap_or_default();
+        let format = format.trim().to_lowercase();
+        let media_type = if format.contains('/') {
+            Some(format.clone())
+        } else {
+            MEDIA_TYPES
+                .iter()
+                .find(|(extension, _)| *extension == format)
+                .map(|(_, media_type)| (*media_type).to_string())
+        };
+        node.value = Some(media_type.unwrap_or_else(|| {
+            self.lose(
+                node,
+                &format!(
+                    "Unknown multimedia format {format}, writing it as application/octet-stream"
+                ),
+            );
+            "application/octet-stream".to_string()
+        }));
+    }
+
+    /// Writes an enumerated value in uppercase, with a value not in `values` kept as
+    /// the `PHRASE` of `OTHER`
+    fn upgrade_enumeration(&mut self, node: &mut GedcomNode, values: &[&str]) {
+        let text = node.value.take().unwrap_or_default();
+        let value = text.trim().to_uppercase();
+        if values.contains(&value.as_str()) {
+            node.value = Some(value);
+        } else {
+            node.value = Some("OTHER".to_string());
+            self.push_phrase(node, Some(text.trim().to_string()));
+        }
+    }
+
+    /// Moves multimedia embedded in a record to a record of its own, as GEDCOM 7 only
+    /// links to multimedia records
+    fn hoist_media(&mut self, node: &mut GedcomNode) {
+        // one of these is free, as each xref in use rules out at most one
+        let xref = (1..=self.xrefs.len() + 1)
+            .map(|n| format!("@M{n}@"))
+            .find(|xref| !self.xrefs.contains(xref))
+            .unwrap_or_default();
+        self.xrefs.insert(xref.clone());
+        let mut record = GedcomNode {
+            xref: Some(xref.clone()),
+            children: std::mem::take(&mut node.children),
+            ..GedcomNode::new(0, "OBJE")
+        };
+        restructure_media(&mut record);
+        renumber(&mut record, 0);
+        self.hoisted.push(record);
+        node.value = Some(xref);
+    }
+
+    /// Declares the extension tags of the converted records in the `SCHMA` of the header
+    fn declare_extensions(&mut self, records: &mut [GedcomNode]) {
+        let Some(base) = &self.options.extension_base_uri else {
+            return;
+        };
+        let Some(header) = records.iter_mut().find(|record| record.tag == "HEAD") else {
+            return;
+        };
+        let after_gedc = header
+            .children
+            .iter()
+            .position(|child| child.tag == "GEDC")
+            .map_or(0, |i| i + 1);
+        let schema = child_or_insert(header, "SCHMA", after_gedc);
+        for tag in &self.extensions {
+            let declared = schema.children.iter().any(|declaration| {
+                declaration
+                    .value
+                    .as_deref()
+                    .and_then(|value| value.split_whitespace().next())
+                    == Some(tag.as_str())
+            });
+            if !declared {
+                schema.push_child("TAG", Some(format!("{tag} {base}{tag}")));
+            }
+        }
+    }
+
+    /// Converts a GEDCOM 7 line and its subordinate lines to GEDCOM 5.5.1, returning
+    /// `None` when it has no place in GEDCOM 5.5.1
+    fn downgrade(&mut self, mut node: GedcomNode, parent: &str) -> Option<GedcomNode> {
+        self.tag_path.push(node.tag.clone());
+        join_continuations(&mut node, GedcomVersion::V7);
+        let tag = node.tag.clone();
+        node.children = std::mem::take(&mut node.children)
+            .into_iter()
+            .filter_map(|child| self.downgrade(child, &tag))
+            .collect();
+        let kept = self.downgrade_line(&mut node, parent);
+        if kept {
+            // a phrase left over is one GEDCOM 5.5.1 has no place for
+            while let Some(phrase) = take_child(&mut node, "PHRASE") {
+                self.lose(&phrase, "GEDCOM 5.5.1 has no phrases, dropping it");
+            }
+            if node.tag != "PHRASE" {
+                self.finish_line(&mut node);
+            }
+        }
+        self.tag_path.pop();
+        kept.then_some(node)
+    }
+
+    /// Converts a single line for GEDCOM 5.5.1, once its subordinate lines are converted
+    fn downgrade_line(&mut self, node: &mut GedcomNode, parent: &str) -> bool {
+        if node.value.as_deref() == Some("@VOID@") {
+            if node.tag == "SOUR" {
+                self.downgrade_void_citation(node);
+                return true;
+            }
+            self.lose(node, "GEDCOM 5.5.1 has no void pointers, dropping it");
+            return false;
+        }
+        match (node.tag.as_str(), parent) {
+            ("HEAD", "") => downgrade_header(node),
+            ("SNOTE", _) => node.tag = "NOTE".to_string(),
+            ("MIME", _) => {
+                if node.value.as_deref() != Some("text/plain") {
+                    self.lose(
+                        node,
+                        "GEDCOM 5.5.1 text is plain, keeping the markup as it is",
+                    );
+                }
+                return false;
+            }
+            ("TRAN", "NAME" | "PLAC") => {
+                node.tag = "ROMN".to_string();
+                if let Some(language) = take_child(node, "LANG") {
+                    node.children.insert(
+                        0,
+                        GedcomNode {
+                            tag: "TYPE".to_string(),
+                            ..language
+                        },
+                    );
+                }
+            }
+            // kept until the parent takes it as the type of a romanized variation
+            ("LANG", "TRAN") => {}
+            ("LANG", "HEAD" | "SUBM") => return self.downgrade_language(node),
+            ("LANG" | "TRAN" | "CREA" | "SDATE" | "INIL" | "NO" | "CROP" | "SCHMA", _) => {
+                if node.tag != "SCHMA" || !node.children.is_empty() {
+                    self.lose(
+                        node,
+                        &format!("GEDCOM 5.5.1 has no {}, dropping it", node.tag),
+                    );
+                }
+                return false;
+            }
+            ("DATE", _) => self.downgrade_date(node),
+            ("AGE", _) => return self.downgrade_age(node),
+            ("ROLE", "ASSO") => {
+                node.tag = "RELA".to_string();
+                let role = node.value.take().unwrap_or_default();
+                let phrase = take_child(node, "PHRASE").and_then(|phrase| phrase.value);
+                node.value = Some(phrase.unwrap_or_else(|| title_case(&role)));
+            }
+            ("ROLE", _) => downgrade_role(node),
+            ("EXID", _) => self.downgrade_external_id(node),
+            ("UID", _) => node.tag = "_UID".to_string(),
+            ("SEX", _) if node.value.as_deref() == Some("X") => {
+                self.lose(node, "GEDCOM 5.5.1 has no sex X, writing it as U");
+                node.value = Some("U".to_string());
+            }
+            ("FORM", "FILE") => self.downgrade_media_type(node),
+            ("MEDI", "FORM") => {
+                node.tag = "TYPE".to_string();
+                return self.downgrade_enumeration(node, MEDIA, false);
+            }
+            ("MEDI", _) => return self.downgrade_enumeration(node, MEDIA, false),
+            ("PEDI", _) => return self.downgrade_enumeration(node, PEDIGREES, false),
+            ("TYPE", "NAME") => return self.downgrade_enumeration(node, NAME_TYPES, true),
+            ("RESN", _) => {
+                if let Some(value) = &mut node.value {
+                    *value = value.to_lowercase();
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Converts an `EXID` to the `AFN`, `RFN` or `RIN` it was converted from, or to
+    /// a `REFN` with the type of the identifier
+    fn downgrade_external_id(&self, node: &mut GedcomNode) {
+        let id_type = take_child(node, "TYPE").and_then(|id_type| id_type.value);
+        let tag = id_type
+            .as_deref()
+            .and_then(|uri| uri.strip_prefix("https://gedcom.io/terms/v7/"))
+            .filter(|tag| ["AFN", "RFN", "RIN"].contains(tag));
+        if let Some(tag) = tag {
+            node.tag = tag.to_string();
+            return;
+        }
+        node.tag = "REFN".to_string();
+        if let Some(id_type) = id_type {
+            node.children.insert(0, self.text_node("TYPE", &id_type));
+        }
+    }
+
+    /// Converts a citation of an unknown source to a source described in place
+    fn downgrade_void_citation(&mut self, node: &mut GedcomNode) {
+        node.value = take_child(node, "PAGE").and_then(|page| page.value);
+        if let Some(mut data) = take_child(node, "DATA") {
+            while let Some(text) = take_child(&mut data, "TEXT") {
+                node.children.push(text);
+            }
+            if !data.children.is_empty() {
+                self.lose(
+                    &data,
+                    "GEDCOM 5.5.1 sources described in place have no data, dropping it",
+                );
+            }
+        }
+    }
+
+    /// Converts a GEDCOM 7 date and its `PHRASE` to a GEDCOM 5.5.1 date
+    fn downgrade_date(&mut self, node: &mut GedcomNode) {
+        let date = node.value.take().unwrap_or_default();
+        let phrase = take_child(node, "PHRASE").and_then(|phrase| phrase.value);
+        let Some(date) = date_tokens_v5(&date) else {
+            self.lose(
+                node,
+                &format!("{date} is not a GEDCOM 5.5.1 date, keeping it as a phrase"),
+            );
+            node.value = Some(format!("({})", phrase.unwrap_or(date)));
+            return;
+        };
+        node.value = Some(match phrase {
+            None => date,
+            Some(phrase) if date.is_empty() => format!("({phrase})"),
+            Some(phrase) if is_exact_date(&date) => format!("INT {date} ({phrase})"),
+            Some(_) => {
+                self.lose(
+                    node,
+                    "GEDCOM 5.5.1 only has phrases for exact dates, dropping it",
+                );
+                date
+            }
+        });
+    }
+
+    /// Converts the media type of a multimedia `FORM` to a file format. A media type
+    /// without a known format is written as its subtype, _ie._ `x-raw` for `image/x-raw`.
+    fn downgrade_media_type(&mut self, node: &mut GedcomNode) {
+        let Some(media_type) = node.value.take() else {
+            return;
+        };
+        let known = MEDIA_TYPES
+            .iter()
+            .find(|(_, known)| *known == media_type)
+            .map(|(extension, _)| (*extension).to_string());
+        node.value = Some(known.unwrap_or_else(|| {
+            let format = media_type
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            self.lose(
+                node,
+                &format!("GEDCOM 5.5.1 has no format for {media_type}, writing it as {format}"),
+            );
+            format
+        }));
+    }
+
+    /// Converts a GEDCOM 7 age and its `PHRASE` to a GEDCOM 5.5.1 age
+    fn downgrade_age(&mut self, node: &mut GedcomNode) -> bool {
+        let age = node.value.take().unwrap_or_default();
+        let phrase = take_child(node, "PHRASE").and_then(|phrase| phrase.value);
+        let keyword = match (
+            age.as_str(),
+            phrase.as_deref().map(str::to_uppercase).as_deref(),
+        ) {
+            ("< 8y", Some("CHILD")) => Some("CHILD"),
+            ("< 1y", Some("INFANT")) => Some("INFANT"),
+            ("0y", Some("STILLBORN")) => Some("STILLBORN"),
+            _ => None,
+        };
+        if let Some(keyword) = keyword {
+            node.value = Some(keyword.to_string());
+            return true;
+        }
+        let Some(duration) = age_duration(&age).and_then(weeks_as_days) else {
+            self.lose(
+                node,
+                "GEDCOM 5.5.1 has no age without a duration, dropping it",
+            );
+            return false;
+        };
+        if phrase.is_some() {
+            self.lose(node, "GEDCOM 5.5.1 has no phrases for ages, dropping it");
+        }
+        node.value = Some(format_age(duration, false));
+        true
+    }
+
+    /// Converts the language tag of a `LANG` to a language name, returning `false` when
+    /// GEDCOM 5.5.1 has no name for it
+    fn downgrade_language(&mut self, node: &mut GedcomNode) -> bool {
+        let tag = node.value.take().unwrap_or_default();
+        let primary = tag.split('-').next().unwrap_or_default();
+        let name = LANGUAGES
+            .iter()
+            .find(|(_, known)| known.eq_ignore_ascii_case(primary))
+            .map(|(name, _)| (*name).to_string());
+        if name.is_none() {
+            self.lose(
+                node,
+                &format!("GEDCOM 5.5.1 has no name for the language {tag}, dropping it"),
+            );
+        }
+        let known = name.is_some();
+        node.value = name;
+        known
+    }
+
+    /// Writes an enumerated value in lowercase, returning `false` when it is not one of
+    /// `values` and GEDCOM 5.5.1 allows no others
+    fn downgrade_enumeration(
+        &mut self,
+        node: &mut GedcomNode,
+        values: &[&str],
+        open: bool,
+    ) -> bool {
+        let value = node.value.take().unwrap_or_default();
+        let phrase = take_child(node, "PHRASE").and_then(|phrase| phrase.value);
+        if values.contains(&value.as_str()) {
+            node.value = Some(value.to_lowercase());
+        } else if open {
+            node.value = Some(phrase.unwrap_or_else(|| value.to_lowercase()));
+        } else {
+            let value = phrase.unwrap_or(value);
+            self.lose(
+                node,
+                &format!(
+                    "{value} is not a GEDCOM 5.5.1 value for {}, dropping it",
+                    node.tag
+                ),
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Escapes the value of a converted line for the target version
+    fn finish_line(&self, node: &mut GedcomNode) {
+        // dates are built for the target version, escape sequences and all
+        if node.tag == "DATE" {
+            return;
+        }
+        if let Some(value) = &mut node.value {
+            *value = escape_text(self.target, value);
+        }
+    }
+
+    /// A new line with an escaped text value
+    fn text_node(&self, tag: &str, text: &str) -> GedcomNode {
+        GedcomNode {
+            value: Some(escape_text(self.target, text)),
+            ..GedcomNode::new(0, tag)
+        }
+    }
+
+    /// Adds a `PHRASE` as the first subordinate line of `node`
+    fn push_phrase(&self, node: &mut GedcomNode, phrase: Option<String>) {
+        if let Some(phrase) = phrase {
+            node.children.insert(0, self.text_node("PHRASE", &phrase));
+        }
+    }
+
+    /// Reports data lost on the line of `node`
+    fn lose(&mut self, node: &GedcomNode, message: &str) {
+        self.report(node, DiagnosticKind::LossyConversion, message.to_string());
+    }
+
+    fn report(&mut self, node: &GedcomNode, kind: DiagnosticKind, message: String) {
+        let mut tag_path = self.tag_path.clone();
+        if tag_path.last() != Some(&node.tag) {
+            tag_path.push(node.tag.clone());
+        }
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            line: node.line,
+            tag_path,
+            xref: self.record_xref.clone(),
+            message,
+            kind,
+        });
+    }
+}
+
+/// Sets the version of a header to 7.0, leaving out the structures GEDCOM 7 dropped
+fn upgrade_header(node: &mut GedcomNode) {
+    let gedc = child_or_insert(node, "GEDC", 0);
+    gedc.children
+        .retain(|child| child.tag != "FORM" && child.tag != "VERS");
+    gedc.children.insert(0, version_node("7.0"));
+}
+
+/// Sets the version of a header to 5.5.1, with the form and character set GEDCOM 5.5.1
+/// requires
+fn downgrade_header(node: &mut GedcomNode) {
+    let gedc = child_or_insert(node, "GEDC", 0);
+    gedc.children
+        .retain(|child| child.tag != "FORM" && child.tag != "VERS");
+    gedc.children.insert(0, version_node("5.5.1"));
+    gedc.children.insert(
+        1,
+        GedcomNode {
+            value: Some("LINEAGE-LINKED".to_string()),
+            ..GedcomNode::new(2, "FORM")
+        },
+    );
+    node.children.retain(|child| child.tag != "CHAR");
+    let after_gedc = node
+        .children
+        .iter()
+        .position(|child| child.tag == "GEDC")
+        .map_or(0, |i| i + 1);
+    node.children.insert(
+        after_gedc,
+        GedcomNode {
+            value: Some("UTF-8".to_string()),
+            ..GedcomNode::new(1, "CHAR")
+        },
+    );
+}
+
+fn version_node(version: &str) -> GedcomNode {
+    GedcomNode {
+        value: Some(version.to_string()),
+        ..GedcomNode::new(2, "VERS")
+    }
+}
+
+/// Converts a GEDCOM 7 `ROLE` of an event to one of the roles of GEDCOM 5.5.1, or a
+/// role described in parentheses
+fn downgrade_role(node: &mut GedcomNode) {
+    let role = node.value.take().unwrap_or_default();
+    let phrase = take_child(node, "PHRASE").and_then(|phrase| phrase.value);
+    node.value = Some(if ROLES_V5.contains(&role.as_str()) && phrase.is_none() {
+        role
+    } else {
+        format!("({})", phrase.unwrap_or_else(|| title_case(&role)))
+    });
+}
+
+/// The first subordinate line of `node` with the given tag, added at `index` when
+/// there is none
+fn child_or_insert<'n>(node: &'n mut GedcomNode, tag: &str, index: usize) -> &'n mut GedcomNode {
+    let i = node
+        .children
+        .iter()
+        .position(|child| child.tag == tag)
+        .unwrap_or_else(|| {
+            node.children
+                .insert(index, GedcomNode::new(node.level + 1, tag));
+            index
+        });
+    &mut node.children[i]
+}
+
+/// The version a header says its file follows
+fn header_version(header: &GedcomNode) -> GedcomVersion {
+    header
+        .child("GEDC")
+        .and_then(|gedc| gedc.child("VERS"))
+        .and_then(|version| version.value.as_deref())
+        .map_or(GedcomVersion::default(), GedcomVersion::from_number)
+}
+
+/// Merges the `CONT` and `CONC` lines of `node` into its value, with the escapes of
+/// `version` undone. Pointers are left as they are.
+fn join_continuations(node: &mut GedcomNode, version: GedcomVersion) {
+    let continued = node
+        .children
+        .iter()
+        .any(|child| child.tag == "CONT" || child.tag == "CONC");
+    if node.value.as_deref().is_some_and(is_pointer) || (node.value.is_none() && !continued) {
+        return;
+    }
+    let mut text = unescape(version, node.value.as_deref().unwrap_or_default());
+    node.children.retain(|child| match child.tag.as_str() {
+        "CONT" | "CONC" => {
+            if child.tag == "CONT" {
+                text.push('\n');
+            }
+            text.push_str(&unescape(
+                version,
+                child.value.as_deref().unwrap_or_default(),
+            ));
+            false
+        }
+        _ => true,
+    });
+    node.value = Some(text);
+}
+
+/// Escapes the `@` signs of a text value: all of them for GEDCOM 5.5.1, only a leading
+/// one for GEDCOM 7
+fn escape_text(version: GedcomVersion, text: &str) -> String {
+    match version {
+        GedcomVersion::V5 => escape(text),
+        GedcomVersion::V7 if text.starts_with('@') && !is_pointer(text) => format!("@{text}"),
+        GedcomVersion::V7 => text.to_string(),
+    }
+}
+
+/// Removes and returns the first subordinate line of `node` with the given tag
+fn take_child(node: &mut GedcomNode, tag: &str) -> Option<GedcomNode> {
+    let i = node.children.iter().position(|child| child.tag == tag)?;
+    Some(node.children.remove(i))
+}
+
+/// Sets the levels of `node` and its descendants from their depth, and marks them as new
+/// so the writer writes them from their values
+fn renumber(node: &mut GedcomNode, level: u8) {
+    node.level = level;
+    node.line = 0;
+    node.raw = None;
+    for child in &mut node.children {
+        renumber(child, level + 1);
+    }
+}
+
+/// Moves the title and format of a GEDCOM 5.5 multimedia link under its `FILE`, where
+/// GEDCOM 7 has them
+fn restructure_media(node: &mut GedcomNode) {
+    let Some(file) = node.children.iter().position(|child| child.tag == "FILE") else {
+        return;
+    };
+    for tag in ["FORM", "TITL"] {
+        if node.children[file].child(tag).is_some() {
+            continue;
+        }
+        if let Some(i) = node.children.iter().position(|child| child.tag == tag) {
+            let moved = node.children.remove(i);
+            let file = node.children.iter_mut().find(|child| child.tag == "FILE");
+            if let Some(file) = file {
+                file.children.push(moved);
+            }
+        }
+    }
+}
+
+/// Splits an interpreted date, `INT <date> (<phrase>)`, into its date and phrase
+fn interpreted_date(text: &str) -> Option<(&str, &str)> {
+    let rest = text
+        .get(..4)?
+        .eq_ignore_ascii_case("INT ")
+        .then(|| &text[4..])?;
+    let open = rest.find('(')?;
+    let phrase = rest[open + 1..].trim_end().strip_suffix(')')?;
+    Some((rest[..open].trim(), phrase))
+}
+
+/// The words of a GEDCOM 5.5.1 date written for GEDCOM 7, along with whether a dual
+/// year had to be replaced, or `None` when it is not a date
+fn date_tokens_v7(date: &str) -> Option<(String, bool)> {
+    let date = date.to_uppercase().replace("@#DFRENCH R@", "FRENCH_R");
+    let mut dual_year = false;
+    let mut words = Vec::new();
+    for word in date.split_whitespace() {
+        let word = match word {
+            "@#DGREGORIAN@" => "GREGORIAN".to_string(),
+            "@#DJULIAN@" => "JULIAN".to_string(),
+            "@#DHEBREW@" => "HEBREW".to_string(),
+            "ABOUT" | "CIRCA" => "ABT".to_string(),
+            "BEFORE" => "BEF".to_string(),
+            "AFTER" => "AFT".to_string(),
+            "BETWEEN" => "BET".to_string(),
+            "B.C." | "BC" => "BCE".to_string(),
+            _ if DATE_WORDS.contains(&word) || MONTHS.contains(&word) => word.to_string(),
+            _ if word.chars().all(|c| c.is_ascii_digit()) => word.to_string(),
+            _ if month_abbreviation(word).is_some() => {
+                month_abbreviation(word).unwrap_or_default().to_string()
+            }
+            _ => {
+                // a dual year, _ie._ 1699/00, is dated by its later year
+                let (year, next) = word.split_once('/')?;
+                let year: u32 = year.parse().ok()?;
+                let later = year.checked_add(1)?;
+                if next.len() != 2 || format!("{:02}", later % 100) != next {
+                    return None;
+                }
+                dual_year = true;
+                later.to_string()
+            }
+        };
+        words.push(word);
+    }
+    Some((words.join(" "), dual_year))
+}
+
+/// The words of a GEDCOM 7 date written for GEDCOM 5.5.1, or `None` when it uses a
+/// calendar GEDCOM 5.5.1 does not have
+fn date_tokens_v5(date: &str) -> Option<String> {
+    let mut words = Vec::new();
+    for word in date.split_whitespace() {
+        words.push(match word {
+            "GREGORIAN" => "@#DGREGORIAN@",
+            "JULIAN" => "@#DJULIAN@",
+            "HEBREW" => "@#DHEBREW@",
+            "FRENCH_R" => "@#DFRENCH R@",
+            "BCE" => "B.C.",
+            _ if word.starts_with('_') => return None,
+            _ => word,
+        });
+    }
+    Some(words.join(" "))
+}
+
+/// The abbreviation of a Gregorian month written out in full or abbreviated with a
+/// period, _ie._ `JAN` for `JANUARY` or `JAN.`
+fn month_abbreviation(word: &str) -> Option<&'static str> {
+    let word = word.trim_end_matches('.');
+    if word.len() < 3 {
+        return None;
+    }
+    MONTH_NAMES
+        .iter()
+        .position(|name| name.starts_with(word))
+        .map(|i| MONTHS[i])
+}
+
+/// Whether a date is a single date, rather than a range, period or approximation
+fn is_exact_date(date: &str) -> bool {
+    !date
+        .split_whitespace()
+        .any(|word| ["ABT", "CAL", "EST", "BEF", "AFT", "BET", "FROM", "TO"].contains(&word))
+}
+
+/// The duration of an age at an event, or `None` when it is not one
+fn age_duration(text: &str) -> Option<AgeDuration> {
+    match text.parse() {
+        Ok(AgeValue::Duration(duration)) => Some(duration),
+        _ => None,
+    }
+}
+
+/// Counts the weeks of an age as days, as GEDCOM 5.5.1 has no unit for them
+fn weeks_as_days(mut duration: AgeDuration) -> Option<AgeDuration> {
+    if let Some(weeks) = duration.weeks.take() {
+        let days = weeks
+            .checked_mul(7)?
+            .checked_add(duration.days.unwrap_or_default())?;
+        duration.days = Some(days);
+    }
+    Some(duration)
+}
+
+/// Writes an age, with a space after the bound for GEDCOM 7
+fn format_age(duration: AgeDuration, spaced: bool) -> String {
+    let age = duration.to_string();
+    match duration.bound {
+        Some(_) if spaced => format!("{} {}", &age[..1], &age[1..]),
+        _ => age,
+    }
+}
+
+/// `CLERGY` as `Clergy`
+fn title_case(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let mut chars = lower.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// The keywords of a date, other than months
+const DATE_WORDS: &[&str] = &[
+    "ABT",
+    "CAL",
+    "EST",
+    "BEF",
+    "AFT",
+    "BET",
+    "AND",
+    "FROM",
+    "TO",
+    "GREGORIAN",
+    "JULIAN",
+    "HEBREW",
+    "FRENCH_R",
+    "BCE",
+];
+
+/// The months of the Gregorian, Julian, Hebrew and French republican calendars
+const MONTHS: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC", "TSH",
+    "CSH", "KSL", "TVT", "SHV", "ADR", "ADS", "NSN", "IYR", "SVN", "TMZ", "AAV", "ELL", "VEND",
+    "BRUM", "FRIM", "NIVO", "PLUV", "VENT", "GERM", "FLOR", "PRAI", "MESS", "THER", "FRUC", "COMP",
+];
+
+const MONTH_NAMES: &[&str] = &[
+    "JANUARY",
+    "FEBRUARY",
+    "MARCH",
+    "APRIL",
+    "MAY",
+    "JUNE",
+    "JULY",
+    "AUGUST",
+    "SEPTEMBER",
+    "OCTOBER",
+    "NOVEMBER",
+    "DECEMBER",
+];
+
+/// The roles of an individual in an event that GEDCOM 5.5.1 has
+const ROLES_V5: &[&str] = &["CHIL", "HUSB", "WIFE", "MOTH", "FATH", "SPOU"];
+
+const PEDIGREES: &[&str] = &["ADOPTED", "BIRTH", "FOSTER", "SEALING"];
+
+const NAME_TYPES: &[&str] = &["AKA", "BIRTH", "IMMIGRANT", "MAIDEN", "MARRIED"];
+
+const MEDIA: &[&str] = &[
+    "AUDIO",
+    "BOOK",
+    "CARD",
+    "ELECTRONIC",
+    "FICHE",
+    "FILM",
+    "MAGAZINE",
+    "MANUSCRIPT",
+    "MAP",
+    "NEWSPAPER",
+    "PHOTO",
+    "TOMBSTONE",
+    "VIDEO",
+];
+
+/// The multimedia formats of GEDCOM 5.5.1 and their media types
+const MEDIA_TYPES: &[(&str, &str)] = &[
+    ("bmp", "image/bmp"),
+    ("gif", "image/gif"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("pdf", "application/pdf"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("mpg", "video/mpeg"),
+    ("avi", "video/x-msvideo"),
+];
+
+/// The language names of GEDCOM 5.5.1 and their language tags
+const LANGUAGES: &[(&str, &str)] = &[
+    ("Afrikaans", "af"),
+    ("Albanian", "sq"),
+    ("Amharic", "am"),
+    ("Anglo-Saxon", "ang"),
+    ("Arabic", "ar"),
+    ("Armenian", "hy"),
+    ("Assamese", "as"),
+    ("Belorusian", "be"),
+    ("Bengali", "bn"),
+    ("Braj", "bra"),
+    ("Bulgarian", "bg"),
+    ("Burmese", "my"),
+    ("Cantonese", "yue"),
+    ("Catalan", "ca"),
+    ("Catalan_Spn", "ca"),
+    ("Church-Slavic", "cu"),
+    ("Czech", "cs"),
+    ("Danish", "da"),
+    ("Dogri", "doi"),
+    ("Dutch", "nl"),
+    ("English", "en"),
+    ("Esperanto", "eo"),
+    ("Estonian", "et"),
+    ("Faroese", "fo"),
+    ("Finnish", "fi"),
+    ("French", "fr"),
+    ("Georgian", "ka"),
+    ("German", "de"),
+    ("Greek", "el"),
+    ("Gujarati", "gu"),
+    ("Hawaiian", "haw"),
+    ("Hebrew", "he"),
+    ("Hindi", "hi"),
+    ("Hungarian", "hu"),
+    ("Icelandic", "is"),
+    ("Indonesian", "id"),
+    ("Italian", "it"),
+    ("Japanese", "ja"),
+    ("Kannada", "kn"),
+    ("Khmer", "km"),
+    ("Konkani", "kok"),
+    ("Korean", "ko"),
+    ("Lahnda", "lah"),
+    ("Lao", "lo"),
+    ("Latvian", "lv"),
+    ("Lithuanian", "lt"),
+    ("Macedonian", "mk"),
+    ("Maithili", "mai"),
+    ("Malayalam", "ml"),
+    ("Mandrin", "cmn"),
+    ("Manipuri", "mni"),
+    ("Marathi", "mr"),
+    ("Mewari", "mtr"),
+    ("Navaho", "nv"),
+    ("Nepali", "ne"),
+    ("Norwegian", "no"),
+    ("Oriya", "or"),
+    ("Pali", "pi"),
+    ("Panjabi", "pa"),
+    ("Persian", "fa"),
+    ("Polish", "pl"),
+    ("Portuguese", "pt"),
+    ("Prakrit", "pra"),
+    ("Pusto", "ps"),
+    ("Rajasthani", "raj"),
+    ("Romanian", "ro"),
+    ("Russian", "ru"),
+    ("Sanskrit", "sa"),
+    ("Serb", "sr"),
+    ("Serbo_Croa", "sh"),
+    ("Slovak", "sk"),
+    ("Slovene", "sl"),
+    ("Spanish", "es"),
+    ("Swedish", "sv"),
+    ("Tagalog", "tl"),
+    ("Tamil", "ta"),
+    ("Telugu", "te"),
+    ("Thai", "th"),
+    ("Tibetan", "bo"),
+    ("Turkish", "tr"),
+    ("Ukrainian", "uk"),
+    ("Urdu", "ur"),
+    ("Vietnamese", "vi"),
+    ("Wendic", "wen"),
+    ("Yiddish", "yi"),
+];
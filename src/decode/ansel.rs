@@ -0,0 +1,286 @@
+//! Tables for the ANSEL character set (ANSI Z39.47) used by GEDCOM 5.5.1
+
+/// The spacing characters of the upper half of ANSEL, by byte, including the
+/// GEDCOM additions of the empty and black boxes, midline e and o, and eszett
+#[rustfmt::skip]
+const SPACING: [(u8, char); 41] = [
+    (0xA1, '\u{0141}'), (0xA2, '\u{00D8}'), (0xA3, '\u{0110}'), (0xA4, '\u{00DE}'),
+    (0xA5, '\u{00C6}'), (0xA6, '\u{0152}'), (0xA7, '\u{02B9}'), (0xA8, '\u{00B7}'),
+    (0xA9, '\u{266D}'), (0xAA, '\u{00AE}'), (0xAB, '\u{00B1}'), (0xAC, '\u{01A0}'),
+    (0xAD, '\u{01AF}'), (0xAE, '\u{02BC}'), (0xB0, '\u{02BB}'), (0xB1, '\u{0142}'),
+    (0xB2, '\u{00F8}'), (0xB3, '\u{0111}'), (0xB4, '\u{00FE}'), (0xB5, '\u{00E6}'),
+    (0xB6, '\u{0153}'), (0xB7, '\u{02BA}'), (0xB8, '\u{0131}'), (0xB9, '\u{00A3}'),
+    (0xBA, '\u{00F0}'), (0xBC, '\u{01A1}'), (0xBD, '\u{01B0}'), (0xBE, '\u{25A1}'),
+    (0xBF, '\u{25A0}'), (0xC0, '\u{00B0}'), (0xC1, '\u{2113}'), (0xC2, '\u{2117}'),
+    (0xC3, '\u{00A9}'), (0xC4, '\u{266F}'), (0xC5, '\u{00BF}'), (0xC6, '\u{00A1}'),
+    (0xC7, '\u{00DF}'), (0xC8, '\u{20AC}'), (0xCD, '\u{0065}'), (0xCE, '\u{006F}'),
+    (0xCF, '\u{00DF}'),
+];
+
+/// The combining diacritics of ANSEL, by byte, with their canonical combining class
+#[rustfmt::skip]
+const COMBINING: [(u8, char, u8); 29] = [
+    (0xE0, '\u{0309}', 230), (0xE1, '\u{0300}', 230), (0xE2, '\u{0301}', 230),
+    (0xE3, '\u{0302}', 230), (0xE4, '\u{0303}', 230), (0xE5, '\u{0304}', 230),
+    (0xE6, '\u{0306}', 230), (0xE7, '\u{0307}', 230), (0xE8, '\u{0308}', 230),
+    (0xE9, '\u{030C}', 230), (0xEA, '\u{030A}', 230), (0xEB, '\u{FE20}', 230),
+    (0xEC, '\u{FE21}', 230), (0xED, '\u{0315}', 232), (0xEE, '\u{030B}', 230),
+    (0xEF, '\u{0310}', 230), (0xF0, '\u{0327}', 202), (0xF1, '\u{0328}', 202),
+    (0xF2, '\u{0323}', 220), (0xF3, '\u{0324}', 220), (0xF4, '\u{0325}', 220),
+    (0xF5, '\u{0333}', 220), (0xF6, '\u{0332}', 220), (0xF7, '\u{0326}', 220),
+    (0xF8, '\u{031C}', 220), (0xF9, '\u{032E}', 220), (0xFA, '\u{FE22}', 230),
+    (0xFB, '\u{FE23}', 230), (0xFE, '\u{0313}', 230),
+];
+
+/// Canonical compositions of a character and a combining diacritic, sorted by both
+#[rustfmt::skip]
+const COMPOSITIONS: [(char, char, char); 447] = [
+    ('\u{0041}', '\u{0300}', '\u{00C0}'), ('\u{0041}', '\u{0301}', '\u{00C1}'),
+    ('\u{0041}', '\u{0302}', '\u{00C2}'), ('\u{0041}', '\u{0303}', '\u{00C3}'),
+    ('\u{0041}', '\u{0304}', '\u{0100}'), ('\u{0041}', '\u{0306}', '\u{0102}'),
+    ('\u{0041}', '\u{0307}', '\u{0226}'), ('\u{0041}', '\u{0308}', '\u{00C4}'),
+    ('\u{0041}', '\u{0309}', '\u{1EA2}'), ('\u{0041}', '\u{030A}', '\u{00C5}'),
+    ('\u{0041}', '\u{030C}', '\u{01CD}'), ('\u{0041}', '\u{0323}', '\u{1EA0}'),
+    ('\u{0041}', '\u{0325}', '\u{1E00}'), ('\u{0041}', '\u{0328}', '\u{0104}'),
+    ('\u{0042}', '\u{0307}', '\u{1E02}'), ('\u{0042}', '\u{0323}', '\u{1E04}'),
+    ('\u{0043}', '\u{0301}', '\u{0106}'), ('\u{0043}', '\u{0302}', '\u{0108}'),
+    ('\u{0043}', '\u{0307}', '\u{010A}'), ('\u{0043}', '\u{030C}', '\u{010C}'),
+    ('\u{0043}', '\u{0327}', '\u{00C7}'), ('\u{0044}', '\u{0307}', '\u{1E0A}'),
+    ('\u{0044}', '\u{030C}', '\u{010E}'), ('\u{0044}', '\u{0323}', '\u{1E0C}'),
+    ('\u{0044}', '\u{0327}', '\u{1E10}'), ('\u{0045}', '\u{0300}', '\u{00C8}'),
+    ('\u{0045}', '\u{0301}', '\u{00C9}'), ('\u{0045}', '\u{0302}', '\u{00CA}'),
+    ('\u{0045}', '\u{0303}', '\u{1EBC}'), ('\u{0045}', '\u{0304}', '\u{0112}'),
+    ('\u{0045}', '\u{0306}', '\u{0114}'), ('\u{0045}', '\u{0307}', '\u{0116}'),
+    ('\u{0045}', '\u{0308}', '\u{00CB}'), ('\u{0045}', '\u{0309}', '\u{1EBA}'),
+    ('\u{0045}', '\u{030C}', '\u{011A}'), ('\u{0045}', '\u{0323}', '\u{1EB8}'),
+    ('\u{0045}', '\u{0327}', '\u{0228}'), ('\u{0045}', '\u{0328}', '\u{0118}'),
+    ('\u{0046}', '\u{0307}', '\u{1E1E}'), ('\u{0047}', '\u{0301}', '\u{01F4}'),
+    ('\u{0047}', '\u{0302}', '\u{011C}'), ('\u{0047}', '\u{0304}', '\u{1E20}'),
+    ('\u{0047}', '\u{0306}', '\u{011E}'), ('\u{0047}', '\u{0307}', '\u{0120}'),
+    ('\u{0047}', '\u{030C}', '\u{01E6}'), ('\u{0047}', '\u{0327}', '\u{0122}'),
+    ('\u{0048}', '\u{0302}', '\u{0124}'), ('\u{0048}', '\u{0307}', '\u{1E22}'),
+    ('\u{0048}', '\u{0308}', '\u{1E26}'), ('\u{0048}', '\u{030C}', '\u{021E}'),
+    ('\u{0048}', '\u{0323}', '\u{1E24}'), ('\u{0048}', '\u{0327}', '\u{1E28}'),
+    ('\u{0048}', '\u{032E}', '\u{1E2A}'), ('\u{0049}', '\u{0300}', '\u{00CC}'),
+    ('\u{0049}', '\u{0301}', '\u{00CD}'), ('\u{0049}', '\u{0302}', '\u{00CE}'),
+    ('\u{0049}', '\u{0303}', '\u{0128}'), ('\u{0049}', '\u{0304}', '\u{012A}'),
+    ('\u{0049}', '\u{0306}', '\u{012C}'), ('\u{0049}', '\u{0307}', '\u{0130}'),
+    ('\u{0049}', '\u{0308}', '\u{00CF}'), ('\u{0049}', '\u{0309}', '\u{1EC8}'),
+    ('\u{0049}', '\u{030C}', '\u{01CF}'), ('\u{0049}', '\u{0323}', '\u{1ECA}'),
+    ('\u{0049}', '\u{0328}', '\u{012E}'), ('\u{004A}', '\u{0302}', '\u{0134}'),
+    ('\u{004B}', '\u{0301}', '\u{1E30}'), ('\u{004B}', '\u{030C}', '\u{01E8}'),
+    ('\u{004B}', '\u{0323}', '\u{1E32}'), ('\u{004B}', '\u{0327}', '\u{0136}'),
+    ('\u{004C}', '\u{0301}', '\u{0139}'), ('\u{004C}', '\u{030C}', '\u{013D}'),
+    ('\u{004C}', '\u{0323}', '\u{1E36}'), ('\u{004C}', '\u{0327}', '\u{013B}'),
+    ('\u{004D}', '\u{0301}', '\u{1E3E}'), ('\u{004D}', '\u{0307}', '\u{1E40}'),
+    ('\u{004D}', '\u{0323}', '\u{1E42}'), ('\u{004E}', '\u{0300}', '\u{01F8}'),
+    ('\u{004E}', '\u{0301}', '\u{0143}'), ('\u{004E}', '\u{0303}', '\u{00D1}'),
+    ('\u{004E}', '\u{0307}', '\u{1E44}'), ('\u{004E}', '\u{030C}', '\u{0147}'),
+    ('\u{004E}', '\u{0323}', '\u{1E46}'), ('\u{004E}', '\u{0327}', '\u{0145}'),
+    ('\u{004F}', '\u{0300}', '\u{00D2}'), ('\u{004F}', '\u{0301}', '\u{00D3}'),
+    ('\u{004F}', '\u{0302}', '\u{00D4}'), ('\u{004F}', '\u{0303}', '\u{00D5}'),
+    ('\u{004F}', '\u{0304}', '\u{014C}'), ('\u{004F}', '\u{0306}', '\u{014E}'),
+    ('\u{004F}', '\u{0307}', '\u{022E}'), ('\u{004F}', '\u{0308}', '\u{00D6}'),
+    ('\u{004F}', '\u{0309}', '\u{1ECE}'), ('\u{004F}', '\u{030B}', '\u{0150}'),
+    ('\u{004F}', '\u{030C}', '\u{01D1}'), ('\u{004F}', '\u{0323}', '\u{1ECC}'),
+    ('\u{004F}', '\u{0328}', '\u{01EA}'), ('\u{0050}', '\u{0301}', '\u{1E54}'),
+    ('\u{0050}', '\u{0307}', '\u{1E56}'), ('\u{0052}', '\u{0301}', '\u{0154}'),
+    ('\u{0052}', '\u{0307}', '\u{1E58}'), ('\u{0052}', '\u{030C}', '\u{0158}'),
+    ('\u{0052}', '\u{0323}', '\u{1E5A}'), ('\u{0052}', '\u{0327}', '\u{0156}'),
+    ('\u{0053}', '\u{0301}', '\u{015A}'), ('\u{0053}', '\u{0302}', '\u{015C}'),
+    ('\u{0053}', '\u{0307}', '\u{1E60}'), ('\u{0053}', '\u{030C}', '\u{0160}'),
+    ('\u{0053}', '\u{0323}', '\u{1E62}'), ('\u{0053}', '\u{0326}', '\u{0218}'),
+    ('\u{0053}', '\u{0327}', '\u{015E}'), ('\u{0054}', '\u{0307}', '\u{1E6A}'),
+    ('\u{0054}', '\u{030C}', '\u{0164}'), ('\u{0054}', '\u{0323}', '\u{1E6C}'),
+    ('\u{0054}', '\u{0326}', '\u{021A}'), ('\u{0054}', '\u{0327}', '\u{0162}'),
+    ('\u{0055}', '\u{0300}', '\u{00D9}'), ('\u{0055}', '\u{0301}', '\u{00DA}'),
+    ('\u{0055}', '\u{0302}', '\u{00DB}'), ('\u{0055}', '\u{0303}', '\u{0168}'),
+    ('\u{0055}', '\u{0304}', '\u{016A}'), ('\u{0055}', '\u{0306}', '\u{016C}'),
+    ('\u{0055}', '\u{0308}', '\u{00DC}'), ('\u{0055}', '\u{0309}', '\u{1EE6}'),
+    ('\u{0055}', '\u{030A}', '\u{016E}'), ('\u{0055}', '\u{030B}', '\u{0170}'),
+    ('\u{0055}', '\u{030C}', '\u{01D3}'), ('\u{0055}', '\u{0323}', '\u{1EE4}'),
+    ('\u{0055}', '\u{0324}', '\u{1E72}'), ('\u{0055}', '\u{0328}', '\u{0172}'),
+    ('\u{0056}', '\u{0303}', '\u{1E7C}'), ('\u{0056}', '\u{0323}', '\u{1E7E}'),
+    ('\u{0057}', '\u{0300}', '\u{1E80}'), ('\u{0057}', '\u{0301}', '\u{1E82}'),
+    ('\u{0057}', '\u{0302}', '\u{0174}'), ('\u{0057}', '\u{0307}', '\u{1E86}'),
+    ('\u{0057}', '\u{0308}', '\u{1E84}'), ('\u{0057}', '\u{0323}', '\u{1E88}'),
+    ('\u{0058}', '\u{0307}', '\u{1E8A}'), ('\u{0058}', '\u{0308}', '\u{1E8C}'),
+    ('\u{0059}', '\u{0300}', '\u{1EF2}'), ('\u{0059}', '\u{0301}', '\u{00DD}'),
+    ('\u{0059}', '\u{0302}', '\u{0176}'), ('\u{0059}', '\u{0303}', '\u{1EF8}'),
+    ('\u{0059}', '\u{0304}', '\u{0232}'), ('\u{0059}', '\u{0307}', '\u{1E8E}'),
+    ('\u{0059}', '\u{0308}', '\u{0178}'), ('\u{0059}', '\u{0309}', '\u{1EF6}'),
+    ('\u{0059}', '\u{0323}', '\u{1EF4}'), ('\u{005A}', '\u{0301}', '\u{0179}'),
+    ('\u{005A}', '\u{0302}', '\u{1E90}'), ('\u{005A}', '\u{0307}', '\u{017B}'),
+    ('\u{005A}', '\u{030C}', '\u{017D}'), ('\u{005A}', '\u{0323}', '\u{1E92}'),
+    ('\u{0061}', '\u{0300}', '\u{00E0}'), ('\u{0061}', '\u{0301}', '\u{00E1}'),
+    ('\u{0061}', '\u{0302}', '\u{00E2}'), ('\u{0061}', '\u{0303}', '\u{00E3}'),
+    ('\u{0061}', '\u{0304}', '\u{0101}'), ('\u{0061}', '\u{0306}', '\u{0103}'),
+    ('\u{0061}', '\u{0307}', '\u{0227}'), ('\u{0061}', '\u{0308}', '\u{00E4}'),
+    ('\u{0061}', '\u{0309}', '\u{1EA3}'), ('\u{0061}', '\u{030A}', '\u{00E5}'),
+    ('\u{0061}', '\u{030C}', '\u{01CE}'), ('\u{0061}', '\u{0323}', '\u{1EA1}'),
+    ('\u{0061}', '\u{0325}', '\u{1E01}'), ('\u{0061}', '\u{0328}', '\u{0105}'),
+    ('\u{0062}', '\u{0307}', '\u{1E03}'), ('\u{0062}', '\u{0323}', '\u{1E05}'),
+    ('\u{0063}', '\u{0301}', '\u{0107}'), ('\u{0063}', '\u{0302}', '\u{0109}'),
+    ('\u{0063}', '\u{0307}', '\u{010B}'), ('\u{0063}', '\u{030C}', '\u{010D}'),
+    ('\u{0063}', '\u{0327}', '\u{00E7}'), ('\u{0064}', '\u{0307}', '\u{1E0B}'),
+    ('\u{0064}', '\u{030C}', '\u{010F}'), ('\u{0064}', '\u{0323}', '\u{1E0D}'),
+    ('\u{0064}', '\u{0327}', '\u{1E11}'), ('\u{0065}', '\u{0300}', '\u{00E8}'),
+    ('\u{0065}', '\u{0301}', '\u{00E9}'), ('\u{0065}', '\u{0302}', '\u{00EA}'),
+    ('\u{0065}', '\u{0303}', '\u{1EBD}'), ('\u{0065}', '\u{0304}', '\u{0113}'),
+    ('\u{0065}', '\u{0306}', '\u{0115}'), ('\u{0065}', '\u{0307}', '\u{0117}'),
+    ('\u{0065}', '\u{0308}', '\u{00EB}'), ('\u{0065}', '\u{0309}', '\u{1EBB}'),
+    ('\u{0065}', '\u{030C}', '\u{011B}'), ('\u{0065}', '\u{0323}', '\u{1EB9}'),
+    ('\u{0065}', '\u{0327}', '\u{0229}'), ('\u{0065}', '\u{0328}', '\u{0119}'),
+    ('\u{0066}', '\u{0307}', '\u{1E1F}'), ('\u{0067}', '\u{0301}', '\u{01F5}'),
+    ('\u{0067}', '\u{0302}', '\u{011D}'), ('\u{0067}', '\u{0304}', '\u{1E21}'),
+    ('\u{0067}', '\u{0306}', '\u{011F}'), ('\u{0067}', '\u{0307}', '\u{0121}'),
+    ('\u{0067}', '\u{030C}', '\u{01E7}'), ('\u{0067}', '\u{0327}', '\u{0123}'),
+    ('\u{0068}', '\u{0302}', '\u{0125}'), ('\u{0068}', '\u{0307}', '\u{1E23}'),
+    ('\u{0068}', '\u{0308}', '\u{1E27}'), ('\u{0068}', '\u{030C}', '\u{021F}'),
+    ('\u{0068}', '\u{0323}', '\u{1E25}'), ('\u{0068}', '\u{0327}', '\u{1E29}'),
+    ('\u{0068}', '\u{032E}', '\u{1E2B}'), ('\u{0069}', '\u{0300}', '\u{00EC}'),
+    ('\u{0069}', '\u{0301}', '\u{00ED}'), ('\u{0069}', '\u{0302}', '\u{00EE}'),
+    ('\u{0069}', '\u{0303}', '\u{0129}'), ('\u{0069}', '\u{0304}', '\u{012B}'),
+    ('\u{0069}', '\u{0306}', '\u{012D}'), ('\u{0069}', '\u{0308}', '\u{00EF}'),
+    ('\u{0069}', '\u{0309}', '\u{1EC9}'), ('\u{0069}', '\u{030C}', '\u{01D0}'),
+    ('\u{0069}', '\u{0323}', '\u{1ECB}'), ('\u{0069}', '\u{0328}', '\u{012F}'),
+    ('\u{006A}', '\u{0302}', '\u{0135}'), ('\u{006A}', '\u{030C}', '\u{01F0}'),
+    ('\u{006B}', '\u{0301}', '\u{1E31}'), ('\u{006B}', '\u{030C}', '\u{01E9}'),
+    ('\u{006B}', '\u{0323}', '\u{1E33}'), ('\u{006B}', '\u{0327}', '\u{0137}'),
+    ('\u{006C}', '\u{0301}', '\u{013A}'), ('\u{006C}', '\u{030C}', '\u{013E}'),
+    ('\u{006C}', '\u{0323}', '\u{1E37}'), ('\u{006C}', '\u{0327}', '\u{013C}'),
+    ('\u{006D}', '\u{0301}', '\u{1E3F}'), ('\u{006D}', '\u{0307}', '\u{1E41}'),
+    ('\u{006D}', '\u{0323}', '\u{1E43}'), ('\u{006E}', '\u{0300}', '\u{01F9}'),
+    ('\u{006E}', '\u{0301}', '\u{0144}'), ('\u{006E}', '\u{0303}', '\u{00F1}'),
+    ('\u{006E}', '\u{0307}', '\u{1E45}'), ('\u{006E}', '\u{030C}', '\u{0148}'),
+    ('\u{006E}', '\u{0323}', '\u{1E47}'), ('\u{006E}', '\u{0327}', '\u{0146}'),
+    ('\u{006F}', '\u{0300}', '\u{00F2}'), ('\u{006F}', '\u{0301}', '\u{00F3}'),
+    ('\u{006F}', '\u{0302}', '\u{00F4}'), ('\u{006F}', '\u{0303}', '\u{00F5}'),
+    ('\u{006F}', '\u{0304}', '\u{014D}'), ('\u{006F}', '\u{0306}', '\u{014F}'),
+    ('\u{006F}', '\u{0307}', '\u{022F}'), ('\u{006F}', '\u{0308}', '\u{00F6}'),
+    ('\u{006F}', '\u{0309}', '\u{1ECF}'), ('\u{006F}', '\u{030B}', '\u{0151}'),
+    ('\u{006F}', '\u{030C}', '\u{01D2}'), ('\u{006F}', '\u{0323}', '\u{1ECD}'),
+    ('\u{006F}', '\u{0328}', '\u{01EB}'), ('\u{0070}', '\u{0301}', '\u{1E55}'),
+    ('\u{0070}', '\u{0307}', '\u{1E57}'), ('\u{0072}', '\u{0301}', '\u{0155}'),
+    ('\u{0072}', '\u{0307}', '\u{1E59}'), ('\u{0072}', '\u{030C}', '\u{0159}'),
+    ('\u{0072}', '\u{0323}', '\u{1E5B}'), ('\u{0072}', '\u{0327}', '\u{0157}'),
+    ('\u{0073}', '\u{0301}', '\u{015B}'), ('\u{0073}', '\u{0302}', '\u{015D}'),
+    ('\u{0073}', '\u{0307}', '\u{1E61}'), ('\u{0073}', '\u{030C}', '\u{0161}'),
+    ('\u{0073}', '\u{0323}', '\u{1E63}'), ('\u{0073}', '\u{0326}', '\u{0219}'),
+    ('\u{0073}', '\u{0327}', '\u{015F}'), ('\u{0074}', '\u{0307}', '\u{1E6B}'),
+    ('\u{0074}', '\u{0308}', '\u{1E97}'), ('\u{0074}', '\u{030C}', '\u{0165}'),
+    ('\u{0074}', '\u{0323}', '\u{1E6D}'), ('\u{0074}', '\u{0326}', '\u{021B}'),
+    ('\u{0074}', '\u{0327}', '\u{0163}'), ('\u{0075}', '\u{0300}', '\u{00F9}'),
+    ('\u{0075}', '\u{0301}', '\u{00FA}'), ('\u{0075}', '\u{0302}', '\u{00FB}'),
+    ('\u{0075}', '\u{0303}', '\u{0169}'), ('\u{0075}', '\u{0304}', '\u{016B}'),
+    ('\u{0075}', '\u{0306}', '\u{016D}'), ('\u{0075}', '\u{0308}', '\u{00FC}'),
+    ('\u{0075}', '\u{0309}', '\u{1EE7}'), ('\u{0075}', '\u{030A}', '\u{016F}'),
+    ('\u{0075}', '\u{030B}', '\u{0171}'), ('\u{0075}', '\u{030C}', '\u{01D4}'),
+    ('\u{0075}', '\u{0323}', '\u{1EE5}'), ('\u{0075}', '\u{0324}', '\u{1E73}'),
+    ('\u{0075}', '\u{0328}', '\u{0173}'), ('\u{0076}', '\u{0303}', '\u{1E7D}'),
+    ('\u{0076}', '\u{0323}', '\u{1E7F}'), ('\u{0077}', '\u{0300}', '\u{1E81}'),
+    ('\u{0077}', '\u{0301}', '\u{1E83}'), ('\u{0077}', '\u{0302}', '\u{0175}'),
+    ('\u{0077}', '\u{0307}', '\u{1E87}'), ('\u{0077}', '\u{0308}', '\u{1E85}'),
+    ('\u{0077}', '\u{030A}', '\u{1E98}'), ('\u{0077}', '\u{0323}', '\u{1E89}'),
+    ('\u{0078}', '\u{0307}', '\u{1E8B}'), ('\u{0078}', '\u{0308}', '\u{1E8D}'),
+    ('\u{0079}', '\u{0300}', '\u{1EF3}'), ('\u{0079}', '\u{0301}', '\u{00FD}'),
+    ('\u{0079}', '\u{0302}', '\u{0177}'), ('\u{0079}', '\u{0303}', '\u{1EF9}'),
+    ('\u{0079}', '\u{0304}', '\u{0233}'), ('\u{0079}', '\u{0307}', '\u{1E8F}'),
+    ('\u{0079}', '\u{0308}', '\u{00FF}'), ('\u{0079}', '\u{0309}', '\u{1EF7}'),
+    ('\u{0079}', '\u{030A}', '\u{1E99}'), ('\u{0079}', '\u{0323}', '\u{1EF5}'),
+    ('\u{007A}', '\u{0301}', '\u{017A}'), ('\u{007A}', '\u{0302}', '\u{1E91}'),
+    ('\u{007A}', '\u{0307}', '\u{017C}'), ('\u{007A}', '\u{030C}', '\u{017E}'),
+    ('\u{007A}', '\u{0323}', '\u{1E93}'), ('\u{00C2}', '\u{0300}', '\u{1EA6}'),
+    ('\u{00C2}', '\u{0301}', '\u{1EA4}'), ('\u{00C2}', '\u{0303}', '\u{1EAA}'),
+    ('\u{00C2}', '\u{0309}', '\u{1EA8}'), ('\u{00C2}', '\u{0323}', '\u{1EAC}'),
+    ('\u{00C4}', '\u{0304}', '\u{01DE}'), ('\u{00C5}', '\u{0301}', '\u{01FA}'),
+    ('\u{00C6}', '\u{0301}', '\u{01FC}'), ('\u{00C6}', '\u{0304}', '\u{01E2}'),
+    ('\u{00C7}', '\u{0301}', '\u{1E08}'), ('\u{00CA}', '\u{0300}', '\u{1EC0}'),
+    ('\u{00CA}', '\u{0301}', '\u{1EBE}'), ('\u{00CA}', '\u{0303}', '\u{1EC4}'),
+    ('\u{00CA}', '\u{0309}', '\u{1EC2}'), ('\u{00CA}', '\u{0323}', '\u{1EC6}'),
+    ('\u{00CF}', '\u{0301}', '\u{1E2E}'), ('\u{00D4}', '\u{0300}', '\u{1ED2}'),
+    ('\u{00D4}', '\u{0301}', '\u{1ED0}'), ('\u{00D4}', '\u{0303}', '\u{1ED6}'),
+    ('\u{00D4}', '\u{0309}', '\u{1ED4}'), ('\u{00D4}', '\u{0323}', '\u{1ED8}'),
+    ('\u{00D5}', '\u{0301}', '\u{1E4C}'), ('\u{00D5}', '\u{0304}', '\u{022C}'),
+    ('\u{00D5}', '\u{0308}', '\u{1E4E}'), ('\u{00D6}', '\u{0304}', '\u{022A}'),
+    ('\u{00D8}', '\u{0301}', '\u{01FE}'), ('\u{00DC}', '\u{0300}', '\u{01DB}'),
+    ('\u{00DC}', '\u{0301}', '\u{01D7}'), ('\u{00DC}', '\u{0304}', '\u{01D5}'),
+    ('\u{00DC}', '\u{030C}', '\u{01D9}'), ('\u{00E2}', '\u{0300}', '\u{1EA7}'),
+    ('\u{00E2}', '\u{0301}', '\u{1EA5}'), ('\u{00E2}', '\u{0303}', '\u{1EAB}'),
+    ('\u{00E2}', '\u{0309}', '\u{1EA9}'), ('\u{00E2}', '\u{0323}', '\u{1EAD}'),
+    ('\u{00E4}', '\u{0304}', '\u{01DF}'), ('\u{00E5}', '\u{0301}', '\u{01FB}'),
+    ('\u{00E6}', '\u{0301}', '\u{01FD}'), ('\u{00E6}', '\u{0304}', '\u{01E3}'),
+    ('\u{00E7}', '\u{0301}', '\u{1E09}'), ('\u{00EA}', '\u{0300}', '\u{1EC1}'),
+    ('\u{00EA}', '\u{0301}', '\u{1EBF}'), ('\u{00EA}', '\u{0303}', '\u{1EC5}'),
+    ('\u{00EA}', '\u{0309}', '\u{1EC3}'), ('\u{00EA}', '\u{0323}', '\u{1EC7}'),
+    ('\u{00EF}', '\u{0301}', '\u{1E2F}'), ('\u{00F4}', '\u{0300}', '\u{1ED3}'),
+    ('\u{00F4}', '\u{0301}', '\u{1ED1}'), ('\u{00F4}', '\u{0303}', '\u{1ED7}'),
+    ('\u{00F4}', '\u{0309}', '\u{1ED5}'), ('\u{00F4}', '\u{0323}', '\u{1ED9}'),
+    ('\u{00F5}', '\u{0301}', '\u{1E4D}'), ('\u{00F5}', '\u{0304}', '\u{022D}'),
+    ('\u{00F5}', '\u{0308}', '\u{1E4F}'), ('\u{00F6}', '\u{0304}', '\u{022B}'),
+    ('\u{00F8}', '\u{0301}', '\u{01FF}'), ('\u{00FC}', '\u{0300}', '\u{01DC}'),
+    ('\u{00FC}', '\u{0301}', '\u{01D8}'), ('\u{00FC}', '\u{0304}', '\u{01D6}'),
+    ('\u{00FC}', '\u{030C}', '\u{01DA}'), ('\u{0102}', '\u{0300}', '\u{1EB0}'),
+    ('\u{0102}', '\u{0301}', '\u{1EAE}'), ('\u{0102}', '\u{0303}', '\u{1EB4}'),
+    ('\u{0102}', '\u{0309}', '\u{1EB2}'), ('\u{0102}', '\u{0323}', '\u{1EB6}'),
+    ('\u{0103}', '\u{0300}', '\u{1EB1}'), ('\u{0103}', '\u{0301}', '\u{1EAF}'),
+    ('\u{0103}', '\u{0303}', '\u{1EB5}'), ('\u{0103}', '\u{0309}', '\u{1EB3}'),
+    ('\u{0103}', '\u{0323}', '\u{1EB7}'), ('\u{0106}', '\u{0327}', '\u{1E08}'),
+    ('\u{0107}', '\u{0327}', '\u{1E09}'), ('\u{0112}', '\u{0300}', '\u{1E14}'),
+    ('\u{0112}', '\u{0301}', '\u{1E16}'), ('\u{0113}', '\u{0300}', '\u{1E15}'),
+    ('\u{0113}', '\u{0301}', '\u{1E17}'), ('\u{0114}', '\u{0327}', '\u{1E1C}'),
+    ('\u{0115}', '\u{0327}', '\u{1E1D}'), ('\u{014C}', '\u{0300}', '\u{1E50}'),
+    ('\u{014C}', '\u{0301}', '\u{1E52}'), ('\u{014C}', '\u{0328}', '\u{01EC}'),
+    ('\u{014D}', '\u{0300}', '\u{1E51}'), ('\u{014D}', '\u{0301}', '\u{1E53}'),
+    ('\u{014D}', '\u{0328}', '\u{01ED}'), ('\u{015A}', '\u{0307}', '\u{1E64}'),
+    ('\u{015B}', '\u{0307}', '\u{1E65}'), ('\u{0160}', '\u{0307}', '\u{1E66}'),
+    ('\u{0161}', '\u{0307}', '\u{1E67}'), ('\u{0168}', '\u{0301}', '\u{1E78}'),
+    ('\u{0169}', '\u{0301}', '\u{1E79}'), ('\u{016A}', '\u{0308}', '\u{1E7A}'),
+    ('\u{016B}', '\u{0308}', '\u{1E7B}'), ('\u{01A0}', '\u{0300}', '\u{1EDC}'),
+    ('\u{01A0}', '\u{0301}', '\u{1EDA}'), ('\u{01A0}', '\u{0303}', '\u{1EE0}'),
+    ('\u{01A0}', '\u{0309}', '\u{1EDE}'), ('\u{01A0}', '\u{0323}', '\u{1EE2}'),
+    ('\u{01A1}', '\u{0300}', '\u{1EDD}'), ('\u{01A1}', '\u{0301}', '\u{1EDB}'),
+    ('\u{01A1}', '\u{0303}', '\u{1EE1}'), ('\u{01A1}', '\u{0309}', '\u{1EDF}'),
+    ('\u{01A1}', '\u{0323}', '\u{1EE3}'), ('\u{01AF}', '\u{0300}', '\u{1EEA}'),
+    ('\u{01AF}', '\u{0301}', '\u{1EE8}'), ('\u{01AF}', '\u{0303}', '\u{1EEE}'),
+    ('\u{01AF}', '\u{0309}', '\u{1EEC}'), ('\u{01AF}', '\u{0323}', '\u{1EF0}'),
+    ('\u{01B0}', '\u{0300}', '\u{1EEB}'), ('\u{01B0}', '\u{0301}', '\u{1EE9}'),
+    ('\u{01B0}', '\u{0303}', '\u{1EEF}'), ('\u{01B0}', '\u{0309}', '\u{1EED}'),
+    ('\u{01B0}', '\u{0323}', '\u{1EF1}'), ('\u{01EA}', '\u{0304}', '\u{01EC}'),
+    ('\u{01EB}', '\u{0304}', '\u{01ED}'), ('\u{0226}', '\u{0304}', '\u{01E0}'),
+    ('\u{0227}', '\u{0304}', '\u{01E1}'), ('\u{0228}', '\u{0306}', '\u{1E1C}'),
+    ('\u{0229}', '\u{0306}', '\u{1E1D}'), ('\u{022E}', '\u{0304}', '\u{0230}'),
+    ('\u{022F}', '\u{0304}', '\u{0231}'), ('\u{1E36}', '\u{0304}', '\u{1E38}'),
+    ('\u{1E37}', '\u{0304}', '\u{1E39}'), ('\u{1E5A}', '\u{0304}', '\u{1E5C}'),
+    ('\u{1E5B}', '\u{0304}', '\u{1E5D}'), ('\u{1E60}', '\u{0323}', '\u{1E68}'),
+    ('\u{1E61}', '\u{0323}', '\u{1E69}'), ('\u{1E62}', '\u{0307}', '\u{1E68}'),
+    ('\u{1E63}', '\u{0307}', '\u{1E69}'), ('\u{1EA0}', '\u{0302}', '\u{1EAC}'),
+    ('\u{1EA0}', '\u{0306}', '\u{1EB6}'), ('\u{1EA1}', '\u{0302}', '\u{1EAD}'),
+    ('\u{1EA1}', '\u{0306}', '\u{1EB7}'), ('\u{1EB8}', '\u{0302}', '\u{1EC6}'),
+    ('\u{1EB9}', '\u{0302}', '\u{1EC7}'), ('\u{1ECC}', '\u{0302}', '\u{1ED8}'),
+    ('\u{1ECD}', '\u{0302}', '\u{1ED9}'),
+];
+
+/// The spacing character for an ANSEL byte of the upper half
+pub(crate) fn spacing(byte: u8) -> Option<char> {
+    SPACING
+        .binary_search_by_key(&byte, |&(b, _)| b)
+        .ok()
+        .map(|i| SPACING[i].1)
+}
+
+/// The combining diacritic for an ANSEL byte, with its canonical combining class
+pub(crate) fn combining(byte: u8) -> Option<(char, u8)> {
+    COMBINING
+        .binary_search_by_key(&byte, |&(b, _, _)| b)
+        .ok()
+        .map(|i| (COMBINING[i].1, COMBINING[i].2))
+}
+
+/// The precomposed character for `base` followed by the diacritic `mark`, if Unicode has one
+pub(crate) fn compose(base: char, mark: char) -> Option<char> {
+    COMPOSITIONS
+        .binary_search_by_key(&(base, mark), |&(b, m, _)| (b, m))
+        .ok()
+        .map(|i| COMPOSITIONS[i].2)
+}
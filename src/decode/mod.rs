@@ -0,0 +1,186 @@
+//! Decoding of raw GEDCOM bytes into text
+//!
+//! GEDCOM 5.5.1 files declare their character set in `HEAD.CHAR`, and may be
+//! ANSEL, UTF-8, UTF-16 (`UNICODE`) or ASCII. Files exported by Windows software
+//! are often in the Windows-1252 code page instead. The encoding of a file is
+//! taken from its byte order mark if it has one, and from `HEAD.CHAR` otherwise.
+//!
+//! ```rust
+//! // "José" in ANSEL, with the acute accent before the letter it belongs to
+//! let bytes = b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME Jos\xe2e /Cruz/\n0 TRLR\n";
+//!
+//! assert_eq!(gedcom::decode::detect(bytes), gedcom::decode::Encoding::Ansel);
+//! assert!(gedcom::decode::decode(bytes).contains("Jos\u{e9} /Cruz/"));
+//!
+//! // the parser detects the encoding itself when reading bytes
+//! let data = gedcom::parser::Parser::from_reader(&bytes[..]).try_parse().unwrap();
+//! ```
+use crate::reader::ReadChars;
+
+mod ansel;
+pub(crate) use ansel::{combining as ansel_combining, compose, spacing as ansel_spacing};
+
+/// A character encoding of GEDCOM files
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8, the `UTF-8` character set
+    Utf8,
+    /// Little-endian UTF-16, the `UNICODE` character set as written on Windows
+    Utf16Le,
+    /// Big-endian UTF-16, the `UNICODE` character set
+    Utf16Be,
+    /// ANSEL (ANSI Z39.47), the `ANSEL` character set. Combining diacritics are moved
+    /// after the letter they modify and composed with it where Unicode allows.
+    Ansel,
+    /// The `ASCII` character set. Bytes outside of ASCII are read as Windows-1252,
+    /// the encoding most often mislabelled as ASCII.
+    Ascii,
+    /// The Windows-1252 code page, labelled `ANSI` by some Windows software
+    Windows1252,
+    /// ISO 8859-1, or Latin-1
+    Latin1,
+}
+
+impl Encoding {
+    /// The encoding for a `HEAD.CHAR` value or a common name of a character set,
+    /// ignoring case. `UNICODE` is taken to be little-endian UTF-16.
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Encoding> {
+        match label.trim().to_uppercase().as_str() {
+            "UTF-8" | "UTF8" => Some(Encoding::Utf8),
+            "UNICODE" | "UTF-16" | "UTF-16LE" => Some(Encoding::Utf16Le),
+            "UTF-16BE" => Some(Encoding::Utf16Be),
+            "ANSEL" => Some(Encoding::Ansel),
+            "ASCII" | "US-ASCII" => Some(Encoding::Ascii),
+            "ANSI" | "WINDOWS-1252" | "CP1252" | "IBM WINDOWS" => Some(Encoding::Windows1252),
+            "ISO-8859-1" | "ISO8859-1" | "LATIN1" | "LATIN-1" => Some(Encoding::Latin1),
+            _ => None,
+        }
+    }
+
+    /// The `HEAD.CHAR` value declaring this encoding
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le | Encoding::Utf16Be => "UNICODE",
+            Encoding::Ansel => "ANSEL",
+            Encoding::Ascii => "ASCII",
+            Encoding::Windows1252 => "ANSI",
+            Encoding::Latin1 => "ISO-8859-1",
+        }
+    }
+}
+
+/// Detects the encoding of a file from its first bytes.
+///
+/// A byte order mark decides the encoding when there is one. UTF-16 is also recognized
+/// by the zero byte next to the level of the first line. Otherwise the `HEAD.CHAR` value
+/// is used, falling back to UTF-8 when it is missing or not recognized.
+#[must_use]
+pub fn detect(bytes: &[u8]) -> Encoding {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Encoding::Utf8,
+        [0xFF, 0xFE, ..] | [b'0', 0, ..] => Encoding::Utf16Le,
+        [0xFE, 0xFF, ..] | [0, b'0', ..] => Encoding::Utf16Be,
+        _ => match header_charset(bytes).and_then(Encoding::from_label) {
+            // the label was readable one byte at a time, so the file is not UTF-16
+            Some(Encoding::Utf16Le | Encoding::Utf16Be) | None => Encoding::Utf8,
+            Some(encoding) => encoding,
+        },
+    }
+}
+
+/// Decodes a whole file into text, detecting its encoding with `detect`.
+/// Byte sequences that are invalid in the encoding are replaced with `U+FFFD`.
+#[must_use]
+pub fn decode(bytes: &[u8]) -> String {
+    decode_with(bytes, detect(bytes))
+}
+
+/// Decodes a whole file of the given encoding into text, dropping any byte order mark.
+/// Byte sequences that are invalid in the encoding are replaced with `U+FFFD`.
+#[must_use]
+pub fn decode_with(bytes: &[u8], encoding: Encoding) -> String {
+    ReadChars::with_encoding(bytes, encoding).collect()
+}
+
+/// Whether `bytes` hold enough of the start of a file for `detect` to settle on its
+/// encoding: a byte order mark, or the header up to its `CHAR` line or the next record.
+///
+/// Only the whole lines from `scanned` on are looked at, and `scanned` is moved past
+/// them, so the bytes read ahead can be checked again as they grow without rescanning.
+pub(crate) fn is_detectable(bytes: &[u8], scanned: &mut usize) -> bool {
+    if let [0xEF, 0xBB, 0xBF, ..]
+    | [0xFF, 0xFE, ..]
+    | [0xFE, 0xFF, ..]
+    | [b'0', 0, ..]
+    | [0, b'0', ..] = bytes
+    {
+        return true;
+    }
+    let start = *scanned;
+    // the last line may be cut short, so only whole lines count
+    let Some(end) = bytes[start..]
+        .iter()
+        .rposition(|&b| b == b'\n' || b == b'\r')
+        .map(|i| start + i + 1)
+    else {
+        return false;
+    };
+    *scanned = end;
+    lines(&bytes[start..end]).enumerate().any(|(i, line)| {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("1"), Some("CHAR")) => true,
+            // the first line of the file is the header itself
+            (Some("0"), _) => start > 0 || i > 0,
+            _ => false,
+        }
+    })
+}
+
+/// The value of the `CHAR` line of the header, read from the lines of an
+/// ASCII-compatible file up to the start of the next record
+fn header_charset(bytes: &[u8]) -> Option<&str> {
+    header_lines(bytes).find_map(|line| {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("1"), Some("CHAR")) => words.next(),
+            _ => None,
+        }
+    })
+}
+
+/// The lines of the header of an ASCII-compatible file, up to the start of the next record
+fn header_lines(bytes: &[u8]) -> impl Iterator<Item = &str> {
+    lines(bytes)
+        .enumerate()
+        .take_while(|(i, line)| *i == 0 || line.split_whitespace().next() != Some("0"))
+        .map(|(_, line)| line)
+}
+
+/// The lines of an ASCII-compatible file that are valid UTF-8
+fn lines(bytes: &[u8]) -> impl Iterator<Item = &str> {
+    bytes
+        .split(|&b| b == b'\n' || b == b'\r')
+        .filter_map(|line| std::str::from_utf8(line).ok())
+}
+
+/// The Windows-1252 characters for the bytes 0x80 to 0x9F. The five bytes it leaves
+/// undefined are mapped to the control characters of the same value, as browsers do.
+#[rustfmt::skip]
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// The Windows-1252 character for a byte
+pub(crate) fn windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
@@ -0,0 +1,72 @@
+//! Non-fatal problems reported while parsing a GEDCOM file
+use std::fmt;
+
+/// How serious a reported problem is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Data was skipped or interpreted loosely, but the file is otherwise usable
+    Warning,
+    /// The file breaks the specification and the parser had to recover from it
+    Error,
+}
+
+/// The category of a reported problem, for filtering and counting
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// A tag the specification allows at this position that the parser does not handle,
+    /// skipped along with its children
+    UnhandledTag,
+    /// A tag the specification does not allow at this position
+    InvalidTag,
+    /// A user-defined tag (beginning with an underscore) that was skipped
+    SkippedCustomTag,
+    /// A value that is not one of those allowed by the specification
+    UnrecognizedValue,
+    /// A tag that requires a value was found without one
+    MissingValue,
+    /// A second `HUSB` or `WIFE` within a family, which was dropped
+    DuplicateSpouse,
+    /// The file ended without a `TRLR` record
+    MissingTrailer,
+    /// A structure or value the version of the specification the file follows does
+    /// not allow, _ie._ `CONC` in a GEDCOM 7 file
+    InvalidForVersion,
+    /// Data that was dropped or changed in meaning while converting between versions
+    LossyConversion,
+    /// An extension tag of a GEDCOM 7 file that is not declared in the `SCHMA` of its header
+    UndeclaredExtension,
+}
+
+/// A non-fatal problem found while parsing, with the location it was found at
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// How serious the problem is
+    pub severity: Severity,
+    /// The line number of the offending line
+    pub line: u32,
+    /// The tags leading from the top-level record to the offending line, _ie._ `["INDI", "BIRT", "DATE"]`
+    pub tag_path: Vec<String>,
+    /// Xref of the top-level record the line belongs to, if it has one
+    pub xref: Option<String>,
+    /// Human readable description of the problem
+    pub message: String,
+    /// The category of the problem
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "WARNING",
+            Severity::Error => "ERROR",
+        };
+        write!(f, "{severity}: line {}: ", self.line)?;
+        if let Some(xref) = &self.xref {
+            write!(f, "{xref} ")?;
+        }
+        if !self.tag_path.is_empty() {
+            write!(f, "{}: ", self.tag_path.join("."))?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,182 @@
+//! Errors that can occur while reading a GEDCOM file
+use std::{fmt, io};
+
+use crate::diagnostic::Diagnostic;
+
+/// A fatal problem found while parsing a GEDCOM file.
+///
+/// Every variant carries the line number of the offending line, and where it
+/// is known, the tag being parsed and the xref of the enclosing top-level record.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// A line did not begin with a valid level number
+    InvalidLevel {
+        /// Line the error occurred on
+        line: u32,
+        /// The text found where the level was expected
+        found: String,
+        /// Xref of the record being parsed
+        xref: Option<String>,
+    },
+    /// A token was found where the GEDCOM grammar does not allow it
+    UnexpectedToken {
+        /// Line the error occurred on
+        line: u32,
+        /// Tag of the line, if one was read
+        tag: Option<String>,
+        /// Debug representation of the token found
+        found: String,
+        /// Xref of the record being parsed
+        xref: Option<String>,
+    },
+    /// A `PEDI` value other than adopted, birth, foster or sealing
+    UnknownPedigree {
+        /// Line the error occurred on
+        line: u32,
+        /// The unrecognized value
+        value: String,
+        /// Xref of the record being parsed
+        xref: Option<String>,
+    },
+    /// A `SEX` value other than M, F, N or U
+    UnknownSex {
+        /// Line the error occurred on
+        line: u32,
+        /// The unrecognized value
+        value: String,
+        /// Xref of the record being parsed
+        xref: Option<String>,
+    },
+    /// A second `HUSB` or `WIFE` within a single family record
+    DuplicateSpouse {
+        /// Line the error occurred on
+        line: u32,
+        /// Either `HUSB` or `WIFE`
+        tag: String,
+        /// Xref of the family record
+        xref: Option<String>,
+    },
+    /// A second event of the same kind within a single family record
+    DuplicateEvent {
+        /// Line the error occurred on
+        line: u32,
+        /// Tag of the repeated event
+        tag: String,
+        /// Xref of the family record
+        xref: Option<String>,
+    },
+    /// A header `TIME` that is not preceded by a `DATE`
+    TimeWithoutDate {
+        /// Line the error occurred on
+        line: u32,
+    },
+    /// A problem that only stops parsing because `ParserOptions::strict` is set
+    Nonconforming(Diagnostic),
+    /// Reading the input failed before the end of the file
+    Io {
+        /// Line being read when the error occurred
+        line: u32,
+        /// The category of the underlying `std::io::Error`
+        kind: io::ErrorKind,
+        /// Description of the underlying `std::io::Error`
+        message: String,
+    },
+}
+
+impl ParseError {
+    /// The line number on which the error occurred
+    #[must_use]
+    pub fn line(&self) -> u32 {
+        match self {
+            ParseError::InvalidLevel { line, .. }
+            | ParseError::UnexpectedToken { line, .. }
+            | ParseError::UnknownPedigree { line, .. }
+            | ParseError::UnknownSex { line, .. }
+            | ParseError::DuplicateSpouse { line, .. }
+            | ParseError::DuplicateEvent { line, .. }
+            | ParseError::TimeWithoutDate { line }
+            | ParseError::Io { line, .. }
+            | ParseError::Nonconforming(Diagnostic { line, .. }) => *line,
+        }
+    }
+
+    /// The tag of the offending line, if known
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            ParseError::UnexpectedToken { tag, .. } => tag.as_deref(),
+            ParseError::UnknownPedigree { .. } => Some("PEDI"),
+            ParseError::UnknownSex { .. } => Some("SEX"),
+            ParseError::DuplicateSpouse { tag, .. } | ParseError::DuplicateEvent { tag, .. } => {
+                Some(tag)
+            }
+            ParseError::TimeWithoutDate { .. } => Some("TIME"),
+            ParseError::InvalidLevel { .. } | ParseError::Io { .. } => None,
+            ParseError::Nonconforming(diagnostic) => diagnostic.tag_path.last().map(String::as_str),
+        }
+    }
+
+    /// The xref of the top-level record containing the error, if it has one
+    #[must_use]
+    pub fn xref(&self) -> Option<&str> {
+        match self {
+            ParseError::InvalidLevel { xref, .. }
+            | ParseError::UnexpectedToken { xref, .. }
+            | ParseError::UnknownPedigree { xref, .. }
+            | ParseError::UnknownSex { xref, .. }
+            | ParseError::DuplicateSpouse { xref, .. }
+            | ParseError::DuplicateEvent { xref, .. }
+            | ParseError::Nonconforming(Diagnostic { xref, .. }) => xref.as_deref(),
+            ParseError::TimeWithoutDate { .. } | ParseError::Io { .. } => None,
+        }
+    }
+
+    /// Attaches the xref of the enclosing record, unless one is already set
+    #[must_use]
+    pub(crate) fn in_record(mut self, record_xref: Option<&String>) -> Self {
+        if let ParseError::InvalidLevel { xref, .. }
+        | ParseError::UnexpectedToken { xref, .. }
+        | ParseError::UnknownPedigree { xref, .. }
+        | ParseError::UnknownSex { xref, .. }
+        | ParseError::DuplicateSpouse { xref, .. }
+        | ParseError::DuplicateEvent { xref, .. }
+        | ParseError::Nonconforming(Diagnostic { xref, .. }) = &mut self
+        {
+            if xref.is_none() {
+                *xref = record_xref.cloned();
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line())?;
+        match self {
+            ParseError::InvalidLevel { found, .. } => {
+                write!(f, "expected a level number, found {found:?}")?;
+            }
+            ParseError::UnexpectedToken { found, .. } => write!(f, "unexpected token {found}")?,
+            ParseError::UnknownPedigree { value, .. } => {
+                write!(f, "unrecognized family link pedigree {value:?}")?;
+            }
+            ParseError::UnknownSex { value, .. } => write!(f, "unrecognized sex {value:?}")?,
+            ParseError::DuplicateSpouse { tag, .. } => {
+                write!(f, "family already has a {tag} individual")?;
+            }
+            ParseError::DuplicateEvent { tag, .. } => {
+                write!(f, "family already has a {tag} event")?;
+            }
+            ParseError::TimeWithoutDate { .. } => write!(f, "expected TIME to be under DATE")?,
+            ParseError::Nonconforming(diagnostic) => write!(f, "{}", diagnostic.message)?,
+            ParseError::Io { message, .. } => write!(f, "could not read input: {message}")?,
+        }
+        if let Some(xref) = self.xref() {
+            write!(f, " (in record {xref})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
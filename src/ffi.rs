@@ -1,11 +1,19 @@
-use std::{ffi::{c_char, CStr, CString}, mem, ptr};
+//! C compatible bindings, used to generate a header with `cbindgen`
+use std::{ffi::{c_char, CStr, CString}, ptr};
 
+/// C compatible representation of `gedcom::types::Gender`
 #[repr(C)]
-enum Gender {
+pub enum Gender {
+    /// Male
     Male,
+    /// Female
     Female,
-    Nonbinary, 
-    Unknown
+    /// Nonbinary
+    Nonbinary,
+    /// Unknown
+    Unknown,
+    /// Neither only male nor only female, `X` in GEDCOM 7
+    Other
 }
 
 impl From<&crate::types::Gender> for Gender {
@@ -15,6 +23,7 @@ impl From<&crate::types::Gender> for Gender {
             crate::types::Gender::Female => Gender::Female,
             crate::types::Gender::Unknown => Gender::Unknown,
             crate::types::Gender::Nonbinary => Gender::Nonbinary,
+            crate::types::Gender::Other => Gender::Other,
         }
     }
 }
@@ -33,7 +42,7 @@ pub struct MaybeString {
 
 
 impl MaybeString {
-    fn new(opt : &Option<String>) -> Self {
+    fn new(opt : Option<&String>) -> Self {
         if let Some(maybe_string_val) = opt {
             MaybeString {
                 data : CString::new(maybe_string_val.clone()).expect("Error").into_raw(),
@@ -41,7 +50,7 @@ impl MaybeString {
             }
         } else {
             MaybeString {
-                data : std::ptr::null_mut(),
+                data : ptr::null_mut(),
                 len : 0
             }
         }
@@ -60,24 +69,29 @@ impl Drop for MaybeString {
 
 
 
+/// C compatible representation of `Vec<String>`
 #[repr(C)]
 pub struct VecString {
+    /// Data pointer
     pub data : *mut *mut c_char,
+    /// Length of vector
     pub len : usize
 }
 
 
 impl VecString {
-    pub fn new(vec : &Vec<String>) ->Self{
-        let mut cstring_vec : Vec<*mut c_char> = vec.iter().map(|s| CString::new(s.clone()).expect("Error").into_raw()).collect();
-        cstring_vec.shrink_to_fit();
-        let retval = VecString {
-            data : cstring_vec.as_mut_ptr(),
-            len : cstring_vec.len()
-
-        };
-        std::mem::forget(cstring_vec);
-        retval
+    /// Copies the strings into C strings
+    ///
+    /// # Panics
+    ///
+    /// Panics when a string contains a nul byte.
+    #[must_use]
+    pub fn new(vec : &[String]) ->Self{
+        let cstring_vec : Box<[*mut c_char]> = vec.iter().map(|s| CString::new(s.clone()).expect("Error").into_raw()).collect();
+        VecString {
+            len : cstring_vec.len(),
+            data : Box::into_raw(cstring_vec).cast::<*mut c_char>(),
+        }
     }
 }
 
@@ -86,12 +100,12 @@ impl Drop for VecString {
     fn drop(&mut self) {
 
         let cstring_vec = unsafe  {
-            Vec::from_raw_parts(self.data, self.len, self.len)
+            Box::from_raw(ptr::slice_from_raw_parts_mut(self.data, self.len))
         };
 
         for cstring in &cstring_vec {
             unsafe {
-                drop(CString::from_raw(*cstring))
+                drop(CString::from_raw(*cstring));
             }
         }
         drop(cstring_vec);
@@ -99,7 +113,7 @@ impl Drop for VecString {
 }
 
 
-/// C compatible representation of gedcom::types::Name
+/// C compatible representation of `gedcom::types::Name`
 #[repr(C)]
 pub struct Name {
     /// Value
@@ -124,23 +138,26 @@ pub struct MaybeName {
 }
 
 impl MaybeName {
-    fn new(opt : &Option<crate::types::Name>) -> Self {
+    fn new(opt : Option<&crate::types::Name>) -> Self {
 
         if let Some(name_val) = opt {
+            // the pieces fall back to the slashes of the value when there is no GIVN, SURN or NSFX
+            let pieces = name_val.pieces();
+            let piece = |piece : Option<&str>| MaybeString::new(piece.map(str::to_string).as_ref());
             let name_box = Box::new(Name {
-                    value : MaybeString::new(&name_val.value),
-                    given : MaybeString::new(&name_val.given),
-                    surname : MaybeString::new(&name_val.surname),
-                    prefix : MaybeString::new(&name_val.prefix),
-                    surname_prefix : MaybeString::new(&name_val.surname_prefix),
-                    suffix : MaybeString::new(&name_val.suffix),
+                    value : MaybeString::new(name_val.value.as_ref()),
+                    given : piece(pieces.given),
+                    surname : piece(pieces.surname),
+                    prefix : MaybeString::new(name_val.prefix.as_ref()),
+                    surname_prefix : MaybeString::new(name_val.surname_prefix.as_ref()),
+                    suffix : piece(pieces.suffix),
                 });
             MaybeName {
                 data : Box::<Name>::into_raw(name_box)
             }
         } else {
             MaybeName {
-                data : std::ptr::null_mut()
+                data : ptr::null_mut()
             }
         }
     }
@@ -163,7 +180,7 @@ impl Drop for MaybeName {
 pub struct Individual {
     /// Xref
     pub xref: MaybeString,
-    /// Name
+    /// Preferred name, the first of the individual's names
     pub name: MaybeName,
     /// Gender 
     pub sex : Gender,
@@ -172,8 +189,8 @@ pub struct Individual {
 impl Individual{
     fn new(ind : &crate::types::Individual) -> Self {
         Individual {
-            xref : MaybeString::new(&ind.xref),
-            name : MaybeName::new(&ind.name),
+            xref : MaybeString::new(ind.xref.as_ref()),
+            name : MaybeName::new(ind.name()),
             sex : Gender::from(&ind.sex)
         }
     }
@@ -190,43 +207,46 @@ pub struct VecIndividual {
 }
 
 impl VecIndividual {
-    fn new(vec : &Vec<crate::types::Individual>) -> Self {
-        let mut internal_individuals : Vec<Individual> = vec.iter().map(|gc_ind| Individual::new(&gc_ind)).collect();
-        internal_individuals.shrink_to_fit();
-        let c_vec =  VecIndividual {
-            data : internal_individuals.as_mut_ptr(),
-            len : vec.len()
-        };
-
-        mem::forget(internal_individuals);
-        return c_vec;
+    fn new(vec : &[crate::types::Individual]) -> Self {
+        let internal_individuals : Box<[Individual]> = vec.iter().map(Individual::new).collect();
+        VecIndividual {
+            len : internal_individuals.len(),
+            data : Box::into_raw(internal_individuals).cast::<Individual>(),
+        }
     }
 }
 
 impl Drop for VecIndividual {
     fn drop(&mut self) {
         unsafe {
-            drop(Vec::from_raw_parts(self.data, self.len, self.len))
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(self.data, self.len)));
         }
     }
 }
 
 
+/// C compatible representation of Family
 #[repr(C)]
 pub struct Family {
+    /// Xref
     pub xref: MaybeString,
-    pub individual1: MaybeString, // mapped from HUSB
-    pub individual2: MaybeString, // mapped from WIFE
+    /// First individual, mapped from HUSB
+    pub individual1: MaybeString,
+    /// Second individual, mapped from WIFE
+    pub individual2: MaybeString,
+    /// Children
     pub children: VecString,
 }
 
 
 impl Family {
+    /// Converts a parsed `gedcom::types::Family`
+    #[must_use]
     pub fn new(fam : &crate::types::Family) -> Self{
         Family {
-            xref : MaybeString::new(&fam.xref),
-            individual1 : MaybeString::new(&fam.individual1),
-            individual2 : MaybeString::new(&fam.individual2),
+            xref : MaybeString::new(fam.xref.as_ref()),
+            individual1 : MaybeString::new(fam.individual1.as_ref()),
+            individual2 : MaybeString::new(fam.individual2.as_ref()),
             children : VecString::new(&fam.children)
         }
     }
@@ -235,30 +255,31 @@ impl Family {
 
 
 
+/// C compatible representation of `Vec<Family>`
 #[repr(C)]
 pub struct VecFamily {
+    /// Data pointer
     pub data : *mut Family,
+    /// Length of vector
     pub len : usize
 }
 
 impl VecFamily {
-    pub fn new(vec : &Vec<crate::types::Family>) -> Self {
-        let mut internal_families : Vec<Family> = vec.iter().map(|gc_ind| Family::new(gc_ind)).collect();
-        internal_families.shrink_to_fit();
-        let c_vec =  VecFamily {
-            data : internal_families.as_mut_ptr(),
-            len : vec.len()
-        };
-
-        mem::forget(internal_families);
-        return c_vec;
+    /// Converts the parsed families
+    #[must_use]
+    pub fn new(vec : &[crate::types::Family]) -> Self {
+        let internal_families : Box<[Family]> = vec.iter().map(Family::new).collect();
+        VecFamily {
+            len : internal_families.len(),
+            data : Box::into_raw(internal_families).cast::<Family>(),
+        }
     }
 }
 
 impl Drop for VecFamily {
     fn drop(&mut self) {
         unsafe {
-            drop(Vec::from_raw_parts(self.data, self.len, self.len))
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(self.data, self.len)));
         }
     }
 }
@@ -267,7 +288,7 @@ impl Drop for VecFamily {
 
 
 
-/// C compatible representation of GedcomData
+/// C compatible representation of `GedcomData`
 #[repr(C)]
 pub struct GedcomData {
     /// Individuals
@@ -277,7 +298,7 @@ pub struct GedcomData {
 }
 
 impl GedcomData {
-    fn new(data : crate::GedcomData) -> Self {
+    fn new(data : &crate::GedcomData) -> Self {
         GedcomData {
             individuals : VecIndividual::new(&data.individuals),
             families : VecFamily::new(&data.families)
@@ -290,47 +311,71 @@ impl GedcomData {
 
 /// Parse string and return C compatible gedcom data
 /// Calle is responsible for freeing data, calling `free_parse`
+///
+/// On a parse error, or a panic while converting the data, a null pointer is
+/// returned and the error message is written to `err_str`, truncated to fit
+/// within `err_maxsize` bytes.
+///
+/// # Safety
+///
+/// `content_raw` must point to a nul-terminated string and `err_str` must
+/// point to a buffer of at least `err_maxsize` bytes.
 #[unsafe(no_mangle)]
-pub extern fn parse(content_raw: *const c_char, err_str : *mut c_char, err_maxsize : usize) -> *mut GedcomData {
+pub unsafe extern "C" fn parse(content_raw: *const c_char, err_str : *mut c_char, err_maxsize : usize) -> *mut GedcomData {
+    // a panic must not unwind across the C boundary
     let result = std::panic::catch_unwind(||{
         let content_cstr = unsafe {
             CStr::from_ptr(content_raw)
         };
-        let content = content_cstr.to_string_lossy();
-        let data = crate::parse(content.chars());
-        let c_data = GedcomData::new(data);
-        Box::<GedcomData>::into_raw(Box::new(c_data))
+        let mut parser = crate::parser::Parser::from_reader(content_cstr.to_bytes());
+        let data = parser.try_parse().map_err(|err| err.to_string())?;
+        let c_data = GedcomData::new(&data);
+        Ok(Box::<GedcomData>::into_raw(Box::new(c_data)))
     });
 
-    match result {
-        Err(cause) => {
-            cause.downcast_ref::<String>().inspect(|s|{
-                if s.len() < err_maxsize {
-                    let mut iter = err_str;
-                    for ch in s.chars(){
-                        unsafe {
-                            *iter = ch as core::ffi::c_char;
-                            iter = iter.add(1);
-                        }
-                    }
-                    unsafe { *iter = 0 as core::ffi::c_char; }
-                }
-            });
-
-            std::ptr::null_mut()
-        }
-        Ok(ptr) => ptr
+    let message = match result {
+        Ok(Ok(ptr)) => return ptr,
+        Ok(Err(message)) => message,
+        Err(cause) => cause.downcast_ref::<String>().cloned()
+            .or_else(|| cause.downcast_ref::<&str>().map(|s| (*s).to_string()))
+            .unwrap_or_else(|| "panicked while parsing".to_string()),
+    };
+    unsafe {
+        write_error(&message, err_str, err_maxsize);
     }
+    ptr::null_mut()
 }
 
 
-/// Free GedcomData, returned from `parse`
-#[unsafe(no_mangle)]
-pub extern fn free_parse(gdata : *mut GedcomData){
+/// Copies `message` into `err_str` as a nul-terminated string, cut at the last
+/// character that fits within `err_maxsize` bytes
+///
+/// # Safety
+///
+/// `err_str` must point to a buffer of at least `err_maxsize` bytes.
+unsafe fn write_error(message : &str, err_str : *mut c_char, err_maxsize : usize) {
+    if err_maxsize == 0 {
+        return;
+    }
+    let mut len = message.len().min(err_maxsize - 1);
+    while !message.is_char_boundary(len) {
+        len -= 1;
+    }
     unsafe {
-        drop(Box::from_raw(gdata))
+        ptr::copy_nonoverlapping(message.as_ptr().cast::<c_char>(), err_str, len);
+        *err_str.add(len) = 0;
     }
 }
 
 
-
+/// Free `GedcomData`, returned from `parse`
+///
+/// # Safety
+///
+/// `gdata` must be a pointer returned from `parse` that has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn free_parse(gdata : *mut GedcomData){
+    unsafe {
+        drop(Box::from_raw(gdata));
+    }
+}
@@ -0,0 +1,917 @@
+//! Import and export of [GEDCOM X](https://github.com/FamilySearch/gedcomx) JSON
+//!
+//! Available with the `gedcomx` feature, which builds on the serde support of the `json`
+//! feature. A `Document` mirrors the parts of the GEDCOM X data model this crate has
+//! counterparts for: individuals become persons, each `Family` becomes a couple
+//! relationship and a parent-child relationship for every pair of parent and child, events
+//! become facts, and the places events happened at are collected as place descriptions.
+//!
+//! ```rust
+//! let source = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
+//! let data = gedcom::parse(source.chars());
+//!
+//! let json = gedcom::gedcomx::to_string(&data).unwrap();
+//! let imported = gedcom::gedcomx::from_str(&json).unwrap();
+//!
+//! assert_eq!(imported.individuals.len(), 3);
+//! // the family with one spouse and no children has no relationship to become
+//! assert_eq!(imported.families.len(), 1);
+//! ```
+//!
+//! Not everything survives the trip. GEDCOM X has no place for the author and publication
+//! facts of a source, so they are only written into its citation, and records without a
+//! counterpart, like submitters, repositories and notes, are left out. A family with a single
+//! spouse and no children has no relationship to become, so it is left out too. GEDCOM X
+//! has no nonbinary or other sex either, so those are written as an unknown gender and
+//! read back as `Gender::Unknown`.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tree::GedcomData;
+use crate::types::{
+    event::HasEvents, Event, EventType, Family, FamilyLink, FamilyLinkType, GedcomDate, Individual,
+    NameType, Pedigree, Place, Source, SourceCitation,
+};
+
+const TYPE_BASE: &str = "http://gedcomx.org/";
+
+/// The event types and the names of the GEDCOM X fact types they match. A national id
+/// comes back as an `IdentityNumber`.
+const FACT_TYPES: &[(EventType, &str)] = &[
+    (EventType::Adoption, "Adoption"),
+    (EventType::Annulment, "Annulment"),
+    (EventType::AdultChristening, "AdultChristening"),
+    (EventType::Baptism, "Baptism"),
+    (EventType::BarMitzvah, "BarMitzvah"),
+    (EventType::BasMitzvah, "BatMitzvah"),
+    (EventType::Birth, "Birth"),
+    (EventType::Blessing, "Blessing"),
+    (EventType::Burial, "Burial"),
+    (EventType::Caste, "Caste"),
+    (EventType::Census, "Census"),
+    (EventType::Christening, "Christening"),
+    (EventType::Confirmation, "Confirmation"),
+    (EventType::Cremation, "Cremation"),
+    (EventType::Death, "Death"),
+    (EventType::Divorce, "Divorce"),
+    (EventType::DivorceFiled, "DivorceFiling"),
+    (EventType::Education, "Education"),
+    (EventType::Emigration, "Emigration"),
+    (EventType::Engagement, "Engagement"),
+    (EventType::FirstCommunion, "FirstCommunion"),
+    (EventType::Immigration, "Immigration"),
+    (EventType::IdentityNumber, "NationalId"),
+    (EventType::SocialSecurityNumber, "NationalId"),
+    (EventType::Marriage, "Marriage"),
+    (EventType::MarriageBann, "MarriageBanns"),
+    (EventType::MarriageContract, "MarriageContract"),
+    (EventType::MarriageLicense, "MarriageLicense"),
+    (EventType::Nationality, "Nationality"),
+    (EventType::Naturalization, "Naturalization"),
+    (EventType::ChildrenCount, "NumberOfChildren"),
+    (EventType::MarriageCount, "NumberOfMarriages"),
+    (EventType::Occupation, "Occupation"),
+    (EventType::Ordination, "Ordination"),
+    (EventType::PhysicalDescription, "PhysicalDescription"),
+    (EventType::Probate, "Probate"),
+    (EventType::Property, "Property"),
+    (EventType::Religion, "Religion"),
+    (EventType::Residence, "Residence"),
+    (EventType::Retirement, "Retirement"),
+    (EventType::Will, "Will"),
+];
+
+/// A GEDCOM X document, the root of its JSON serialization
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    /// The people of the family tree
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub persons: Vec<Person>,
+    /// Couple and parent-child relationships between persons
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relationships: Vec<Relationship>,
+    /// Sources of facts, along with one for each cited page of a source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_descriptions: Vec<SourceDescription>,
+    /// Places facts happened at
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub places: Vec<PlaceDescription>,
+}
+
+/// A person, exported from an `Individual`
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    /// Identifier of the person within the document, the xref without its `@` signs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Sex of the person
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+    /// Names of the person
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<Name>,
+    /// Events of the person's life
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub facts: Vec<Fact>,
+}
+
+/// The sex of a person, _ie._ `http://gedcomx.org/Female`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Gender {
+    /// URI of the gender type
+    #[serde(rename = "type")]
+    pub gender_type: String,
+}
+
+/// A name of a person
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Name {
+    /// URI of the type of the name, _ie._ `http://gedcomx.org/MarriedName`
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub name_type: Option<String>,
+    /// The name as written in each language or script, the first being the name itself and
+    /// the others its translations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub name_forms: Vec<NameForm>,
+}
+
+/// One way of writing a name
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameForm {
+    /// The language of the name, as a BCP 47 tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The full name, without the slashes GEDCOM puts around the surname
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_text: Option<String>,
+    /// The given name, surname, prefix and suffix
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<NamePart>,
+}
+
+/// A part of a name, _ie._ the `http://gedcomx.org/Surname`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamePart {
+    /// URI of the type of the part
+    #[serde(rename = "type")]
+    pub part_type: String,
+    /// The text of the part
+    pub value: String,
+}
+
+/// A fact about a person or relationship, exported from an `Event`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Fact {
+    /// URI of the type of the fact, _ie._ `http://gedcomx.org/Birth`
+    #[serde(rename = "type")]
+    pub fact_type: String,
+    /// The description of the fact, _ie._ the occupation of an `Occupation`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// When the fact happened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<Date>,
+    /// Where the fact happened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub place: Option<PlaceReference>,
+    /// The sources citing the fact
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceReference>,
+}
+
+/// A date, as it was given in the GEDCOM file
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Date {
+    /// The date, or the phrase describing it when there is no date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
+}
+
+/// A place a fact happened at
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlaceReference {
+    /// The place, as it was given in the GEDCOM file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
+    /// Reference to the `PlaceDescription` of the place, _ie._ `#P1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A place facts happened at, one for each distinct `PLAC` value
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlaceDescription {
+    /// Identifier of the place within the document
+    pub id: String,
+    /// The names of the place
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<TextValue>,
+    /// The latitude in decimal degrees, negative to the south
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    /// The longitude in decimal degrees, negative to the west
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+}
+
+/// A relationship between two persons, exported from a `Family`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Relationship {
+    /// Identifier of a couple relationship, the xref of its family without the `@` signs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `http://gedcomx.org/Couple` or `http://gedcomx.org/ParentChild`
+    #[serde(rename = "type")]
+    pub relationship_type: String,
+    /// One spouse of a couple, or the parent of a parent-child relationship
+    pub person1: ResourceReference,
+    /// The other spouse of a couple, or the child of a parent-child relationship
+    pub person2: ResourceReference,
+    /// Events of the family, or the pedigree of the child
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub facts: Vec<Fact>,
+}
+
+/// A reference to a resource within the document, _ie._ `#I1`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResourceReference {
+    /// The fragment naming the identifier of the resource
+    pub resource: String,
+}
+
+/// A citation of a source, exported from a `SourceCitation`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SourceReference {
+    /// Reference to the `SourceDescription` of the source, or of the cited page
+    pub description: String,
+}
+
+/// A source of facts, exported from a `Source` or from the page of a `SourceCitation`
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceDescription {
+    /// Identifier of the source within the document, the xref without its `@` signs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The bibliographic citations of the source, or the cited page
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
+    /// The titles of the source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub titles: Vec<TextValue>,
+    /// Notes about the source, holding its `TEXT`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<Note>,
+    /// The source a cited page belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_of: Option<SourceReference>,
+}
+
+/// A bibliographic citation of a source
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// The text of the citation
+    pub value: String,
+}
+
+/// A note about a resource
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    /// The text of the note
+    pub text: String,
+}
+
+/// A text, _ie._ the title of a source or the name of a place
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TextValue {
+    /// The text
+    pub value: String,
+}
+
+/// Serializes `data` as GEDCOM X JSON.
+///
+/// # Errors
+///
+/// Returns a `serde_json::Error` when serialization fails.
+pub fn to_string(data: &GedcomData) -> serde_json::Result<String> {
+    serde_json::to_string(&Document::from(data))
+}
+
+/// Parses GEDCOM X JSON into `GedcomData`.
+///
+/// # Errors
+///
+/// Returns a `serde_json::Error` when `json` is not a GEDCOM X document.
+pub fn from_str(json: &str) -> serde_json::Result<GedcomData> {
+    let document: Document = serde_json::from_str(json)?;
+    Ok(GedcomData::from(&document))
+}
+
+impl From<&GedcomData> for Document {
+    fn from(data: &GedcomData) -> Self {
+        let mut exporter = Exporter::default();
+        for source in &data.sources {
+            exporter
+                .document
+                .source_descriptions
+                .push(export_source(source));
+        }
+        for individual in &data.individuals {
+            let person = exporter.person(individual);
+            exporter.document.persons.push(person);
+        }
+        for family in &data.families {
+            exporter.family(family, &data.individuals);
+        }
+        exporter.document
+    }
+}
+
+/// Builds a `Document`, keeping track of the places and cited pages already described
+#[derive(Default)]
+struct Exporter {
+    document: Document,
+    places: HashMap<String, String>,
+    pages: HashMap<(String, String), String>,
+}
+
+impl Exporter {
+    fn person(&mut self, individual: &Individual) -> Person {
+        let gender = match individual.sex {
+            crate::types::Gender::Male => Some("Male"),
+            crate::types::Gender::Female => Some("Female"),
+            // GEDCOM X only knows intersex besides male and female, which is not what
+            // either of these says
+            crate::types::Gender::Nonbinary | crate::types::Gender::Other => Some("Unknown"),
+            crate::types::Gender::Unknown => None,
+        };
+        Person {
+            id: individual.xref.as_deref().map(id),
+            gender: gender.map(|gender| Gender {
+                gender_type: format!("{TYPE_BASE}{gender}"),
+            }),
+            names: individual
+                .names
+                .iter()
+                .map(|name| Name {
+                    name_type: export_name_type(name.name_type.as_ref()),
+                    name_forms: std::iter::once(name)
+                        .chain(&name.translations)
+                        .map(export_name)
+                        .collect(),
+                })
+                .collect(),
+            facts: self.facts(&individual.events()),
+        }
+    }
+
+    fn family(&mut self, family: &Family, individuals: &[Individual]) {
+        let parents: Vec<&String> = family
+            .individual1
+            .iter()
+            .chain(&family.individual2)
+            .collect();
+        if let [spouse1, spouse2] = parents[..] {
+            let facts = self.facts(&family.events());
+            self.document.relationships.push(Relationship {
+                id: family.xref.as_deref().map(id),
+                relationship_type: format!("{TYPE_BASE}Couple"),
+                person1: reference(spouse1),
+                person2: reference(spouse2),
+                facts,
+            });
+        }
+
+        for child in &family.children {
+            let pedigree = individuals
+                .iter()
+                .find(|individual| individual.xref.as_ref() == Some(child))
+                .and_then(|individual| {
+                    individual
+                        .families
+                        .iter()
+                        .find(|link| Some(link.xref()) == family.xref.as_ref())
+                })
+                .and_then(FamilyLink::pedigree)
+                .and_then(|pedigree| match pedigree {
+                    Pedigree::Adopted => Some("AdoptiveParent"),
+                    Pedigree::Birth => Some("BiologicalParent"),
+                    Pedigree::Foster => Some("FosterParent"),
+                    Pedigree::Sealing | Pedigree::Other(_) => None,
+                });
+            for parent in &parents {
+                self.document.relationships.push(Relationship {
+                    id: None,
+                    relationship_type: format!("{TYPE_BASE}ParentChild"),
+                    person1: reference(parent),
+                    person2: reference(child),
+                    facts: pedigree
+                        .iter()
+                        .map(|pedigree| Fact {
+                            fact_type: format!("{TYPE_BASE}{pedigree}"),
+                            value: None,
+                            date: None,
+                            place: None,
+                            sources: Vec::new(),
+                        })
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    /// The facts for `events`, leaving out those of a type GEDCOM X has no counterpart for
+    fn facts(&mut self, events: &[Event]) -> Vec<Fact> {
+        events
+            .iter()
+            .filter_map(|event| {
+                let (_, fact_type) = FACT_TYPES
+                    .iter()
+                    .find(|(event_type, _)| *event_type == event.event)?;
+                Some(Fact {
+                    fact_type: format!("{TYPE_BASE}{fact_type}"),
+                    value: event.value.clone(),
+                    date: event
+                        .date
+                        .as_ref()
+                        .map(|date| &date.original)
+                        .filter(|date| !date.is_empty())
+                        .or(event.date_phrase.as_ref())
+                        .map(|date| Date {
+                            original: Some(date.clone()),
+                        }),
+                    place: event.place.as_ref().map(|place| PlaceReference {
+                        original: Some(place.value.clone()),
+                        description: Some(format!("#{}", self.place(place))),
+                    }),
+                    sources: event
+                        .citations
+                        .iter()
+                        .map(|citation| self.citation(citation))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// The identifier of the description of `place`, describing it the first time
+    fn place(&mut self, place: &Place) -> String {
+        if let Some(place_id) = self.places.get(&place.value) {
+            return place_id.clone();
+        }
+        let place_id = format!("P{}", self.places.len() + 1);
+        self.document.places.push(PlaceDescription {
+            id: place_id.clone(),
+            names: vec![TextValue {
+                value: place.value.clone(),
+            }],
+            latitude: place.latitude,
+            longitude: place.longitude,
+        });
+        self.places.insert(place.value.clone(), place_id.clone());
+        place_id
+    }
+
+    /// A reference to the cited source, or to a description of the cited page which is a
+    /// component of the source
+    fn citation(&mut self, citation: &SourceCitation) -> SourceReference {
+        let source_id = id(&citation.xref);
+        let Some(page) = &citation.page else {
+            return SourceReference {
+                description: format!("#{source_id}"),
+            };
+        };
+
+        let key = (source_id, page.clone());
+        let page_id = if let Some(page_id) = self.pages.get(&key) {
+            page_id.clone()
+        } else {
+            let page_id = format!("{}-{}", key.0, self.pages.len() + 1);
+            self.document.source_descriptions.push(SourceDescription {
+                id: Some(page_id.clone()),
+                citations: vec![Citation {
+                    value: page.clone(),
+                }],
+                component_of: Some(SourceReference {
+                    description: format!("#{}", key.0),
+                }),
+                ..SourceDescription::default()
+            });
+            self.pages.insert(key, page_id.clone());
+            page_id
+        };
+        SourceReference {
+            description: format!("#{page_id}"),
+        }
+    }
+}
+
+/// The URI of the type of a name, for the types GEDCOM X has
+fn export_name_type(name_type: Option<&NameType>) -> Option<String> {
+    let name_type = match name_type? {
+        NameType::Aka => "AlsoKnownAs",
+        NameType::Birth => "BirthName",
+        NameType::Married => "MarriedName",
+        _ => return None,
+    };
+    Some(format!("{TYPE_BASE}{name_type}"))
+}
+
+fn export_name(name: &crate::types::Name) -> NameForm {
+    let pieces = name.pieces();
+    let parts = [
+        ("Prefix", name.prefix.as_deref()),
+        ("Given", pieces.given),
+        ("Surname", pieces.surname),
+        ("Suffix", pieces.suffix),
+    ];
+    NameForm {
+        lang: name.language.clone(),
+        full_text: name.value.as_ref().map(|value| {
+            value
+                .replace('/', " ")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        }),
+        parts: parts
+            .iter()
+            .filter_map(|(part_type, value)| {
+                value.map(|value| NamePart {
+                    part_type: format!("{TYPE_BASE}{part_type}"),
+                    value: value.to_string(),
+                })
+            })
+            .collect(),
+    }
+}
+
+fn export_source(source: &Source) -> SourceDescription {
+    let citation: Vec<&str> = [&source.author, &source.title, &source.publication]
+        .iter()
+        .filter_map(|fact| fact.as_deref())
+        .collect();
+    SourceDescription {
+        id: source.xref.as_deref().map(id),
+        citations: if citation.is_empty() {
+            Vec::new()
+        } else {
+            vec![Citation {
+                value: citation.join(". "),
+            }]
+        },
+        titles: source
+            .title
+            .iter()
+            .map(|title| TextValue {
+                value: title.clone(),
+            })
+            .collect(),
+        notes: source
+            .text
+            .iter()
+            .map(|text| Note { text: text.clone() })
+            .collect(),
+        component_of: None,
+    }
+}
+
+/// The identifier of the resource with `xref`, _ie._ `I1` for `@I1@`
+fn id(xref: &str) -> String {
+    xref.trim_matches('@').to_string()
+}
+
+fn reference(xref: &str) -> ResourceReference {
+    ResourceReference {
+        resource: format!("#{}", id(xref)),
+    }
+}
+
+impl From<&Document> for GedcomData {
+    fn from(document: &Document) -> Self {
+        let mut importer = Importer {
+            document,
+            data: GedcomData::default(),
+        };
+        importer.import();
+        importer.data
+    }
+}
+
+/// The parents of a child, each with the pedigree of its relationship to the child
+type Parents = Vec<(String, Option<Pedigree>)>;
+
+/// Builds `GedcomData` from a `Document`
+struct Importer<'a> {
+    document: &'a Document,
+    data: GedcomData,
+}
+
+impl Importer<'_> {
+    fn import(&mut self) {
+        let document = self.document;
+        for source in &document.source_descriptions {
+            if source.component_of.is_some() {
+                continue;
+            }
+            let mut imported = Source::new(source.id.as_deref().map(xref));
+            imported.title = source.titles.first().map(|title| title.value.clone());
+            imported.text = source.notes.first().map(|note| note.text.clone());
+            self.data.add_source(imported);
+        }
+
+        for person in &document.persons {
+            let individual = self.individual(person);
+            self.data.add_individual(individual);
+        }
+
+        let mut parents: Vec<(String, Parents)> = Vec::new();
+        for relationship in &document.relationships {
+            let person1 = xref(&relationship.person1.resource);
+            let person2 = xref(&relationship.person2.resource);
+            match type_name(&relationship.relationship_type) {
+                Some("Couple") => {
+                    let family_xref = relationship
+                        .id
+                        .as_deref()
+                        .map_or_else(|| self.free_family_xref(), xref);
+                    let mut family = Family::new(Some(family_xref));
+                    for event in self.events(&relationship.facts) {
+                        family.add_event(event);
+                    }
+                    self.add_family(family, &[person1, person2]);
+                }
+                Some("ParentChild") => {
+                    let pedigree = relationship.facts.iter().find_map(|fact| {
+                        match type_name(&fact.fact_type)? {
+                            "AdoptiveParent" => Some(Pedigree::Adopted),
+                            "BiologicalParent" => Some(Pedigree::Birth),
+                            "FosterParent" => Some(Pedigree::Foster),
+                            _ => None,
+                        }
+                    });
+                    let position = parents.iter().position(|(child, _)| *child == person2);
+                    let index = position.unwrap_or_else(|| {
+                        parents.push((person2, Vec::new()));
+                        parents.len() - 1
+                    });
+                    parents[index].1.push((person1, pedigree));
+                }
+                _ => {}
+            }
+        }
+
+        for (child, mut child_parents) in parents {
+            self.add_child(&child, &mut child_parents);
+        }
+    }
+
+    fn individual(&self, person: &Person) -> Individual {
+        let mut individual = Individual::new(person.id.as_deref().map(xref));
+        individual.sex = match person
+            .gender
+            .as_ref()
+            .and_then(|g| type_name(&g.gender_type))
+        {
+            Some("Male") => crate::types::Gender::Male,
+            Some("Female") => crate::types::Gender::Female,
+            Some("Intersex") => crate::types::Gender::Other,
+            _ => crate::types::Gender::Unknown,
+        };
+        individual.names = person
+            .names
+            .iter()
+            .filter_map(|name| {
+                let mut forms = name.name_forms.iter().map(import_name);
+                let mut imported = forms.next()?;
+                imported.name_type = match name.name_type.as_deref().and_then(type_name) {
+                    Some("AlsoKnownAs") => Some(NameType::Aka),
+                    Some("BirthName") => Some(NameType::Birth),
+                    Some("MarriedName") => Some(NameType::Married),
+                    _ => None,
+                };
+                imported.translations = forms.collect();
+                Some(imported)
+            })
+            .collect();
+        for event in self.events(&person.facts) {
+            individual.add_event(event);
+        }
+        individual
+    }
+
+    /// The events for `facts`, leaving out those of a type this crate has no counterpart for
+    fn events(&self, facts: &[Fact]) -> Vec<Event> {
+        facts
+            .iter()
+            .filter_map(|fact| {
+                let fact_type = type_name(&fact.fact_type)?;
+                let (event_type, _) = FACT_TYPES.iter().find(|(_, name)| *name == fact_type)?;
+                let mut event = Event::new(event_type.clone());
+                event.value.clone_from(&fact.value);
+                event.date = fact
+                    .date
+                    .as_ref()
+                    .and_then(|date| date.original.as_deref())
+                    .map(GedcomDate::parse);
+                event.place = fact.place.as_ref().and_then(|place| self.place(place));
+                for source in &fact.sources {
+                    event.add_citation(self.citation(source));
+                }
+                Some(event)
+            })
+            .collect()
+    }
+
+    fn place(&self, place: &PlaceReference) -> Option<Place> {
+        let description = place.description.as_deref().and_then(|place_id| {
+            let place_id = place_id.trim_start_matches('#');
+            self.document.places.iter().find(|p| p.id == place_id)
+        });
+        let value = place
+            .original
+            .clone()
+            .or_else(|| Some(description?.names.first()?.value.clone()))?;
+        Some(Place {
+            latitude: description.and_then(|description| description.latitude),
+            longitude: description.and_then(|description| description.longitude),
+            ..Place::new(&value)
+        })
+    }
+
+    /// The citation for `source`, which refers either to a source or to a cited page of one
+    fn citation(&self, source: &SourceReference) -> SourceCitation {
+        let source_id = source.description.trim_start_matches('#');
+        let page = self
+            .document
+            .source_descriptions
+            .iter()
+            .filter(|description| description.id.as_deref() == Some(source_id))
+            .find_map(|page| Some((page, page.component_of.as_ref()?)));
+        match page {
+            Some((page, source)) => SourceCitation {
+                xref: xref(&source.description),
+                page: page
+                    .citations
+                    .first()
+                    .map(|citation| citation.value.clone()),
+                quality: None,
+                notes: Vec::new(),
+            },
+            None => SourceCitation {
+                xref: xref(source_id),
+                page: None,
+                quality: None,
+                notes: Vec::new(),
+            },
+        }
+    }
+
+    /// Adds `family` with `spouses` to the tree, linking the spouses to it
+    fn add_family(&mut self, mut family: Family, spouses: &[String]) {
+        let family_xref = family.xref.clone().unwrap_or_default();
+        for (index, spouse) in spouses.iter().enumerate() {
+            if index == 0 {
+                family.set_individual1(spouse.clone());
+            } else {
+                family.set_individual2(spouse.clone());
+            }
+            if let Some(individual) = self.individual_mut(spouse) {
+                individual.add_family(FamilyLink::new(family_xref.clone(), FamilyLinkType::Spouse));
+            }
+        }
+        self.data.add_family(family);
+    }
+
+    /// Adds `child` to the families of its `parents`, adding a family for parents who do not
+    /// have one yet
+    fn add_child(&mut self, child: &str, parents: &mut Parents) {
+        while !parents.is_empty() {
+            let count = parents.len().min(2);
+            let is_parent = |xref: &String| parents.iter().any(|(parent, _)| parent == xref);
+            let existing = self.data.families.iter().position(|family| {
+                let spouses: Vec<&String> = family
+                    .individual1
+                    .iter()
+                    .chain(&family.individual2)
+                    .collect();
+                spouses.len() == count && spouses.into_iter().all(is_parent)
+            });
+            let index = if let Some(index) = existing {
+                index
+            } else {
+                let spouses: Vec<String> = parents[..count]
+                    .iter()
+                    .map(|(parent, _)| parent.clone())
+                    .collect();
+                let family_xref = self.free_family_xref();
+                self.add_family(Family::new(Some(family_xref)), &spouses);
+                self.data.families.len() - 1
+            };
+
+            let family = &mut self.data.families[index];
+            let spouses: Vec<String> = family
+                .individual1
+                .iter()
+                .chain(&family.individual2)
+                .cloned()
+                .collect();
+            family.add_child(child.to_string());
+            let family_xref = family.xref.clone().unwrap_or_default();
+
+            let mut pedigree = None;
+            parents.retain_mut(|(parent, parent_pedigree)| {
+                let covered = spouses.contains(parent);
+                if covered && pedigree.is_none() {
+                    pedigree = parent_pedigree.take();
+                }
+                !covered
+            });
+            if let Some(individual) = self.individual_mut(child) {
+                let mut link = FamilyLink::new(family_xref, FamilyLinkType::Child);
+                if let Some(pedigree) = pedigree {
+                    link.set_pedigree(pedigree);
+                }
+                individual.add_family(link);
+            }
+        }
+    }
+
+    fn individual_mut(&mut self, xref: &str) -> Option<&mut Individual> {
+        self.data
+            .individuals
+            .iter_mut()
+            .find(|individual| individual.xref.as_deref() == Some(xref))
+    }
+
+    /// The first `@F{n}@` neither taken by a family nor by a relationship still to import
+    fn free_family_xref(&self) -> String {
+        let in_use: Vec<String> = self
+            .data
+            .families
+            .iter()
+            .filter_map(|family| family.xref.clone())
+            .chain(
+                self.document
+                    .relationships
+                    .iter()
+                    .filter_map(|relationship| relationship.id.as_deref().map(xref)),
+            )
+            .collect();
+        // one of these is free, as each xref in use rules out at most one
+        (1..=in_use.len() + 1)
+            .map(|n| format!("@F{n}@"))
+            .find(|xref| !in_use.contains(xref))
+            .unwrap_or_default()
+    }
+}
+
+fn import_name(form: &NameForm) -> crate::types::Name {
+    let part = |part_type: &str| {
+        form.parts
+            .iter()
+            .find(|part| type_name(&part.part_type) == Some(part_type))
+            .map(|part| part.value.clone())
+    };
+    let mut name = crate::types::Name {
+        given: part("Given"),
+        surname: part("Surname"),
+        prefix: part("Prefix"),
+        suffix: part("Suffix"),
+        language: form.lang.clone(),
+        ..crate::types::Name::default()
+    };
+    name.value = match &name.surname {
+        Some(surname) => {
+            let surname = format!("/{surname}/");
+            let value: Vec<&str> = [
+                name.prefix.as_deref(),
+                name.given.as_deref(),
+                Some(surname.as_str()),
+                name.suffix.as_deref(),
+            ]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+            Some(value.join(" "))
+        }
+        None => form.full_text.clone(),
+    };
+    name
+}
+
+/// The name of a type URI of GEDCOM X, _ie._ `Birth` for `http://gedcomx.org/Birth`
+fn type_name(uri: &str) -> Option<&str> {
+    uri.strip_prefix(TYPE_BASE)
+}
+
+/// The xref of a resource, _ie._ `@I1@` for `I1` or `#I1`
+fn xref(resource: &str) -> String {
+    format!("@{}@", resource.trim_start_matches('#'))
+}
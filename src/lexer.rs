@@ -0,0 +1,259 @@
+//! Zero-copy tokenization of GEDCOM text held in memory
+//!
+//! The `Lexer` produces tokens borrowing slices of the input, each with the byte range
+//! it was found at. The owned `tokenizer::Tokenizer` is built on the same line splitting.
+use std::ops::Range;
+
+use crate::error::ParseError;
+
+/// A token borrowing its text from the input
+///
+/// making use of [GEDCOM Standard Release 5.5.1](https://edge.fscdn.org/assets/img/documents/ged551-5bac5e57fe88dd37df0e153d9c515335.pdf), p.11
+/// `gedcom_line: level + delim + [optional_xref_ID] + tag + [optional_line_value] + terminator`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// The `level`, denoting the depth within the tree
+    Level(u8),
+    /// The `tag`, a four character code that distinguishes datatypes
+    Tag(&'a str),
+    /// The value of the data: `optional_line_value`
+    LineValue(&'a str),
+    /// The `optional_xref_ID` used throughout the file to refer to a particular face
+    Pointer(&'a str),
+    /// A user-defined tag, always begins with an underscore
+    CustomTag(&'a str),
+}
+
+/// A token along with where it was found in the input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpannedToken<'a> {
+    /// The token itself
+    pub token: Token<'a>,
+    /// Byte offsets of the token's text within the input
+    pub span: Range<usize>,
+    /// The line number of the token, starting from 1
+    pub line: u32,
+}
+
+/// Splits GEDCOM text into borrowed tokens, without allocating.
+///
+/// Blank lines are skipped, and lines may end in `\n`, `\r\n` or `\r`.
+///
+/// ```
+/// use gedcom::lexer::{Lexer, Token};
+///
+/// let lexer = Lexer::new("0 @I1@ INDI\n1 NAME John /Doe/\n");
+/// let tokens: Vec<Token> = lexer.map(|spanned| spanned.unwrap().token).collect();
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         Token::Level(0),
+///         Token::Pointer("@I1@"),
+///         Token::Tag("INDI"),
+///         Token::Level(1),
+///         Token::Tag("NAME"),
+///         Token::LineValue("John /Doe/"),
+///     ]
+/// );
+///
+/// let name = Lexer::new("0 @I1@ INDI\n1 NAME John /Doe/\n").nth(4).unwrap().unwrap();
+/// assert_eq!(name.token, Token::Tag("NAME"));
+/// assert_eq!((name.span, name.line), (14..18, 2));
+/// ```
+pub struct Lexer<'a> {
+    source: &'a str,
+    /// Byte offset of the start of the next line
+    pos: usize,
+    /// Byte offset of the start of the current line
+    line_start: usize,
+    /// The current line number
+    line: u32,
+    /// The parts of the current line not yet returned
+    parts: LineParts,
+}
+
+impl<'a> Lexer<'a> {
+    /// Creates a lexer over the gedcom file contents
+    #[must_use]
+    pub fn new(source: &'a str) -> Lexer<'a> {
+        Lexer {
+            source,
+            pos: 0,
+            line_start: 0,
+            line: 0,
+            parts: LineParts::default(),
+        }
+    }
+
+    /// The next token of the input, or `None` at the end of the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` when a line has no valid level number.
+    pub fn next_token(&mut self) -> Result<Option<SpannedToken<'a>>, ParseError> {
+        let line = &self.source[self.line_start..self.pos];
+        if let Some((token, span)) = self.parts.next_part(line) {
+            return Ok(Some(SpannedToken {
+                token,
+                span: self.line_start + span.start..self.line_start + span.end,
+                line: self.line,
+            }));
+        }
+
+        while self.pos < self.source.len() {
+            let rest = &self.source[self.pos..];
+            let len = rest
+                .bytes()
+                .position(|b| b == b'\n' || b == b'\r')
+                .unwrap_or(rest.len());
+            let terminator = match rest.as_bytes().get(len..len + 2) {
+                Some(b"\r\n") => 2,
+                _ => usize::from(len < rest.len()),
+            };
+
+            self.line_start = self.pos;
+            self.pos += len + terminator;
+            self.line += 1;
+
+            let line = &rest[..len];
+            if let Some((level, span, parts)) = LineParts::split(line, self.line)? {
+                self.parts = parts;
+                return Ok(Some(SpannedToken {
+                    token: Token::Level(level),
+                    span: self.line_start + span.start..self.line_start + span.end,
+                    line: self.line,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<SpannedToken<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token().transpose()
+    }
+}
+
+/// The byte ranges of the parts of a line that follow its level,
+/// relative to the start of the line
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LineParts {
+    pointer: Option<Range<usize>>,
+    tag: Option<Range<usize>>,
+    value: Option<Range<usize>>,
+}
+
+impl LineParts {
+    /// Splits a line without its terminator into its level, the range of the level,
+    /// and the ranges of its remaining parts. Returns `None` for a blank line.
+    pub(crate) fn split(
+        line: &str,
+        line_no: u32,
+    ) -> Result<Option<(u8, Range<usize>, LineParts)>, ParseError> {
+        let start = skip_whitespace(line, 0);
+        if start == line.len() {
+            return Ok(None);
+        }
+
+        let digits_end = start + line[start..].bytes().take_while(u8::is_ascii_digit).count();
+        let digits = &line[start..digits_end];
+        let level = digits.parse::<u8>().map_err(|_| ParseError::InvalidLevel {
+            line: line_no,
+            found: if digits.is_empty() {
+                line[start..end_of_word(line, start)].to_string()
+            } else {
+                digits.to_string()
+            },
+            xref: None,
+        })?;
+
+        let mut parts = LineParts::default();
+        let mut pos = skip_whitespace(line, digits_end);
+        if line[pos..].starts_with('@') {
+            let end = end_of_word(line, pos);
+            parts.pointer = Some(pos..end);
+            pos = skip_whitespace(line, end);
+        }
+        if pos < line.len() {
+            let end = end_of_word(line, pos);
+            parts.tag = Some(pos..end);
+            pos = skip_whitespace(line, end);
+            if pos < line.len() {
+                parts.value = Some(pos..line.len());
+            }
+        }
+        Ok(Some((level, start..digits_end, parts)))
+    }
+
+    /// Whether `line` is written as `level [xref] tag [value]` separated by single spaces,
+    /// with no other whitespace and no leading zeros on the level
+    pub(crate) fn is_regular(&self, line: &str, level: &Range<usize>) -> bool {
+        if level.start != 0 || (level.len() > 1 && line.starts_with('0')) {
+            return false;
+        }
+        let mut end = level.end;
+        for part in [&self.pointer, &self.tag, &self.value]
+            .iter()
+            .copied()
+            .flatten()
+        {
+            if part.start != end + 1 || line.as_bytes()[end] != b' ' {
+                return false;
+            }
+            end = part.end;
+        }
+        end == line.len()
+    }
+
+    /// Takes the next part of `line` in the order they appear on the line
+    pub(crate) fn next_part<'l>(&mut self, line: &'l str) -> Option<(Token<'l>, Range<usize>)> {
+        if let Some(span) = self.pointer.take() {
+            Some((Token::Pointer(&line[span.clone()]), span))
+        } else if let Some(span) = self.tag.take() {
+            let tag = &line[span.clone()];
+            if tag.starts_with('_') {
+                Some((Token::CustomTag(tag), span))
+            } else {
+                Some((Token::Tag(tag), span))
+            }
+        } else {
+            let span = self.value.take()?;
+            Some((Token::LineValue(&line[span.clone()]), span))
+        }
+    }
+}
+
+/// Byte offset of the first character from `pos` that is not whitespace
+fn skip_whitespace(line: &str, pos: usize) -> usize {
+    find_from(line, pos, |c| !(c.is_whitespace() || c == '\u{feff}'))
+}
+
+/// Byte offset of the first whitespace character from `pos`
+fn end_of_word(line: &str, pos: usize) -> usize {
+    find_from(line, pos, char::is_whitespace)
+}
+
+/// Byte offset of the first character from `pos` matching `predicate`, or the
+/// length of the line. ASCII is checked byte by byte without decoding.
+fn find_from(line: &str, pos: usize, predicate: impl Fn(char) -> bool) -> usize {
+    let bytes = line.as_bytes();
+    let mut i = pos;
+    while i < bytes.len() {
+        if bytes[i].is_ascii() {
+            if predicate(char::from(bytes[i])) {
+                return i;
+            }
+            i += 1;
+        } else {
+            let c = line[i..].chars().next().unwrap_or_default();
+            if predicate(c) {
+                return i;
+            }
+            i += c.len_utf8();
+        }
+    }
+    bytes.len()
+}
@@ -7,13 +7,50 @@ use gedcom::parser::Parser;
 let gedcom_source = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
 
 let mut parser = Parser::new(gedcom_source.chars());
-let gedcom_data = parser.parse_record();
+let gedcom_data = parser.try_parse().expect("malformed gedcom file");
 
 // output some stats on the gedcom contents
 gedcom_data.stats();
 ```
 
+`Parser::try_parse` returns a `ParseError` describing the offending line of a malformed
+file, while `Parser::parse_record` panics instead. Problems the parser can recover from, like
+unhandled tags, are collected as `Diagnostic`s rather than printed:
+
+```rust
+# let gedcom_source = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
+let (gedcom_data, diagnostics) = gedcom::parse_with_diagnostics(gedcom_source.chars()).unwrap();
+
+for diagnostic in &diagnostics {
+    eprintln!("{}", diagnostic);
+}
+```
+
+`Parser::with_options` takes `ParserOptions` to choose between a strict conformance check and a
+best-effort import:
+
+```rust
+use gedcom::parser::{Parser, ParserOptions};
+
+# let gedcom_source = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
+let mut parser = Parser::with_options(gedcom_source.chars(), ParserOptions::lenient());
+let gedcom_data = parser.try_parse().unwrap();
+```
+
+The parser reads from any char iterator. `Parser::from_reader` decodes the bytes of a `BufRead`
+as it goes, so files, sockets and stdin can be parsed without reading them into memory first.
+The encoding is detected from a byte order mark or the `HEAD.CHAR` value, see `decode`:
+
+```rust
+use gedcom::parser::Parser;
+use std::io::BufReader;
+
+let file = std::fs::File::open("./tests/fixtures/sample.ged").unwrap();
+let gedcom_data = Parser::from_reader(BufReader::new(file)).try_parse().unwrap();
+```
+
 This crate contains an optional `"json"` feature that implements serialization & deserialization to json with [`serde`](https://serde.rs).
+The `"gedcomx"` feature builds on it to import and export [GEDCOM X](https://github.com/FamilySearch/gedcomx) JSON, see `gedcomx`.
 */
 
 #![deny(clippy::pedantic)]
@@ -22,9 +59,27 @@ This crate contains an optional `"json"` feature that implements serialization &
 #[macro_use]
 mod util;
 
+pub mod convert;
+pub mod decode;
+
+pub mod diagnostic;
+pub use diagnostic::{Diagnostic, DiagnosticKind, Severity};
+
+pub mod error;
+pub use error::ParseError;
+
+#[cfg(feature = "gedcomx")]
+pub mod gedcomx;
+
+pub mod lexer;
+pub mod line;
+pub mod node;
+pub use node::GedcomNode;
 pub mod parser;
+pub mod reader;
 pub mod tokenizer;
 pub mod types;
+pub mod writer;
 pub mod ffi;
 
 mod tree;
@@ -32,7 +87,36 @@ pub use tree::GedcomData;
 
 #[must_use]
 /// Helper function for converting GEDCOM file content stream to parsed data.
-pub fn parse(content: std::str::Chars) -> GedcomData {
+///
+/// # Panics
+///
+/// Panics when the content is malformed. Use `try_parse` to handle these errors.
+pub fn parse<I: Iterator<Item = char>>(content: I) -> GedcomData {
     let mut p = parser::Parser::new(content);
     p.parse_record()
 }
+
+/// Helper function for converting GEDCOM file content stream to parsed data,
+/// returning an error instead of panicking on malformed content.
+///
+/// # Errors
+///
+/// Returns a `ParseError` with the line, tag and record xref of the first fatal problem.
+pub fn try_parse<I: Iterator<Item = char>>(content: I) -> Result<GedcomData, ParseError> {
+    let mut p = parser::Parser::new(content);
+    p.try_parse()
+}
+
+/// Helper function for converting GEDCOM file content stream to parsed data,
+/// along with the non-fatal problems found while parsing it.
+///
+/// # Errors
+///
+/// Returns a `ParseError` with the line, tag and record xref of the first fatal problem.
+pub fn parse_with_diagnostics<I: Iterator<Item = char>>(
+    content: I,
+) -> Result<(GedcomData, Vec<Diagnostic>), ParseError> {
+    let mut p = parser::Parser::new(content);
+    let data = p.try_parse()?;
+    Ok((data, p.take_diagnostics()))
+}
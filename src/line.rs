@@ -0,0 +1,25 @@
+//! The terminators ending the lines of a GEDCOM file
+
+/// The terminator of each line of a file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// A line feed, as on Unix
+    #[default]
+    Lf,
+    /// A carriage return and a line feed, as on Windows
+    CrLf,
+    /// A carriage return, as on classic Mac OS
+    Cr,
+}
+
+impl LineEnding {
+    /// The characters ending a line
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
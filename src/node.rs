@@ -0,0 +1,87 @@
+//! The raw tree of GEDCOM lines, beneath the typed data structures
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// A single line of a GEDCOM file along with its subordinate lines.
+///
+/// Every line of a file is kept in this tree, whether or not the typed parser
+/// understands its tag, so no data is lost on import.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct GedcomNode {
+    /// The `level`, denoting the depth within the tree
+    pub level: u8,
+    /// The `optional_xref_ID` of the line, _ie._ `@I1@`
+    pub xref: Option<String>,
+    /// The tag of the line. User-defined tags begin with an underscore.
+    pub tag: String,
+    /// The `optional_line_value` of the line
+    pub value: Option<String>,
+    /// The lines one level below this one, in file order
+    pub children: Vec<GedcomNode>,
+    /// The line number of the line in the file it was read from, or 0 for new nodes
+    pub line: u32,
+    /// The text of the line as it was read, kept only when it differs from how the
+    /// writer would write it, _eg._ when it is indented or has trailing spaces
+    pub raw: Option<String>,
+    /// A digest of the lines the typed record parsed from this node writes, taken when it
+    /// was parsed, so the writer can tell whether the record was edited since
+    pub(crate) digest: Option<u64>,
+}
+
+impl GedcomNode {
+    /// Creates a node with no xref, value or children
+    #[must_use]
+    pub fn new(level: u8, tag: &str) -> GedcomNode {
+        GedcomNode {
+            level,
+            tag: tag.to_string(),
+            ..GedcomNode::default()
+        }
+    }
+
+    /// Appends a child one level below this node, returning it to add children to
+    pub fn push_child(&mut self, tag: &str, value: Option<String>) -> &mut GedcomNode {
+        let mut child = GedcomNode::new(self.level + 1, tag);
+        child.value = value;
+        self.children.push(child);
+        let last = self.children.len() - 1;
+        &mut self.children[last]
+    }
+
+    /// Whether the tag is user-defined, beginning with an underscore
+    #[must_use]
+    pub fn is_custom(&self) -> bool {
+        self.tag.starts_with('_')
+    }
+
+    /// The first child with the given tag
+    #[must_use]
+    pub fn child(&self, tag: &str) -> Option<&GedcomNode> {
+        self.children.iter().find(|child| child.tag == tag)
+    }
+
+    /// The children with the given tag
+    pub fn children_with_tag<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = &'a GedcomNode> + 'a {
+        self.children.iter().filter(move |child| child.tag == tag)
+    }
+
+    /// Visits this node and all of its descendants, parents before their children
+    pub fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a GedcomNode)) {
+        visit(self);
+        for child in &self.children {
+            child.walk(visit);
+        }
+    }
+}
+
+/// Whether a value is a pointer to a record, an xref such as `@I1@`
+pub(crate) fn is_pointer(value: &str) -> bool {
+    value.len() > 2
+        && value.starts_with('@')
+        && value.ends_with('@')
+        && !value[1..value.len() - 1].contains(|c: char| c == '@' || c.is_whitespace())
+}
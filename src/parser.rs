@@ -1,775 +1,2001 @@
 //! The state machine that parses a char iterator of the gedcom's contents
-use std::{panic, str::Chars};
+use std::io::{self, BufRead};
 
+use crate::diagnostic::{Diagnostic, DiagnosticKind, Severity};
+use crate::error::ParseError;
+use crate::node::{is_pointer, GedcomNode};
+use crate::reader::ReadChars;
 use crate::tokenizer::{Token, Tokenizer};
 use crate::tree::GedcomData;
 use crate::types::{
-    event::HasEvents, Address, CustomData, Event, Family, FamilyLink, Gender, Header, Individual,
-    Name, RepoCitation, Repository, Source, SourceCitation, Submitter,
+    event::HasEvents, Address, Age, Association, Corporation, CustomData, Event, EventType,
+    ExtensionTag, ExternalId, Family, FamilyLink, FamilyLinkType, GedcomDate, GedcomVersion,
+    Gender, Header, HeaderSource, HeaderSourceData, Individual, Media, MediaFile, MediaLink, Name,
+    NameType, NameVariant, Note, NoteStructure, Pedigree, Place, PlaceVariant, Record,
+    RepoCitation, Repository, Source, SourceCitation, Submission, Submitter, Translation,
+    UserReference,
 };
+use crate::writer::{self, WriterOptions};
+
+/// How the parser treats a value outside of those allowed by the specification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValuePolicy {
+    /// Stop parsing with a `ParseError`
+    Error,
+    /// Report an error `Diagnostic` and carry on without the value
+    Warn,
+    /// Silently carry on without the value
+    Ignore,
+}
+
+/// Options controlling how strictly the parser follows the specification
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParserOptions {
+    /// Turns every diagnostic of `Severity::Error` into a fatal `ParseError::Nonconforming`,
+    /// rejecting anything that does not conform to GEDCOM 5.5.1, such as a tag its grammar
+    /// does not allow where it is found. Tags it allows that the parser skips, and
+    /// user-defined tags, are still only reported.
+    pub strict: bool,
+    /// Stores unhandled tags within records as `CustomData` on their record. They are
+    /// kept in the `GedcomNode` of the record either way.
+    pub keep_unknown_tags: bool,
+    /// Whether a second `HUSB` or `WIFE` in a family is a `ParseError`.
+    /// Otherwise the first one is kept and an error diagnostic is reported.
+    pub duplicate_spouse_is_error: bool,
+    /// Whether a second family event of the same type is a `ParseError`. Otherwise both
+    /// are kept without a diagnostic, as a family may well have married twice or been
+    /// counted in several censuses. GEDCOM 5.5.1 allows it, so even `strict` leaves it off.
+    pub duplicate_family_event_is_error: bool,
+    /// Whether a `TIME` of the header that does not follow its `DATE` is a `ParseError`.
+    /// Otherwise the time is kept and an error diagnostic is reported.
+    pub time_without_date_is_error: bool,
+    /// How to treat a `PEDI` value other than adopted, birth, foster or sealing
+    pub unrecognized_pedigree: ValuePolicy,
+    /// How to treat a `SEX` value other than M, F, N or U
+    pub unrecognized_sex: ValuePolicy,
+}
+
+impl ParserOptions {
+    /// Options for a conformance check, failing on the first deviation from the specification
+    #[must_use]
+    pub fn strict() -> ParserOptions {
+        ParserOptions {
+            strict: true,
+            keep_unknown_tags: false,
+            duplicate_spouse_is_error: true,
+            duplicate_family_event_is_error: false,
+            time_without_date_is_error: true,
+            unrecognized_pedigree: ValuePolicy::Error,
+            unrecognized_sex: ValuePolicy::Error,
+        }
+    }
+
+    /// Options for a best-effort import, recovering from every problem it can
+    #[must_use]
+    pub fn lenient() -> ParserOptions {
+        ParserOptions {
+            strict: false,
+            keep_unknown_tags: true,
+            duplicate_spouse_is_error: false,
+            duplicate_family_event_is_error: false,
+            time_without_date_is_error: false,
+            unrecognized_pedigree: ValuePolicy::Warn,
+            unrecognized_sex: ValuePolicy::Warn,
+        }
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            strict: false,
+            keep_unknown_tags: false,
+            duplicate_spouse_is_error: true,
+            duplicate_family_event_is_error: false,
+            time_without_date_is_error: true,
+            unrecognized_pedigree: ValuePolicy::Error,
+            unrecognized_sex: ValuePolicy::Warn,
+        }
+    }
+}
 
 /// The Gedcom parser that converts the token list into a data structure
-pub struct Parser<'a> {
-    tokenizer: Tokenizer<'a>,
+pub struct Parser<I: Iterator<Item = char>> {
+    tokenizer: Tokenizer<I>,
+    options: ParserOptions,
+    /// Error raised by the tokenizer while loading the very first token
+    pending_error: Option<ParseError>,
+    /// Non-fatal problems found so far
+    diagnostics: Vec<Diagnostic>,
+    /// Xref of the top-level record being parsed
+    record_xref: Option<String>,
+    /// Tags of the structures enclosing the lines being parsed
+    tag_path: Vec<String>,
+    /// The version of the specification the file follows, known once the header is parsed
+    version: GedcomVersion,
+    /// Whether the trailer or the end of the file has been reached
+    finished: bool,
+    /// Takes the error that cut the input short, for sources that can fail
+    take_read_error: fn(&mut I) -> Option<io::Error>,
+}
+
+/// Iterator over the raw top-level nodes of a file, created by `Parser::nodes`
+pub struct Nodes<'p, I: Iterator<Item = char>> {
+    parser: &'p mut Parser<I>,
+    done: bool,
 }
 
-impl<'a> Parser<'a> {
+impl<I: Iterator<Item = char>> Iterator for Nodes<'_, I> {
+    type Item = Result<GedcomNode, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.parser.next_node() {
+            Ok(Some(node)) => Some(Ok(node)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Iterator over the top-level records of a file, created by `Parser::records`
+pub struct Records<'p, I: Iterator<Item = char>> {
+    parser: &'p mut Parser<I>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = char>> Iterator for Records<'_, I> {
+    type Item = Result<Record, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.parser.next_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Parser<ReadChars<R>> {
+    /// Creates a parser state machine reading gedcom contents from `reader`.
+    ///
+    /// The input is decoded as it is parsed rather than read into memory up front. Its
+    /// encoding is detected with `decode::detect` from the start of the input, read up to
+    /// the `CHAR` line of the header, and invalid byte sequences are replaced by `U+FFFD`.
+    /// A read error ends parsing with `ParseError::Io`.
+    #[must_use]
+    pub fn from_reader(reader: R) -> Parser<ReadChars<R>> {
+        Parser::from_reader_with_options(reader, ParserOptions::default())
+    }
+
+    /// Creates a parser state machine reading gedcom contents from `reader`
+    /// that follows the given `ParserOptions`
+    #[must_use]
+    pub fn from_reader_with_options(reader: R, options: ParserOptions) -> Parser<ReadChars<R>> {
+        // the tokenizer drops the byte order mark itself, noting that the file had one
+        let chars = ReadChars::new(reader).keep_byte_order_mark();
+        let mut parser = Parser::with_options(chars, options);
+        parser.take_read_error = ReadChars::take_error;
+        parser
+    }
+}
+
+impl<I: Iterator<Item = char>> Parser<I> {
     /// Creates a parser state machine for parsing a gedcom file as a chars iterator
     #[must_use]
-    pub fn new(chars: Chars<'a>) -> Parser {
+    pub fn new(chars: I) -> Parser<I> {
+        Parser::with_options(chars, ParserOptions::default())
+    }
+
+    /// Creates a parser state machine that follows the given `ParserOptions`
+    #[must_use]
+    pub fn with_options(chars: I, options: ParserOptions) -> Parser<I> {
         let mut tokenizer = Tokenizer::new(chars);
-        tokenizer.next_token();
-        Parser { tokenizer }
+        let pending_error = tokenizer.next_token().err();
+        Parser {
+            tokenizer,
+            options,
+            pending_error,
+            diagnostics: Vec::new(),
+            record_xref: None,
+            tag_path: Vec::new(),
+            version: GedcomVersion::default(),
+            finished: false,
+            take_read_error: |_| None,
+        }
+    }
+
+    /// The non-fatal problems found while parsing, in the order they were found.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the non-fatal problems found while parsing.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// The version of the specification the file follows, from the `GEDC.VERS` of its
+    /// header. Files are taken to be GEDCOM 5.5.1 until the header says otherwise.
+    ///
+    /// ```
+    /// use gedcom::parser::Parser;
+    /// use gedcom::types::GedcomVersion;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 TRLR";
+    /// let mut parser = Parser::new(source.chars());
+    /// parser.try_parse().unwrap();
+    /// assert_eq!(parser.version(), GedcomVersion::V7);
+    /// ```
+    #[must_use]
+    pub fn version(&self) -> GedcomVersion {
+        self.version
+    }
+
+    /// Options for writing the parsed records back out the way they were read, with the
+    /// same line endings and byte order mark, and with the header kept.
+    #[must_use]
+    pub fn writer_options(&self) -> WriterOptions {
+        WriterOptions {
+            line_ending: self.tokenizer.line_ending().unwrap_or_default(),
+            byte_order_mark: self.tokenizer.byte_order_mark(),
+            final_line_ending: self.tokenizer.ends_with_line_ending(),
+            keep_header: true,
+            version: self.version,
+        }
     }
 
     /// Does the actual parsing of the record.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the file is malformed. Use `try_parse` to handle these errors.
     pub fn parse_record(&mut self) -> GedcomData {
+        self.try_parse().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Parses the records of the file, stopping at the first fatal error.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` describing the offending line when the file is malformed.
+    pub fn try_parse(&mut self) -> Result<GedcomData, ParseError> {
         let mut data = GedcomData::default();
-        loop {
-            let level = match self.tokenizer.current_token {
-                Token::Level(n) => n,
-                _ => panic!(
-                    "{} Expected Level, found {:?}",
-                    self.dbg(),
-                    self.tokenizer.current_token
-                ),
-            };
-
-            self.tokenizer.next_token();
-
-            let mut pointer: Option<String> = None;
-            if let Token::Pointer(xref) = &self.tokenizer.current_token {
-                pointer = Some(xref.to_string());
-                self.tokenizer.next_token();
-            }
-
-            if let Token::Tag(tag) = &self.tokenizer.current_token {
-                match tag.as_str() {
-                    "HEAD" => data.header = self.parse_header(),
-                    "FAM" => data.add_family(self.parse_family(level, pointer)),
-                    "INDI" => data.add_individual(self.parse_individual(level, pointer)),
-                    "REPO" => self.skip_level(level),
-                    "SOUR" => self.skip_level(level),
-                    "SUBM" => self.skip_level(level),
-                    "TRLR" => break,
-                    _ => {
-                        println!("WARNING: {} Unhandled tag {}", self.dbg(), tag);
-                        self.skip_level(level);
-                    }
-                };
-            } else if let Token::CustomTag(tag) = &self.tokenizer.current_token {
-                // TODO
-                let tag_clone = tag.clone();
-                let custom_data = self.parse_custom_tag(tag_clone);
-                println!(
-                    "WARNING: {} Skipping top-level custom tag: {:?}",
-                    self.dbg(),
-                    custom_data
-                );
-                self.skip_level(level);
-            } else {
-                println!(
-                    "WARNING: {} Unhandled token {:?}",
-                    self.dbg(),
-                    self.tokenizer.current_token
-                );
-                self.skip_level(level);
-            };
+        while let Some(record) = self.next_record()? {
+            data.add_record(record);
         }
+        Ok(data)
+    }
 
-        data
+    /// Iterates over the top-level records of the file, parsing one at a time.
+    ///
+    /// Only the record being parsed is held in memory, so this suits files too large
+    /// to build a `GedcomData` from. The iterator ends at the `TRLR` record, or after
+    /// yielding the first fatal error.
+    ///
+    /// ```
+    /// use gedcom::parser::Parser;
+    /// use gedcom::types::Record;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+    /// let mut parser = Parser::new(source.chars());
+    /// let mut individuals = 0;
+    /// for record in parser.records() {
+    ///     if let Record::Individual(_) = record.unwrap() {
+    ///         individuals += 1;
+    ///     }
+    /// }
+    /// assert_eq!(individuals, 1);
+    /// ```
+    pub fn records(&mut self) -> Records<'_, I> {
+        Records {
+            parser: self,
+            done: false,
+        }
     }
 
-    /// Parses HEAD top-level tag
-    fn parse_header(&mut self) -> Header {
-        // skip over HEAD tag name
-        self.tokenizer.next_token();
+    /// Iterates over the raw top-level nodes of the file, including the `TRLR`,
+    /// without building typed records from them.
+    ///
+    /// ```
+    /// use gedcom::parser::Parser;
+    ///
+    /// let source = "0 @I1@ INDI\n1 NAME John /Doe/\n1 _HAIR brown\n0 TRLR";
+    /// let mut parser = Parser::new(source.chars());
+    /// let individual = parser.nodes().next().unwrap().unwrap();
+    /// assert_eq!(individual.xref.as_deref(), Some("@I1@"));
+    /// assert_eq!(individual.children[1].tag, "_HAIR");
+    /// assert_eq!(individual.children[1].value.as_deref(), Some("brown"));
+    /// ```
+    pub fn nodes(&mut self) -> Nodes<'_, I> {
+        Nodes {
+            parser: self,
+            done: false,
+        }
+    }
+
+    /// Parses the next top-level record, returning `None` once the file has ended.
+    ///
+    /// Records the parser does not handle are returned whole as `Record::Custom`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` describing the offending line when the file is malformed.
+    pub fn next_record(&mut self) -> Result<Option<Record>, ParseError> {
+        let record = self.read_record();
+        // a failed read ends the input early, so it explains any other error
+        self.check_read_error()?;
+        record
+    }
+
+    /// Reads the next top-level line along with all of its subordinate lines,
+    /// returning `None` at the end of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` when a line has no valid level number.
+    pub fn next_node(&mut self) -> Result<Option<GedcomNode>, ParseError> {
+        let node = self.read_node();
+        self.check_read_error()?;
+        node
+    }
+
+    /// Builds the typed record for a top-level node, reporting problems with it as
+    /// diagnostics of this parser. The node is kept on the record it becomes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` when the record breaks the specification in a way the
+    /// `ParserOptions` do not allow.
+    pub fn parse_node(&mut self, node: GedcomNode) -> Result<Record, ParseError> {
+        self.record_xref.clone_from(&node.xref);
+        self.tag_path.clear();
+        let xref = node.xref.clone();
+        let mut record = self
+            .parse_top_level(node)
+            .map_err(|err| err.in_record(xref.as_ref()))?;
+        writer::seal(&mut record);
+        Ok(record)
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>, ParseError> {
+        if self.finished {
+            return Ok(None);
+        }
+        let Some(node) = self.read_node()? else {
+            self.finished = true;
+            self.check_read_error()?;
+            self.record_xref = None;
+            self.tag_path.clear();
+            self.report_at(
+                self.tokenizer.line,
+                Vec::new(),
+                Severity::Error,
+                DiagnosticKind::MissingTrailer,
+                "File ended without a TRLR record".to_string(),
+            )?;
+            return Ok(None);
+        };
+        if node.tag == "TRLR" {
+            self.finished = true;
+            return Ok(None);
+        }
+        self.parse_node(node).map(Some)
+    }
+
+    fn read_node(&mut self) -> Result<Option<GedcomNode>, ParseError> {
+        if let Some(err) = self.pending_error.take() {
+            return Err(err);
+        }
+
+        let level = match self.tokenizer.current_token {
+            Token::Level(n) => n,
+            Token::EOF => return Ok(None),
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    line: self.tokenizer.line,
+                    tag: None,
+                    found: format!("{:?}", self.tokenizer.current_token),
+                    xref: None,
+                })
+            }
+        };
+
+        // the open nodes, each a child of the one before it
+        let mut open = vec![self.read_line(level)?];
+        while let Token::Level(level) = self.tokenizer.current_token {
+            if level <= open[0].level {
+                break;
+            }
+            let node = self
+                .read_line(level)
+                .map_err(|err| err.in_record(open[0].xref.as_ref()))?;
+            close_nodes(&mut open, level);
+            open.push(node);
+        }
+        close_nodes(&mut open, 0);
+        Ok(open.pop())
+    }
+
+    /// Reads the line starting at the current `Level` token into a node without children
+    fn read_line(&mut self, level: u8) -> Result<GedcomNode, ParseError> {
+        let mut node = GedcomNode {
+            level,
+            line: self.tokenizer.line,
+            raw: self.tokenizer.irregular_line().map(str::to_string),
+            ..GedcomNode::default()
+        };
+        self.tokenizer.next_token()?;
+        if let Token::Pointer(xref) = &mut self.tokenizer.current_token {
+            node.xref = Some(std::mem::take(xref));
+            self.tokenizer.next_token()?;
+        }
+        if let Token::Tag(tag) | Token::CustomTag(tag) = &mut self.tokenizer.current_token {
+            node.tag = std::mem::take(tag);
+            self.tokenizer.next_token()?;
+        }
+        if let Token::LineValue(value) = &mut self.tokenizer.current_token {
+            node.value = Some(std::mem::take(value));
+            self.tokenizer.next_token()?;
+        }
+        Ok(node)
+    }
 
+    /// Fails with `ParseError::Io` when reading the input was cut short by an error.
+    fn check_read_error(&mut self) -> Result<(), ParseError> {
+        match (self.take_read_error)(self.tokenizer.source_mut()) {
+            Some(err) => {
+                self.finished = true;
+                Err(ParseError::Io {
+                    line: self.tokenizer.line,
+                    kind: err.kind(),
+                    message: err.to_string(),
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Builds the typed record for a top-level node
+    fn parse_top_level(&mut self, node: GedcomNode) -> Result<Record, ParseError> {
+        let record = match node.tag.as_str() {
+            "HEAD" => Record::Header(self.parse_header(node)?),
+            "FAM" => Record::Family(self.parse_family(node)?),
+            "INDI" => Record::Individual(self.parse_individual(node)?),
+            "NOTE" | "SNOTE" => Record::Note(self.parse_note(node)?),
+            "OBJE" => Record::Media(self.parse_media(node)?),
+            "REPO" => Record::Repository(self.parse_repository(node)?),
+            "SOUR" => Record::Source(self.parse_source(node)?),
+            "SUBM" => Record::Submitter(self.parse_submitter(node)?),
+            "SUBN" => Record::Submission(self.parse_submission(node)?),
+            _ => {
+                if node.is_custom() {
+                    self.warn(
+                        &node,
+                        DiagnosticKind::SkippedCustomTag,
+                        format!("Skipping top-level custom tag: {}", node.tag),
+                    )?;
+                } else if self.version == GedcomVersion::V5 {
+                    // the parser handles every record of GEDCOM 5.5.1
+                    self.report(
+                        &node,
+                        Severity::Error,
+                        DiagnosticKind::InvalidTag,
+                        format!("Invalid tag {}", node.tag),
+                    )?;
+                } else {
+                    self.warn(
+                        &node,
+                        DiagnosticKind::UnhandledTag,
+                        format!("Unhandled tag {}", node.tag),
+                    )?;
+                }
+                Record::Custom(node)
+            }
+        };
+        Ok(record)
+    }
+
+    /// Parses HEAD top-level tag
+    fn parse_header(&mut self, node: GedcomNode) -> Result<Header, ParseError> {
         let mut header = Header::default();
+        // the version decides how the lines before GEDC are read
+        if let Some(version) = node.child("GEDC").and_then(|gedc| gedc.child("VERS")) {
+            self.version = version
+                .value
+                .as_deref()
+                .map_or(GedcomVersion::default(), GedcomVersion::from_number);
+        }
 
-        // just skipping the header for now
-        while self.tokenizer.current_token != Token::Level(0) {
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    // TODO: CHAR.VERS
-                    "CHAR" => header.encoding = Some(self.take_line_value()),
-                    "CORP" => header.corporation = Some(self.take_line_value()),
-                    "COPR" => header.copyright = Some(self.take_line_value()),
-                    "DATE" => header.date = Some(self.take_line_value()),
-                    "DEST" => header.add_destination(self.take_line_value()),
-                    "LANG" => header.language = Some(self.take_line_value()),
-                    "FILE" => header.filename = Some(self.take_line_value()),
-                    "NOTE" => header.note = Some(self.take_continued_text(1)),
-                    "SUBM" => header.submitter_tag = Some(self.take_line_value()),
-                    "SUBN" => header.submission_tag = Some(self.take_line_value()),
-                    "TIME" => {
-                        let time = self.take_line_value();
-                        // assuming subtag of DATE
-                        if let Some(date) = header.date {
-                            let mut datetime = String::new();
-                            datetime.push_str(&date);
-                            datetime.push_str(" ");
-                            datetime.push_str(&time);
-                            header.date = Some(datetime);
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "CHAR" => {
+                    let encoding = p.value_of(child)?;
+                    p.for_each_child(child, |p, version| {
+                        if version.tag == "VERS" {
+                            header.encoding_version = Some(p.line_value(version)?);
                         } else {
-                            panic!("Expected TIME to be under DATE in header.");
+                            p.unhandled(version, "Header Character Set")?;
                         }
+                        Ok(())
+                    })?;
+                    if p.version == GedcomVersion::V7 && encoding != "UTF-8" {
+                        p.report(
+                            child,
+                            Severity::Error,
+                            DiagnosticKind::InvalidForVersion,
+                            format!("GEDCOM 7 files must be UTF-8, found {encoding}"),
+                        )?;
                     }
-                    "GEDC" => {
-                        header = self.parse_gedcom_data(header);
-                    }
-                    // TODO: HeaderSource
-                    "SOUR" => {
-                        println!("WARNING: Skipping header source.");
-                        while self.tokenizer.current_token != Token::Level(1) {
-                            self.tokenizer.next_token();
+                    header.encoding = Some(encoding);
+                }
+                "CORP" => header.corporation = Some(p.line_value(child)?),
+                "COPR" => header.copyright = Some(p.line_value(child)?),
+                "DATE" => {
+                    header.date = Some(p.parse_date(child)?);
+                    p.for_each_child(child, |p, time| {
+                        if time.tag == "TIME" {
+                            p.parse_header_time(&mut header, time)
+                        } else {
+                            p.unhandled(time, "Header Date").map(drop)
                         }
+                    })?;
+                }
+                "DEST" => header.add_destination(p.line_value(child)?),
+                "LANG" => header.language = Some(p.line_value(child)?),
+                "PLAC" => p.for_each_child(child, |p, form| {
+                    if form.tag == "FORM" {
+                        header.place_form = Some(p.line_value(form)?);
+                    } else {
+                        p.unhandled(form, "Header Place")?;
                     }
-                    _ => {
-                        println!("WARNING: {} Unhandled Header Tag: {}", self.dbg(), tag);
-                        self.tokenizer.next_token();
+                    Ok(())
+                })?,
+                "FILE" => header.filename = Some(p.line_value(child)?),
+                "NOTE" => header.note = Some(p.continued_text(child)?),
+                "SUBM" => header.submitter_tag = Some(p.line_value(child)?),
+                "SUBN" => header.submission_tag = Some(p.line_value(child)?),
+                "TIME" => p.parse_header_time(&mut header, child)?,
+                "GEDC" => p.parse_gedcom_data(&mut header, child)?,
+                "SCHMA" => p.for_each_child(child, |p, tag| {
+                    if tag.tag == "TAG" {
+                        if let Some(extension) = p.parse_extension_tag(tag)? {
+                            header.extension_tags.push(extension);
+                        }
+                    } else {
+                        p.unhandled(tag, "Schema")?;
                     }
-                },
-                Token::Level(_) => self.tokenizer.next_token(),
+                    Ok(())
+                })?,
+                "SOUR" => header.source = Some(p.parse_header_source(child)?),
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Header Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.tokenizer.next_token();
+                    p.unhandled(child, "Header")?;
                 }
             }
-        }
-        header
+            Ok(())
+        })?;
+
+        header.node = Some(node);
+        Ok(header)
     }
 
-    /// Parses SUBM top-level tag
-    #[allow(dead_code)]
-    fn parse_submitter(&mut self, level: u8, xref: Option<String>) -> Submitter {
-        // skip over SUBM tag name
-        self.tokenizer.next_token();
-        let mut current_level = level;
-
-        let mut submitter = Submitter::new(xref);
-        while self.tokenizer.current_token != Token::Level(level) {
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "NAME" => submitter.name = Some(self.take_line_value()),
-                    "ADDR" => {
-                        submitter.address = Some(self.parse_address(level + 1));
-                    }
-                    "PHON" => submitter.phone = Some(self.take_line_value()),
-                    _ => {
-                        println!("WARNING: {} Unhandled Submitter Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+    /// Parses the SOUR of the header, the program that wrote the file
+    fn parse_header_source(&mut self, node: &GedcomNode) -> Result<HeaderSource, ParseError> {
+        let mut source = HeaderSource {
+            system_id: self.value_of(node)?,
+            ..HeaderSource::default()
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "VERS" => source.version = Some(p.line_value(child)?),
+                "NAME" => source.name = Some(p.line_value(child)?),
+                "CORP" => source.corporation = Some(p.parse_corporation(child)?),
+                "DATA" => source.data = Some(p.parse_header_source_data(child)?),
+                _ => {
+                    p.unhandled(child, "Header Source")?;
                 }
+            }
+            Ok(())
+        })?;
+        Ok(source)
+    }
+
+    /// Parses the CORP of a header source
+    fn parse_corporation(&mut self, node: &GedcomNode) -> Result<Corporation, ParseError> {
+        let mut corporation = Corporation {
+            name: self.value_of(node)?,
+            ..Corporation::default()
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "ADDR" => corporation.address = Some(p.parse_address(child)?),
+                "PHON" => corporation.phones.push(p.line_value(child)?),
+                "EMAIL" => corporation.emails.push(p.line_value(child)?),
+                "FAX" => corporation.faxes.push(p.line_value(child)?),
+                "WWW" => corporation.websites.push(p.line_value(child)?),
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Submitter Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Corporation")?;
                 }
             }
-        }
-        // println!("found submitter:\n{:#?}", submitter);
-        submitter
+            Ok(())
+        })?;
+        Ok(corporation)
     }
 
-    /// Parses INDI top-level tag
-    fn parse_individual(&mut self, start_level: u8, xref: Option<String>) -> Individual {
-        // skip over INDI tag name
-        self.tokenizer.next_token();
-        let mut individual = Individual::new(xref);
-        let mut current_level = start_level;
-
-        while self.tokenizer.current_token != Token::Level(start_level) {
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "NAME" => individual.name = Some(self.parse_name(current_level)),
-                    "SEX" => individual.sex = self.parse_gender(),
-                    "ADOP" | "BIRT" | "BAPM" | "BARM" | "BASM" | "BLES" | "BURI" | "CENS"
-                    | "CHR" | "CHRA" | "CONF" | "CREM" | "DEAT" | "EMIG" | "FCOM" | "GRAD"
-                    | "IMMI" | "NATU" | "ORDN" | "RETI" | "RESI" | "PROB" | "WILL" | "EVEN" => {
-                        let tag_clone = tag.clone();
-                        individual.add_event(self.parse_event(tag_clone.as_str(), current_level));
-                    }
-                    "FAMC" | "FAMS" => {
-                        let tag_clone = tag.clone();
-                        individual
-                            .add_family(self.parse_family_link(tag_clone.as_str(), current_level));
-                    }
-                    "CHAN" => {
-                        // assuming it always only has a single DATE subtag
-                        self.tokenizer.next_token(); // level
-                        current_level += 1;
-                        self.tokenizer.next_token(); // DATE tag
-                        individual.last_updated = Some(self.take_line_value());
-                    }
-                    _ => {
-                        println!("WARNING: {} Unhandled Individual Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::CustomTag(tag) => {
-                    let tag_clone = tag.clone();
-                    individual.add_custom_data(self.parse_custom_tag(tag_clone))
-                }
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+    /// Parses the DATA of a header source
+    fn parse_header_source_data(
+        &mut self,
+        node: &GedcomNode,
+    ) -> Result<HeaderSourceData, ParseError> {
+        let mut data = HeaderSourceData {
+            name: self.value_of(node)?,
+            ..HeaderSourceData::default()
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "DATE" => {
+                    data.date = Some(p.parse_date(child)?);
+                    p.for_each_child(child, |p, time| {
+                        if time.tag == "TIME" {
+                            data.time = Some(p.line_value(time)?);
+                        } else {
+                            p.unhandled(time, "Header Source Date")?;
+                        }
+                        Ok(())
+                    })?;
                 }
+                "COPR" => data.copyright = Some(p.continued_text(child)?),
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Individual Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Header Source Data")?;
                 }
             }
+            Ok(())
+        })?;
+        Ok(data)
+    }
+
+    /// Parses the `TIME` of the date of the header
+    fn parse_header_time(
+        &mut self,
+        header: &mut Header,
+        node: &GedcomNode,
+    ) -> Result<(), ParseError> {
+        let time = self.line_value(node)?;
+        // assuming subtag of DATE
+        if header.date.is_none() {
+            if self.options.time_without_date_is_error {
+                return Err(ParseError::TimeWithoutDate { line: node.line });
+            }
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::InvalidTag,
+                "Header time found before its date".to_string(),
+            )?;
         }
-        // println!("found individual:\n{:#?}", individual);
-        individual
+        header.time = Some(time);
+        Ok(())
     }
 
-    /// Parses FAM top-level tag
-    fn parse_family(&mut self, start_level: u8, xref: Option<String>) -> Family {
-        // skip over FAM tag name
-        self.tokenizer.next_token();
-        let mut family = Family::new(xref);
-        let mut current_level = start_level;
-
-        while self.tokenizer.current_token != Token::Level(start_level) {
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "MARR" => family.add_event(self.parse_event("MARR", current_level)),
-                    "HUSB" => family.set_individual1(self.take_line_value()),
-                    "WIFE" => family.set_individual2(self.take_line_value()),
-                    "CHIL" => family.add_child(self.take_line_value()),
-                    _ => {
-                        println!("WARNING: {} Unhandled Family Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+    /// Parses a `TAG` of the `SCHMA`, _ie._ `2 TAG _SKYPEID http://xmlns.com/foaf/0.1/skypeID`
+    fn parse_extension_tag(
+        &mut self,
+        node: &GedcomNode,
+    ) -> Result<Option<ExtensionTag>, ParseError> {
+        let value = self.line_value(node)?;
+        let mut parts = value.splitn(2, char::is_whitespace);
+        let tag = parts.next().unwrap_or_default().to_string();
+        let uri = parts.next().unwrap_or_default().trim().to_string();
+        if uri.is_empty() {
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::MissingValue,
+                format!("Expected a URI for the extension tag {tag}"),
+            )?;
+            return Ok(None);
+        }
+        Ok(Some(ExtensionTag { tag, uri }))
+    }
+
+    /// Handle parsing GEDC tag
+    fn parse_gedcom_data(
+        &mut self,
+        header: &mut Header,
+        node: &GedcomNode,
+    ) -> Result<(), ParseError> {
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "VERS" => header.gedcom_version = Some(p.line_value(child)?),
+                // this is the only value that makes sense. warn them otherwise.
+                "FORM" => {
+                    let form = p.line_value(child)?;
+                    if &form.to_uppercase() != "LINEAGE-LINKED" {
+                        p.report(
+                            child,
+                            Severity::Error,
+                            DiagnosticKind::UnrecognizedValue,
+                            format!(
+                                "Unrecognized GEDCOM form. Expected LINEAGE-LINKED, found {form}"
+                            ),
+                        )?;
+                    }
                 }
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Family Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "GEDC")?;
                 }
             }
-        }
+            Ok(())
+        })
+    }
+
+    /// Parses SUBM top-level tag
+    fn parse_submitter(&mut self, node: GedcomNode) -> Result<Submitter, ParseError> {
+        let mut submitter = Submitter::new(node.xref.clone());
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "NAME" => submitter.name = Some(p.line_value(child)?),
+                "ADDR" => submitter.address = Some(p.parse_address(child)?),
+                "PHON" => submitter.phone = Some(p.line_value(child)?),
+                "EXID" => submitter.external_ids.push(p.parse_external_id(child)?),
+                _ => {
+                    p.unhandled(child, "Submitter")?;
+                }
+            }
+            Ok(())
+        })?;
 
-        // println!("found family:\n{:#?}", family);
-        family
+        submitter.node = Some(node);
+        Ok(submitter)
     }
 
-    fn skip_level(&mut self, level: u8) {
-        println!("Skipping from {}, level : {}", self.dbg(), level);
-        self.tokenizer.next_token();
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= level {
-                    println!("Skipped until {}", self.dbg());
-                    break;
+    /// Parses SUBN top-level tag
+    fn parse_submission(&mut self, node: GedcomNode) -> Result<Submission, ParseError> {
+        let mut submission = Submission::new(node.xref.clone());
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "SUBM" => submission.submitter = p.pointer(child)?,
+                "FAMF" => submission.family_file = Some(p.line_value(child)?),
+                "TEMP" => submission.temple = Some(p.line_value(child)?),
+                "ANCE" => submission.ancestor_generations = p.parse_generations(child)?,
+                "DESC" => submission.descendant_generations = p.parse_generations(child)?,
+                "ORDI" => {
+                    let flag = p.line_value(child)?;
+                    match flag.to_lowercase().as_str() {
+                        "yes" => submission.ordinance_process = Some(true),
+                        "no" => submission.ordinance_process = Some(false),
+                        _ => p.report(
+                            child,
+                            Severity::Error,
+                            DiagnosticKind::UnrecognizedValue,
+                            format!("Unrecognized ordinance process flag: {flag}"),
+                        )?,
+                    }
+                }
+                "RIN" => submission.record_id = Some(p.line_value(child)?),
+                "NOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        submission.notes.push(note);
+                    }
+                }
+                _ => {
+                    p.unhandled(child, "Submission")?;
                 }
             }
-            self.tokenizer.next_token();
+            Ok(())
+        })?;
+
+        submission.node = Some(node);
+        Ok(submission)
+    }
+
+    /// Parses the ANCE or DESC of a submission, a number of generations
+    fn parse_generations(&mut self, node: &GedcomNode) -> Result<Option<u32>, ParseError> {
+        let value = self.line_value(node)?;
+        if let Ok(generations) = value.trim().parse() {
+            return Ok(Some(generations));
         }
+        self.report(
+            node,
+            Severity::Error,
+            DiagnosticKind::UnrecognizedValue,
+            format!("Unrecognized number of generations: {value}"),
+        )?;
+        Ok(None)
     }
 
-    #[allow(dead_code)]
-    fn parse_source(&mut self, level: u8, xref: Option<String>) -> Source {
-        // skip SOUR tag
-        self.tokenizer.next_token();
-        let mut current_level = level;
-        let mut source = Source::new(xref);
+    /// Parses INDI top-level tag
+    fn parse_individual(&mut self, node: GedcomNode) -> Result<Individual, ParseError> {
+        let mut individual = Individual::new(node.xref.clone());
 
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= level {
-                    break;
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "NAME" => individual.names.push(p.parse_name(child)?),
+                "SEX" => individual.sex = p.parse_gender(child)?,
+                "ADOP" | "BIRT" | "BAPM" | "BARM" | "BASM" | "BLES" | "BURI" | "CENS" | "CHR"
+                | "CHRA" | "CONF" | "CREM" | "DEAT" | "EMIG" | "FCOM" | "GRAD" | "IMMI"
+                | "NATU" | "ORDN" | "RETI" | "RESI" | "PROB" | "WILL" | "EVEN" | "CAST"
+                | "DSCR" | "EDUC" | "IDNO" | "NATI" | "NCHI" | "NMR" | "OCCU" | "PROP" | "RELI"
+                | "SSN" | "TITL" | "FACT" => {
+                    individual.add_event(p.parse_event(child)?);
+                }
+                "FAMC" | "FAMS" => {
+                    if let Some(link) = p.parse_family_link(child)? {
+                        individual.add_family(link);
+                    }
+                }
+                "EXID" => individual.external_ids.push(p.parse_external_id(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        individual.notes.push(note);
+                    }
+                }
+                "OBJE" => {
+                    if let Some(link) = p.parse_media_link(child)? {
+                        individual.media.push(link);
+                    }
+                }
+                "SOUR" => {
+                    if let Some(citation) = p.parse_citation(child)? {
+                        individual.citations.push(citation);
+                    }
+                }
+                "ALIA" => {
+                    if let Some(xref) = p.pointer(child)? {
+                        individual.aliases.push(xref);
+                    }
+                }
+                "ASSO" => {
+                    if let Some(association) = p.parse_association(child)? {
+                        individual.associations.push(association);
+                    }
+                }
+                "RESN" => individual.restriction = Some(p.line_value(child)?),
+                "REFN" => individual
+                    .user_references
+                    .push(p.parse_user_reference(child)?),
+                "CHAN" => {
+                    // assuming it always only has a single DATE subtag
+                    if let Some(date) = child.child("DATE") {
+                        individual.last_updated = Some(p.parse_date(date)?);
+                    }
+                }
+                // custom tags of individuals are always kept
+                _ if child.is_custom() => individual.add_custom_data(CustomData {
+                    tag: child.tag.clone(),
+                    value: child.value.clone().unwrap_or_default(),
+                }),
+                _ => {
+                    if let Some(custom_data) = p.unhandled(child, "Individual")? {
+                        individual.add_custom_data(custom_data);
+                    }
                 }
             }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "DATA" => self.tokenizer.next_token(),
-                    "EVEN" => {
-                        let events_recorded = self.take_line_value();
-                        let mut event = self.parse_event("OTHER", level + 2);
-                        event.with_source_data(events_recorded);
-                        source.data.add_event(event);
+            Ok(())
+        })?;
+
+        individual.node = Some(node);
+        Ok(individual)
+    }
+
+    /// Parses FAM top-level tag
+    fn parse_family(&mut self, node: GedcomNode) -> Result<Family, ParseError> {
+        let mut family = Family::new(node.xref.clone());
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "ANUL" | "CENS" | "DIV" | "DIVF" | "ENGA" | "MARB" | "MARC" | "MARR" | "MARL"
+                | "MARS" | "RESI" | "EVEN" => {
+                    let event = p.parse_event(child)?;
+                    // remarriages and repeated censuses are allowed, so they are only
+                    // refused when asked to
+                    if p.options.duplicate_family_event_is_error
+                        && family.events().iter().any(|e| e.event == event.event)
+                    {
+                        return Err(ParseError::DuplicateEvent {
+                            line: child.line,
+                            tag: child.tag.clone(),
+                            xref: None,
+                        });
+                    }
+                    family.add_event(event);
+                }
+                "HUSB" | "WIFE" => {
+                    let is_husband = child.tag == "HUSB";
+                    let existing = if is_husband {
+                        &family.individual1
+                    } else {
+                        &family.individual2
+                    };
+                    if existing.is_some() {
+                        if p.options.duplicate_spouse_is_error {
+                            return Err(ParseError::DuplicateSpouse {
+                                line: child.line,
+                                tag: child.tag.clone(),
+                                xref: None,
+                            });
+                        }
+                        // keep the first individual, as a second one has nowhere to go
+                        return p.report(
+                            child,
+                            Severity::Error,
+                            DiagnosticKind::DuplicateSpouse,
+                            format!("Family already has a {} individual", child.tag),
+                        );
+                    }
+                    let Some(xref) = p.pointer(child)? else {
+                        return Ok(());
+                    };
+                    if is_husband {
+                        family.set_individual1(xref);
+                    } else {
+                        family.set_individual2(xref);
+                    }
+                }
+                "CHIL" => {
+                    if let Some(xref) = p.pointer(child)? {
+                        family.add_child(xref);
+                    }
+                }
+                "NCHI" => family.num_children = p.parse_count(child)?,
+                "EXID" => family.external_ids.push(p.parse_external_id(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        family.notes.push(note);
                     }
-                    "AGNC" => source.data.agency = Some(self.take_line_value()),
-                    "ABBR" => source.abbreviation = Some(self.take_continued_text(level + 1)),
-                    "TITL" => source.title = Some(self.take_continued_text(level + 1)),
-                    "REPO" => source.add_repo_citation(self.parse_repo_citation(level + 1)),
-                    _ => {
-                        println!("WARNING: {} Unhandled Source Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
+                }
+                "OBJE" => {
+                    if let Some(link) = p.parse_media_link(child)? {
+                        family.media.push(link);
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
                 }
-                Token::LineValue(_) => {}
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Source Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    if let Some(custom_data) = p.unhandled(child, "Family")? {
+                        family.add_custom_data(custom_data);
+                    }
                 }
             }
-        }
+            Ok(())
+        })?;
 
-        // println!("found source:\n{:#?}", source);
-        source
+        family.node = Some(node);
+        Ok(family)
+    }
+
+    /// Parses SOUR top-level tag
+    fn parse_source(&mut self, node: GedcomNode) -> Result<Source, ParseError> {
+        let mut source = Source::new(node.xref.clone());
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "DATA" => p.for_each_child(child, |p, data| {
+                    match data.tag.as_str() {
+                        "EVEN" => {
+                            let events_recorded = p.value_of(data)?;
+                            let mut event = Event::from_tag("OTHER");
+                            event.with_source_data(events_recorded);
+                            p.parse_event_detail(&mut event, data)?;
+                            source.data.add_event(event);
+                        }
+                        "AGNC" => source.data.agency = Some(p.line_value(data)?),
+                        _ => {
+                            p.unhandled(data, "Source Data")?;
+                        }
+                    }
+                    Ok(())
+                })?,
+                "ABBR" => source.abbreviation = Some(p.continued_text(child)?),
+                "AUTH" => source.author = Some(p.continued_text(child)?),
+                "PUBL" => source.publication = Some(p.continued_text(child)?),
+                "TITL" => source.title = Some(p.continued_text(child)?),
+                "TEXT" => source.text = Some(p.continued_text(child)?),
+                "REPO" => {
+                    if let Some(citation) = p.parse_repo_citation(child)? {
+                        source.add_repo_citation(citation);
+                    }
+                }
+                "EXID" => source.external_ids.push(p.parse_external_id(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        source.notes.push(note);
+                    }
+                }
+                "OBJE" => {
+                    if let Some(link) = p.parse_media_link(child)? {
+                        source.media.push(link);
+                    }
+                }
+                _ => {
+                    p.unhandled(child, "Source")?;
+                }
+            }
+            Ok(())
+        })?;
+
+        source.node = Some(node);
+        Ok(source)
     }
 
     /// Parses REPO top-level tag.
-    #[allow(dead_code)]
-    fn parse_repository(&mut self, level: u8, xref: Option<String>) -> Repository {
-        // skip REPO tag
-        self.tokenizer.next_token();
-        let mut current_level = level;
+    fn parse_repository(&mut self, node: GedcomNode) -> Result<Repository, ParseError> {
         let mut repo = Repository {
-            xref,
+            xref: node.xref.clone(),
             name: None,
             address: None,
+            notes: Vec::new(),
+            external_ids: Vec::new(),
+            node: None,
         };
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "NAME" => repo.name = Some(self.take_line_value()),
-                    "ADDR" => repo.address = Some(self.parse_address(current_level)),
-                    _ => {
-                        println!("WARNING : {} Unhandled Repository Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "NAME" => repo.name = Some(p.line_value(child)?),
+                "ADDR" => repo.address = Some(p.parse_address(child)?),
+                "EXID" => repo.external_ids.push(p.parse_external_id(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        repo.notes.push(note);
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
                 }
                 _ => {
-                    println!(
-                        "WARNING : Unhandled Repository Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Repository")?;
                 }
             }
+            Ok(())
+        })?;
+
+        repo.node = Some(node);
+        Ok(repo)
+    }
+
+    /// Parses NOTE top-level tag, or the SNOTE of GEDCOM 7.
+    fn parse_note(&mut self, node: GedcomNode) -> Result<Note, ParseError> {
+        let mut shared = Note {
+            xref: node.xref.clone(),
+            value: self.value_of(&node)?,
+            mime: None,
+            language: None,
+            translations: Vec::new(),
+            external_ids: Vec::new(),
+            node: None,
+        };
+
+        self.for_each_child(&node, |p, child| {
+            match child.tag.as_str() {
+                "CONT" | "CONC" => p.continue_text(&mut shared.value, child)?,
+                "MIME" => shared.mime = Some(p.line_value(child)?),
+                "LANG" => shared.language = Some(p.line_value(child)?),
+                "TRAN" => shared.translations.push(p.parse_translation(child)?),
+                "EXID" => shared.external_ids.push(p.parse_external_id(child)?),
+                _ => {
+                    p.unhandled(child, "Note")?;
+                }
+            }
+            Ok(())
+        })?;
+
+        shared.node = Some(node);
+        Ok(shared)
+    }
+
+    /// Parses a NOTE of a record or structure, which is either its text or a pointer to
+    /// a note record. The SNOTE of GEDCOM 7 is always a pointer.
+    fn parse_note_structure(
+        &mut self,
+        node: &GedcomNode,
+    ) -> Result<Option<NoteStructure>, ParseError> {
+        if node.tag == "SNOTE" || is_pointer(node.value.as_deref().unwrap_or_default()) {
+            return Ok(self.pointer(node)?.map(NoteStructure::Shared));
         }
-        // println!("found repositiory:\n{:#?}", repo);
-        repo
+        Ok(Some(NoteStructure::Text(self.continued_text(node)?)))
+    }
+
+    /// Parses the TRAN of a note
+    fn parse_translation(&mut self, node: &GedcomNode) -> Result<Translation, ParseError> {
+        let mut translation = Translation {
+            value: self.value_of(node)?,
+            mime: None,
+            language: None,
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "CONT" | "CONC" => p.continue_text(&mut translation.value, child)?,
+                "MIME" => translation.mime = Some(p.line_value(child)?),
+                "LANG" => translation.language = Some(p.line_value(child)?),
+                _ => {
+                    p.unhandled(child, "Translation")?;
+                }
+            }
+            Ok(())
+        })?;
+        Ok(translation)
     }
 
-    fn parse_custom_tag(&mut self, tag: String) -> CustomData {
-        let value = self.take_line_value();
-        CustomData { tag, value }
+    /// Parses EXID tag
+    fn parse_external_id(&mut self, node: &GedcomNode) -> Result<ExternalId, ParseError> {
+        let mut external_id = ExternalId {
+            id: self.value_of(node)?,
+            id_type: None,
+        };
+        self.for_each_child(node, |p, child| {
+            if child.tag == "TYPE" {
+                external_id.id_type = Some(p.line_value(child)?);
+            } else {
+                p.unhandled(child, "ExternalId")?;
+            }
+            Ok(())
+        })?;
+        Ok(external_id)
     }
 
-    /// Handle parsing GEDC tag
-    fn parse_gedcom_data(&mut self, mut header: Header) -> Header {
-        // skip GEDC tag
-        self.tokenizer.next_token();
+    /// Parses OBJE top-level tag.
+    fn parse_media(&mut self, node: GedcomNode) -> Result<Media, ParseError> {
+        let mut media = self.parse_media_object(&node)?;
+        media.xref.clone_from(&node.xref);
+        media.node = Some(node);
+        Ok(media)
+    }
 
-        while self.tokenizer.current_token != Token::Level(1)
-           && self.tokenizer.current_token != Token::Level(0)
-        {
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "VERS" => header.gedcom_version = Some(self.take_line_value()),
-                    // this is the only value that makes sense. warn them otherwise.
-                    "FORM" => {
-                        let form = self.take_line_value();
-                        if &form.to_uppercase() != "LINEAGE-LINKED" {
-                            println!(
-                                "WARNING: Unrecognized GEDCOM form. Expected LINEAGE-LINKED, found {}"
-                            , form);
-                        }
+    /// Parses the OBJE of a record or event, which either points to a multimedia record
+    /// or embeds the object itself
+    fn parse_media_link(&mut self, node: &GedcomNode) -> Result<Option<MediaLink>, ParseError> {
+        if is_pointer(node.value.as_deref().unwrap_or_default()) {
+            return Ok(self.pointer(node)?.map(MediaLink::Shared));
+        }
+        let media = self.parse_media_object(node)?;
+        Ok(Some(MediaLink::Embedded(Box::new(media))))
+    }
+
+    /// Parses the substructures of a multimedia record or of an embedded object
+    fn parse_media_object(&mut self, node: &GedcomNode) -> Result<Media, ParseError> {
+        let mut media = Media::default();
+        // GEDCOM 5.5 gives the format and title of an object next to its file
+        let mut format = None;
+        let mut title = None;
+
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "FILE" => media.files.push(p.parse_media_file(child)?),
+                "FORM" => format = Some(p.parse_media_format(child)?),
+                "TITL" => title = Some(p.line_value(child)?),
+                "REFN" => media.user_references.push(p.parse_user_reference(child)?),
+                "RIN" => media.record_id = Some(p.line_value(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        media.notes.push(note);
+                    }
+                }
+                "SOUR" => {
+                    if let Some(citation) = p.parse_citation(child)? {
+                        media.citations.push(citation);
                     }
-                    _ => {
-                        println!("WARNING: {} Unhandled GEDC Tag: {}", self.dbg(), tag);
-                        self.tokenizer.next_token();
+                }
+                "CHAN" => {
+                    // assuming it always only has a single DATE subtag
+                    if let Some(date) = child.child("DATE") {
+                        media.last_updated = Some(p.parse_date(date)?);
                     }
-                },
-                Token::Level(_) => self.tokenizer.next_token(),
+                }
+                "EXID" => media.external_ids.push(p.parse_external_id(child)?),
                 _ => {
-                    println!(
-                        "WARNING: {} Unexpected GEDC Token: {:?}",
-                        self.dbg(),
-                        &self.tokenizer.current_token
-                    );
-                    self.tokenizer.next_token();
+                    p.unhandled(child, "Multimedia")?;
                 }
             }
+            Ok(())
+        })?;
+
+        if let Some(file) = media.files.first_mut() {
+            if let Some((format, media_type)) = format {
+                file.format = file.format.take().or(Some(format));
+                file.media_type = file.media_type.take().or(media_type);
+            }
+            file.title = file.title.take().or(title);
         }
-        header
+        Ok(media)
     }
 
-    fn parse_family_link(&mut self, tag: &str, start_level: u8) -> FamilyLink {
-        let xref = self.take_line_value();
-        let mut link = FamilyLink::new(xref, tag);
-        let mut current_level = start_level;
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
+    /// Parses the FILE of a multimedia object
+    fn parse_media_file(&mut self, node: &GedcomNode) -> Result<MediaFile, ParseError> {
+        let mut file = MediaFile {
+            reference: self.value_of(node)?,
+            ..MediaFile::default()
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "FORM" => {
+                    let (format, media_type) = p.parse_media_format(child)?;
+                    file.format = Some(format);
+                    file.media_type = media_type;
+                }
+                "TITL" => file.title = Some(p.line_value(child)?),
+                _ => {
+                    p.unhandled(child, "Multimedia File")?;
                 }
             }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "PEDI" => link.set_pedigree(self.take_line_value().as_str()),
-                    _ => {
-                        println!("WARNING : {} Unhandled FamilyLink Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
+            Ok(())
+        })?;
+        Ok(file)
+    }
+
+    /// Parses the FORM of a multimedia file into its format and the type of its media,
+    /// which is the TYPE of a GEDCOM 5.5.1 record and the MEDI elsewhere
+    fn parse_media_format(
+        &mut self,
+        node: &GedcomNode,
+    ) -> Result<(String, Option<String>), ParseError> {
+        let format = self.value_of(node)?;
+        let mut media_type = None;
+        self.for_each_child(node, |p, child| {
+            if child.tag == "TYPE" || child.tag == "MEDI" {
+                media_type = Some(p.line_value(child)?);
+            } else {
+                p.unhandled(child, "Multimedia Format")?;
+            }
+            Ok(())
+        })?;
+        Ok((format, media_type))
+    }
+
+    /// Parses REFN tag
+    fn parse_user_reference(&mut self, node: &GedcomNode) -> Result<UserReference, ParseError> {
+        let mut reference = UserReference {
+            number: self.value_of(node)?,
+            reference_type: None,
+        };
+        self.for_each_child(node, |p, child| {
+            if child.tag == "TYPE" {
+                reference.reference_type = Some(p.line_value(child)?);
+            } else {
+                p.unhandled(child, "UserReference")?;
+            }
+            Ok(())
+        })?;
+        Ok(reference)
+    }
+
+    /// Parses the ASSO tag of an individual
+    fn parse_association(&mut self, node: &GedcomNode) -> Result<Option<Association>, ParseError> {
+        let Some(xref) = self.pointer_of(node)? else {
+            return Ok(None);
+        };
+        let mut association = Association {
+            xref,
+            relation: None,
+            citations: Vec::new(),
+            notes: Vec::new(),
+        };
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "RELA" => association.relation = Some(p.line_value(child)?),
+                // a role other than the ones GEDCOM 7 names is described by its phrase
+                "ROLE" => {
+                    let role = p.value_of(child)?;
+                    let mut phrase = None;
+                    p.for_each_child(child, |p, grandchild| {
+                        if grandchild.tag == "PHRASE" {
+                            phrase = Some(p.line_value(grandchild)?);
+                        } else {
+                            p.unhandled(grandchild, "ROLE")?;
+                        }
+                        Ok(())
+                    })?;
+                    association.relation = Some(phrase.unwrap_or(role));
+                }
+                "SOUR" => {
+                    if let Some(citation) = p.parse_citation(child)? {
+                        association.citations.push(citation);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        association.notes.push(note);
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
                 }
                 _ => {
-                    println!(
-                        "WARNING: Unhandled FamilyLink Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Association")?;
                 }
             }
-        }
+            Ok(())
+        })?;
+        Ok(Some(association))
+    }
+
+    fn parse_family_link(&mut self, node: &GedcomNode) -> Result<Option<FamilyLink>, ParseError> {
+        let link_type = if node.tag == "FAMC" {
+            FamilyLinkType::Child
+        } else {
+            FamilyLinkType::Spouse
+        };
+        let Some(xref) = self.pointer_of(node)? else {
+            return Ok(None);
+        };
+        let mut link = FamilyLink::new(xref, link_type);
+
+        self.for_each_child(node, |p, child| {
+            if child.tag == "PEDI" {
+                let value = p.value_of(child)?;
+                let mut phrase = None;
+                p.for_each_child(child, |p, detail| {
+                    if detail.tag == "PHRASE" {
+                        phrase = Some(p.line_value(detail)?);
+                    } else {
+                        p.unhandled(detail, "PEDI")?;
+                    }
+                    Ok(())
+                })?;
+                match value.parse::<Pedigree>() {
+                    Ok(Pedigree::Other(_)) => link.set_pedigree(Pedigree::Other(phrase)),
+                    Ok(pedigree) => link.set_pedigree(pedigree),
+                    Err(()) => match p.options.unrecognized_pedigree {
+                        ValuePolicy::Error => {
+                            return Err(ParseError::UnknownPedigree {
+                                line: child.line,
+                                value,
+                                xref: None,
+                            })
+                        }
+                        ValuePolicy::Warn => p.report(
+                            child,
+                            Severity::Error,
+                            DiagnosticKind::UnrecognizedValue,
+                            format!("Unrecognized family link pedigree {value}"),
+                        )?,
+                        ValuePolicy::Ignore => {}
+                    },
+                }
+            } else {
+                p.unhandled(child, "FamilyLink")?;
+            }
+            Ok(())
+        })?;
 
-        link
+        Ok(Some(link))
     }
 
-    fn parse_repo_citation(&mut self, start_level: u8) -> RepoCitation {
-        let xref = self.take_line_value();
+    fn parse_repo_citation(
+        &mut self,
+        node: &GedcomNode,
+    ) -> Result<Option<RepoCitation>, ParseError> {
+        let Some(xref) = self.pointer_of(node)? else {
+            return Ok(None);
+        };
         let mut citation = RepoCitation {
             xref,
             call_number: None,
         };
-        let mut current_level = start_level;
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "CALN" => citation.call_number = Some(self.take_line_value()),
-                    _ => {
-                        println!(
-                            "WARNING: {} Unhandled RepoCitation Tag: {}",
-                            self.dbg(),
-                            tag
-                        );
-                        self.skip_level(current_level);
+        self.for_each_child(node, |p, child| {
+            if child.tag == "CALN" {
+                citation.call_number = Some(p.line_value(child)?);
+            } else {
+                p.unhandled(child, "RepoCitation")?;
+            }
+            Ok(())
+        })?;
+        Ok(Some(citation))
+    }
+
+    fn parse_gender(&mut self, node: &GedcomNode) -> Result<Gender, ParseError> {
+        let gender_string = self.line_value(node)?;
+        let gender = match gender_string.as_str() {
+            "M" => Gender::Male,
+            "F" => Gender::Female,
+            "N" => Gender::Nonbinary,
+            "X" => Gender::Other,
+            "U" | "" => Gender::Unknown,
+            _ => {
+                match self.options.unrecognized_sex {
+                    ValuePolicy::Error => {
+                        return Err(ParseError::UnknownSex {
+                            line: node.line,
+                            value: gender_string,
+                            xref: None,
+                        })
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
-                }
-                _ => {
-                    println!(
-                        "WARNING: Unhandled RepoCitation Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    ValuePolicy::Warn => self.report(
+                        node,
+                        Severity::Error,
+                        DiagnosticKind::UnrecognizedValue,
+                        format!("Unknown gender value {gender_string}"),
+                    )?,
+                    ValuePolicy::Ignore => {}
                 }
+                Gender::Unknown
             }
-        }
-        citation
+        };
+        Ok(gender)
     }
 
-    fn parse_gender(&mut self) -> Gender {
-        self.tokenizer.next_token();
-        let gender: Gender;
-        if let Token::LineValue(gender_string) = &self.tokenizer.current_token {
-            gender = match gender_string.as_str() {
-                "M" => Gender::Male,
-                "F" => Gender::Female,
-                "N" => Gender::Nonbinary,
-                "U" => Gender::Unknown,
-                _ => {
-                    println!(
-                        "WARNING: {} Unknown gender value {}",
-                        self.dbg(),
-                        gender_string
-                    );
-                    Gender::Unknown
-                }
-            };
-        } else {
-            println!(
-                "WARNING: Expected gender LineValue, found {:?}",
-                self.tokenizer.current_token
-            );
-            gender = Gender::Unknown;
-        }
-        self.tokenizer.next_token();
-        gender
-    }
-
-    fn parse_name(&mut self, start_level: u8) -> Name {
-        let mut name = Name::default();
-        name.value = Some(self.take_line_value());
-        let mut current_level = start_level;
-
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "GIVN" => name.given = Some(self.take_line_value()),
-                    "NPFX" => name.prefix = Some(self.take_line_value()),
-                    "NSFX" => name.suffix = Some(self.take_line_value()),
-                    "SPFX" => name.surname_prefix = Some(self.take_line_value()),
-                    "SURN" => name.surname = Some(self.take_line_value()),
-                    _ => {
-                        println!("WARNING: {} Unhandled Name Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+    fn parse_name(&mut self, node: &GedcomNode) -> Result<Name, ParseError> {
+        let mut name = Name {
+            value: Some(self.value_of(node)?),
+            ..Name::default()
+        };
+
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "TYPE" => name.name_type = Some(p.parse_name_type(child)?),
+                "LANG" => name.language = Some(p.line_value(child)?),
+                "FONE" => name.phonetic.push(p.parse_name_variant(child)?),
+                "ROMN" => name.romanized.push(p.parse_name_variant(child)?),
+                "TRAN" => name.translations.push(p.parse_name(child)?),
+                _ => p.parse_name_piece(&mut name, child, "Name")?,
+            }
+            Ok(())
+        })?;
+
+        Ok(name)
+    }
+
+    /// Parses the TYPE of a NAME
+    fn parse_name_type(&mut self, node: &GedcomNode) -> Result<NameType, ParseError> {
+        let value = self.value_of(node)?;
+        let mut phrase = None;
+        self.for_each_child(node, |p, detail| {
+            if detail.tag == "PHRASE" {
+                phrase = Some(p.line_value(detail)?);
+            } else {
+                p.unhandled(detail, "Name Type")?;
+            }
+            Ok(())
+        })?;
+        Ok(match value.parse::<NameType>() {
+            Ok(NameType::Other(_)) => NameType::Other(phrase),
+            Ok(name_type) => name_type,
+            // GEDCOM 5.5.1 lets the type be any text
+            Err(()) => NameType::Other(Some(value)),
+        })
+    }
+
+    /// Parses the FONE or ROMN of a name
+    fn parse_name_variant(&mut self, node: &GedcomNode) -> Result<NameVariant, ParseError> {
+        let mut variant = NameVariant {
+            name: Name {
+                value: Some(self.value_of(node)?),
+                ..Name::default()
+            },
+            variant_type: None,
+        };
+        self.for_each_child(node, |p, child| {
+            if child.tag == "TYPE" {
+                variant.variant_type = Some(p.line_value(child)?);
+            } else {
+                p.parse_name_piece(&mut variant.name, child, "Name Variant")?;
+            }
+            Ok(())
+        })?;
+        Ok(variant)
+    }
+
+    /// Parses a line of the pieces a name or its variations share into `name`, passing
+    /// anything else to `unhandled`
+    fn parse_name_piece(
+        &mut self,
+        name: &mut Name,
+        node: &GedcomNode,
+        context: &str,
+    ) -> Result<(), ParseError> {
+        match node.tag.as_str() {
+            "GIVN" => name.given = Some(self.line_value(node)?),
+            "NPFX" => name.prefix = Some(self.line_value(node)?),
+            "NSFX" => name.suffix = Some(self.line_value(node)?),
+            "SPFX" => name.surname_prefix = Some(self.line_value(node)?),
+            "SURN" => name.surname = Some(self.line_value(node)?),
+            "NICK" => name.nickname = Some(self.line_value(node)?),
+            "NOTE" | "SNOTE" => {
+                if let Some(note) = self.parse_note_structure(node)? {
+                    name.notes.push(note);
                 }
-                _ => {
-                    println!(
-                        "WARNING: Unhandled Name Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+            }
+            "SOUR" => {
+                if let Some(citation) = self.parse_citation(node)? {
+                    name.citations.push(citation);
                 }
             }
+            _ => {
+                self.unhandled(node, context)?;
+            }
         }
+        Ok(())
+    }
 
-        name
+    fn parse_event(&mut self, node: &GedcomNode) -> Result<Event, ParseError> {
+        let mut event = Event::from_tag(&node.tag);
+        event.value = node
+            .value
+            .as_deref()
+            .map(|value| unescape(self.version, value));
+        self.parse_event_detail(&mut event, node)?;
+        Ok(event)
     }
 
-    fn parse_event(&mut self, tag: &str, start_level: u8) -> Event {
-        self.tokenizer.next_token();
-        let mut event = Event::from_tag(tag);
-        let mut current_level = start_level;
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
+    /// Parses the substructures of an event into `event`
+    fn parse_event_detail(
+        &mut self,
+        event: &mut Event,
+        node: &GedcomNode,
+    ) -> Result<(), ParseError> {
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "DATE" => {
+                    event.date = Some(p.parse_date(child)?);
+                    p.for_each_child(child, |p, detail| {
+                        if detail.tag == "PHRASE" {
+                            event.date_phrase = Some(p.line_value(detail)?);
+                        } else {
+                            p.unhandled(detail, "Date")?;
+                        }
+                        Ok(())
+                    })?;
                 }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "DATE" => event.date = Some(self.take_line_value()),
-                    "PLAC" => event.place = Some(self.take_line_value()),
-                    "SOUR" => event.add_citation(self.parse_citation(current_level)),
-                    _ => {
-                        println!("WARNING: {} Unhandled Event Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
+                "PLAC" => event.place = Some(p.parse_place(child)?),
+                "SOUR" => {
+                    if let Some(citation) = p.parse_citation(child)? {
+                        event.add_citation(citation);
+                    }
+                }
+                // the `TYPE` of an `EVEN` names the event
+                "TYPE" if event.event == EventType::Other => {
+                    event.event = EventType::Custom(p.line_value(child)?);
+                }
+                "TYPE" => event.classification = Some(p.line_value(child)?),
+                "AGE" => event.age = Some(p.parse_age(child)?),
+                "AGNC" => event.agency = Some(p.line_value(child)?),
+                "CAUS" => event.cause = Some(p.line_value(child)?),
+                "RELI" => event.religion = Some(p.line_value(child)?),
+                "RESN" => event.restriction = Some(p.line_value(child)?),
+                "ADDR" => event.address = Some(p.parse_address(child)?),
+                "PHON" => event.phones.push(p.line_value(child)?),
+                "EMAIL" => event.emails.push(p.line_value(child)?),
+                "FAX" => event.faxes.push(p.line_value(child)?),
+                "WWW" => event.websites.push(p.line_value(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        event.notes.push(note);
+                    }
+                }
+                "OBJE" => {
+                    if let Some(link) = p.parse_media_link(child)? {
+                        event.media.push(link);
+                    }
+                }
+                "HUSB" | "WIFE" => p.for_each_child(child, |p, detail| {
+                    if detail.tag == "AGE" {
+                        let age = Some(p.parse_age(detail)?);
+                        if child.tag == "HUSB" {
+                            event.husband_age = age;
+                        } else {
+                            event.wife_age = age;
+                        }
+                    } else {
+                        p.unhandled(detail, "Spouse")?;
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+                    Ok(())
+                })?,
+                // a long description of an attribute, _ie._ a `DSCR`
+                "CONT" | "CONC" => {
+                    p.continue_text(event.value.get_or_insert_with(String::new), child)?;
                 }
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Event Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Event")?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Parses PLAC tag
+    fn parse_place(&mut self, node: &GedcomNode) -> Result<Place, ParseError> {
+        let mut place = Place::new(&self.value_of(node)?);
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "FORM" => place.form = Some(p.line_value(child)?),
+                "FONE" => place.phonetic.push(p.parse_place_variant(child)?),
+                "ROMN" => place.romanized.push(p.parse_place_variant(child)?),
+                "TRAN" => place.translations.push(p.parse_translation(child)?),
+                "MAP" => p.for_each_child(child, |p, coordinate| {
+                    match coordinate.tag.as_str() {
+                        "LATI" => place.latitude = p.parse_coordinate(coordinate, 'N', 'S')?,
+                        "LONG" => place.longitude = p.parse_coordinate(coordinate, 'E', 'W')?,
+                        _ => {
+                            p.unhandled(coordinate, "Map")?;
+                        }
+                    }
+                    Ok(())
+                })?,
+                _ => {
+                    p.unhandled(child, "Place")?;
                 }
             }
+            Ok(())
+        })?;
+        Ok(place)
+    }
+
+    /// Parses the FONE or ROMN of a place
+    fn parse_place_variant(&mut self, node: &GedcomNode) -> Result<PlaceVariant, ParseError> {
+        let mut variant = PlaceVariant {
+            value: self.value_of(node)?,
+            variant_type: None,
+        };
+        self.for_each_child(node, |p, child| {
+            if child.tag == "TYPE" {
+                variant.variant_type = Some(p.line_value(child)?);
+            } else {
+                p.unhandled(child, "Place Variant")?;
+            }
+            Ok(())
+        })?;
+        Ok(variant)
+    }
+
+    /// Parses a LATI or LONG, _ie._ `N18.150944`, into decimal degrees that are negative
+    /// towards `negative`
+    fn parse_coordinate(
+        &mut self,
+        node: &GedcomNode,
+        positive: char,
+        negative: char,
+    ) -> Result<Option<f64>, ParseError> {
+        let value = self.line_value(node)?;
+        let mut chars = value.chars();
+        let sign = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some(c) if c == positive => 1.0,
+            Some(c) if c == negative => -1.0,
+            _ => 0.0,
+        };
+        match chars.as_str().parse::<f64>() {
+            Ok(degrees) if sign != 0.0 && degrees.is_finite() => Ok(Some(sign * degrees)),
+            _ => {
+                self.report(
+                    node,
+                    Severity::Error,
+                    DiagnosticKind::UnrecognizedValue,
+                    format!("Unrecognized coordinate: {value}"),
+                )?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// The number of an `NCHI` line, reporting an error when it is not one
+    fn parse_count(&mut self, node: &GedcomNode) -> Result<Option<u8>, ParseError> {
+        let value = self.line_value(node)?;
+        if let Ok(count) = value.trim().parse() {
+            return Ok(Some(count));
         }
-        event
+        self.report(
+            node,
+            Severity::Error,
+            DiagnosticKind::UnrecognizedValue,
+            format!("Unrecognized count: {value}"),
+        )?;
+        Ok(None)
     }
 
     /// Parses ADDR tag
-    fn parse_address(&mut self, start_level: u8) -> Address {
-        // skip ADDR tag
-        self.tokenizer.next_token();
-        let mut current_level = start_level;
+    fn parse_address(&mut self, node: &GedcomNode) -> Result<Address, ParseError> {
         let mut address = Address::default();
-        let mut value = String::new();
-
-        // handle value on ADDR line
-        if let Token::LineValue(addr) = &self.tokenizer.current_token {
-            value.push_str(addr);
-            self.tokenizer.next_token();
-        }
-
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "CONT" => {
-                        value.push('\n');
-                        value.push_str(&self.take_line_value());
-                    }
-                    "ADR1" => address.adr1 = Some(self.take_line_value()),
-                    "ADR2" => address.adr2 = Some(self.take_line_value()),
-                    "ADR3" => address.adr3 = Some(self.take_line_value()),
-                    "CITY" => address.city = Some(self.take_line_value()),
-                    "STAE" => address.state = Some(self.take_line_value()),
-                    "POST" => address.post = Some(self.take_line_value()),
-                    "CTRY" => address.country = Some(self.take_line_value()),
-                    _ => {
-                        println!("WARNING: {} Unhandled Address Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
+        let mut value = node.value.clone().unwrap_or_default();
+
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "CONT" => {
+                    value.push('\n');
+                    value.push_str(&p.line_value(child)?);
                 }
+                "ADR1" => address.adr1 = Some(p.line_value(child)?),
+                "ADR2" => address.adr2 = Some(p.line_value(child)?),
+                "ADR3" => address.adr3 = Some(p.line_value(child)?),
+                "CITY" => address.city = Some(p.line_value(child)?),
+                "STAE" => address.state = Some(p.line_value(child)?),
+                "POST" => address.post = Some(p.line_value(child)?),
+                "CTRY" => address.country = Some(p.line_value(child)?),
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Address Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Address")?;
                 }
             }
-        }
+            Ok(())
+        })?;
 
-        if &value != "" {
+        if !value.is_empty() {
             address.value = Some(value);
         }
 
-        address
+        Ok(address)
     }
 
-    fn parse_citation(&mut self, start_level: u8) -> SourceCitation {
+    fn parse_citation(&mut self, node: &GedcomNode) -> Result<Option<SourceCitation>, ParseError> {
+        let Some(xref) = self.pointer_of(node)? else {
+            return Ok(None);
+        };
         let mut citation = SourceCitation {
-            xref: self.take_line_value(),
+            xref,
             page: None,
+            quality: None,
+            notes: Vec::new(),
         };
-        let mut current_level = start_level;
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "PAGE" => citation.page = Some(self.take_line_value()),
-                    _ => {
-                        println!("WARNING: {} Unhandled Citation Tag: {}", self.dbg(), tag);
-                        self.skip_level(current_level);
+        self.for_each_child(node, |p, child| {
+            match child.tag.as_str() {
+                "PAGE" => citation.page = Some(p.line_value(child)?),
+                "QUAY" => citation.quality = Some(p.line_value(child)?),
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = p.parse_note_structure(child)? {
+                        citation.notes.push(note);
                     }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
                 }
                 _ => {
-                    println!(
-                        "WARNING: Unhandled Citation Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
+                    p.unhandled(child, "Citation")?;
                 }
             }
+            Ok(())
+        })?;
+        Ok(Some(citation))
+    }
+
+    /// Calls `parse` on each child of `node`, with the tag of `node` on the tag path
+    /// of any diagnostics reported meanwhile.
+    fn for_each_child<F>(&mut self, node: &GedcomNode, mut parse: F) -> Result<(), ParseError>
+    where
+        F: FnMut(&mut Self, &GedcomNode) -> Result<(), ParseError>,
+    {
+        if node.children.is_empty() {
+            return Ok(());
         }
-        citation
+        self.tag_path.push(node.tag.clone());
+        for child in &node.children {
+            parse(self, child)?;
+        }
+        self.tag_path.pop();
+        Ok(())
     }
 
-    /// Takes the value of the current line including handling
+    /// The value of a node including handling
     /// multi-line values from CONT & CONC tags.
-    fn take_continued_text(&mut self, start_level: u8) -> String {
-        let mut value = self.take_line_value();
-        let mut current_level = start_level;
-
-        loop {
-            if let Token::Level(cur_level) = self.tokenizer.current_token {
-                if cur_level <= start_level {
-                    break;
-                }
-            }
-            match &self.tokenizer.current_token {
-                Token::Tag(tag) => match tag.as_str() {
-                    "CONT" => {
-                        value.push('\n');
-                        value.push_str(&self.take_line_value())
-                    }
-                    "CONC" => {
-                        value.push(' ');
-                        value.push_str(&self.take_line_value())
-                    }
-                    _ => {
-                        println!(
-                            "WARNING: {} Unhandled Continuation Tag: {}",
-                            self.dbg(),
-                            tag
-                        );
-                        self.skip_level(current_level);
-                    }
-                },
-                Token::Level(new_level) => {
-                    current_level = *new_level;
-                    self.tokenizer.next_token();
-                }
-                _ => {
-                    println!(
-                        "Unhandled Continuation Token: {:?}",
-                        self.tokenizer.current_token
-                    );
-                    self.skip_level(current_level);
-                }
+    fn continued_text(&mut self, node: &GedcomNode) -> Result<String, ParseError> {
+        let mut value = self.value_of(node)?;
+
+        self.for_each_child(node, |p, child| {
+            if child.tag == "CONT" || child.tag == "CONC" {
+                p.continue_text(&mut value, child)
+            } else {
+                p.unhandled(child, "Continuation").map(drop)
             }
+        })?;
+
+        Ok(value)
+    }
+
+    /// Appends a CONT or CONC line to `value`. GEDCOM 7 has no CONC, but one is still
+    /// joined after reporting it.
+    fn continue_text(&mut self, value: &mut String, node: &GedcomNode) -> Result<(), ParseError> {
+        if node.tag == "CONT" {
+            value.push('\n');
+        } else if self.version == GedcomVersion::V7 {
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::InvalidForVersion,
+                "GEDCOM 7 does not allow CONC, lines may be of any length".to_string(),
+            )?;
+        }
+        // an empty continuation line has no value
+        if let Some(text) = &node.value {
+            value.push_str(&unescape(self.version, text));
+        }
+        self.for_each_child(node, |p, child| p.unhandled(child, &node.tag).map(drop))
+    }
+
+    /// The value of a node without substructures, reporting any it has as unhandled
+    fn line_value(&mut self, node: &GedcomNode) -> Result<String, ParseError> {
+        let value = self.value_of(node)?;
+        self.for_each_child(node, |p, child| p.unhandled(child, &node.tag).map(drop))?;
+        Ok(value)
+    }
+
+    /// The date of a `DATE` line, reporting an error when it does not follow the `DATE_VALUE` grammar.
+    /// Substructures are left to the caller.
+    fn parse_date(&mut self, node: &GedcomNode) -> Result<GedcomDate, ParseError> {
+        let date = GedcomDate::parse(&self.value_of(node)?);
+        // GEDCOM 7 leaves the date empty when it only has a `PHRASE`
+        if !date.is_valid() && !date.original.is_empty() {
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::UnrecognizedValue,
+                format!("Unrecognized date: {}", date.original),
+            )?;
+        }
+        Ok(date)
+    }
+
+    /// The age of an `AGE` line, reporting an error when it does not follow the `AGE_AT_EVENT` grammar
+    fn parse_age(&mut self, node: &GedcomNode) -> Result<Age, ParseError> {
+        let age = Age::parse(&self.line_value(node)?);
+        // GEDCOM 7 leaves the age empty when it only has a `PHRASE`
+        if !age.is_valid() && !age.original.is_empty() {
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::UnrecognizedValue,
+                format!("Unrecognized age: {}", age.original),
+            )?;
+        }
+        Ok(age)
+    }
+
+    /// The value of a node with its `@@` escapes undone, reporting an error when it has none
+    fn value_of(&mut self, node: &GedcomNode) -> Result<String, ParseError> {
+        if let Some(value) = &node.value {
+            return Ok(unescape(self.version, value));
         }
+        self.report(
+            node,
+            Severity::Error,
+            DiagnosticKind::MissingValue,
+            format!("Expected a value for {}", node.tag),
+        )?;
+        Ok(String::new())
+    }
 
-        value
+    /// The xref a pointer line links to, or `None` for the `@VOID@` pointer of GEDCOM 7,
+    /// which stands for a record that is not known
+    fn pointer(&mut self, node: &GedcomNode) -> Result<Option<String>, ParseError> {
+        let xref = self.line_value(node)?;
+        Ok((xref != "@VOID@").then_some(xref))
     }
 
-    /// Grabs and returns to the end of the current line as a String
-    fn take_line_value(&mut self) -> String {
-        let value: String;
-        self.tokenizer.next_token();
+    /// Like `pointer`, for pointer lines that have substructures
+    fn pointer_of(&mut self, node: &GedcomNode) -> Result<Option<String>, ParseError> {
+        let xref = self.value_of(node)?;
+        Ok((xref != "@VOID@").then_some(xref))
+    }
 
-        if let Token::LineValue(val) = &self.tokenizer.current_token {
-            value = val.to_string();
+    /// Reports a substructure the parser does not handle, returning it as custom data
+    /// when the parser is asked to keep unknown tags. It is an error when GEDCOM 5.5.1
+    /// does not allow it in `context`.
+    fn unhandled(
+        &mut self,
+        node: &GedcomNode,
+        context: &str,
+    ) -> Result<Option<CustomData>, ParseError> {
+        if node.is_custom() {
+            self.warn(
+                node,
+                DiagnosticKind::SkippedCustomTag,
+                format!("Skipping {context} custom tag: {}", node.tag),
+            )?;
+        } else if self.version == GedcomVersion::V5
+            && !skipped_substructures(context).contains(&node.tag.as_str())
+        {
+            self.report(
+                node,
+                Severity::Error,
+                DiagnosticKind::InvalidTag,
+                format!("Invalid {context} Tag: {}", node.tag),
+            )?;
         } else {
-            println!(
-                "ERROR  : {} Expected LineValue, found {:?}",
-                self.dbg(),
-                self.tokenizer.current_token
-            );
-            value = "".to_string();
+            self.warn(
+                node,
+                DiagnosticKind::UnhandledTag,
+                format!("Unhandled {context} Tag: {}", node.tag),
+            )?;
+        }
+        Ok(self.options.keep_unknown_tags.then(|| CustomData {
+            tag: node.tag.clone(),
+            value: node.value.clone().unwrap_or_default(),
+        }))
+    }
+
+    /// Records a non-fatal problem found on the line of `node`.
+    fn warn(
+        &mut self,
+        node: &GedcomNode,
+        kind: DiagnosticKind,
+        message: String,
+    ) -> Result<(), ParseError> {
+        self.report(node, Severity::Warning, kind, message)
+    }
+
+    /// Records a problem found on the line of `node` with the given severity.
+    fn report(
+        &mut self,
+        node: &GedcomNode,
+        severity: Severity,
+        kind: DiagnosticKind,
+        message: String,
+    ) -> Result<(), ParseError> {
+        let mut tag_path = self.tag_path.clone();
+        tag_path.push(node.tag.clone());
+        self.report_at(node.line, tag_path, severity, kind, message)
+    }
+
+    /// Records a problem found on `line`, failing instead when parsing strictly.
+    fn report_at(
+        &mut self,
+        line: u32,
+        tag_path: Vec<String>,
+        severity: Severity,
+        kind: DiagnosticKind,
+        message: String,
+    ) -> Result<(), ParseError> {
+        let diagnostic = Diagnostic {
+            severity,
+            line,
+            tag_path,
+            xref: self.record_xref.clone(),
+            message,
+            kind,
+        };
+        if self.options.strict && severity == Severity::Error {
+            return Err(ParseError::Nonconforming(diagnostic));
+        }
+        self.diagnostics.push(diagnostic);
+        Ok(())
+    }
+}
+
+/// The substructures GEDCOM 5.5.1 allows in a context passed to `Parser::unhandled` that the
+/// parser skips. Those it handles never reach `unhandled`, and so are left out.
+fn skipped_substructures(context: &str) -> &'static [&'static str] {
+    match context {
+        "Individual" => &[
+            "BAPL", "CONL", "ENDL", "SLGC", "SUBM", "ANCI", "DESI", "RFN", "AFN", "RIN",
+        ],
+        "Family" => &["RESN", "SUBM", "SLGS", "REFN", "RIN", "CHAN", "SOUR"],
+        "Submitter" => &[
+            "OBJE", "LANG", "RFN", "RIN", "NOTE", "CHAN", "EMAIL", "FAX", "WWW",
+        ],
+        "Repository" => &["PHON", "EMAIL", "FAX", "WWW", "REFN", "RIN", "CHAN"],
+        "Source" => &["REFN", "RIN", "CHAN"],
+        "Note" => &["REFN", "RIN", "SOUR", "CHAN"],
+        "Submission" => &["CHAN"],
+        "Source Data" | "FamilyLink" | "RepoCitation" | "Place" => &["NOTE"],
+        "Citation" => &["EVEN", "DATA", "OBJE"],
+        // the family a birth, christening or adoption was into
+        "Event" => &["FAMC"],
+        "CALN" => &["MEDI"],
+        _ => &[],
+    }
+}
+
+/// Closes the open nodes at `level` or deeper, adding each to the children of its parent
+fn close_nodes(open: &mut Vec<GedcomNode>, level: u8) {
+    while open.len() > 1 && open[open.len() - 1].level >= level {
+        if let (Some(node), Some(parent)) = (open.pop(), open.last_mut()) {
+            parent.children.push(node);
         }
-        self.tokenizer.next_token();
-        value
     }
+}
 
-    /// Debug function displaying GEDCOM line number of error message.
-    fn dbg(&self) -> String {
-        format!("line {}:", self.tokenizer.line)
+/// Undoes the `@@` escapes of a value of a file of `version`. GEDCOM 7 only doubles an `@`
+/// that begins a value.
+pub(crate) fn unescape(version: GedcomVersion, value: &str) -> String {
+    match version {
+        GedcomVersion::V5 => value.replace("@@", "@"),
+        GedcomVersion::V7 => match value.strip_prefix("@@") {
+            Some(rest) => format!("@{rest}"),
+            None => value.to_string(),
+        },
     }
 }
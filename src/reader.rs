@@ -0,0 +1,261 @@
+//! Incremental decoding of GEDCOM input read from any `BufRead`
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use crate::decode::{self, Encoding};
+
+/// A char iterator over the bytes read from a `BufRead`, decoded as they are needed.
+///
+/// Invalid byte sequences are replaced with `U+FFFD`. Iteration stops at the first
+/// read error, which is kept to be retrieved with `take_error`.
+pub struct ReadChars<R> {
+    reader: R,
+    /// The encoding of the input, once it is known
+    encoding: Option<Encoding>,
+    /// Bytes read ahead to detect the encoding, not yet decoded
+    read_ahead: VecDeque<u8>,
+    /// How many of the bytes read ahead have been scanned for the end of the header
+    scanned: usize,
+    /// Characters decoded ahead of the one last returned
+    pending: VecDeque<char>,
+    /// Whether a character has been returned yet, so a byte order mark can be dropped
+    started: bool,
+    /// Whether to drop a byte order mark rather than return it
+    drop_byte_order_mark: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> ReadChars<R> {
+    /// Creates a char iterator decoding the contents of `reader`, detecting its encoding
+    /// with `decode::detect` from the start of the input, read up to the `CHAR` line of
+    /// its header
+    #[must_use]
+    pub fn new(reader: R) -> ReadChars<R> {
+        ReadChars {
+            reader,
+            encoding: None,
+            read_ahead: VecDeque::new(),
+            scanned: 0,
+            pending: VecDeque::new(),
+            started: false,
+            drop_byte_order_mark: true,
+            error: None,
+        }
+    }
+
+    /// Creates a char iterator decoding the contents of `reader` in the given encoding
+    #[must_use]
+    pub fn with_encoding(reader: R, encoding: Encoding) -> ReadChars<R> {
+        ReadChars {
+            encoding: Some(encoding),
+            ..ReadChars::new(reader)
+        }
+    }
+
+    /// The encoding of the input, known once the first character has been read
+    #[must_use]
+    pub fn encoding(&self) -> Option<Encoding> {
+        self.encoding
+    }
+
+    /// Removes and returns the error that stopped the iteration, if there was one
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Returns a leading byte order mark as the first character instead of dropping it
+    pub(crate) fn keep_byte_order_mark(mut self) -> ReadChars<R> {
+        self.drop_byte_order_mark = false;
+        self
+    }
+
+    /// The encoding of the input, detected from the bytes read ahead if not yet known.
+    /// Reads stop once they hold enough of the header, however few bytes each one gives.
+    fn detect_encoding(&mut self) -> Option<Encoding> {
+        while self.encoding.is_none() {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.error = Some(err);
+                    return None;
+                }
+            };
+            let len = buf.len();
+            self.read_ahead.extend(buf);
+            self.reader.consume(len);
+            let bytes = self.read_ahead.make_contiguous();
+            // an empty read is the end of the input
+            if len == 0 || decode::is_detectable(bytes, &mut self.scanned) {
+                self.encoding = Some(decode::detect(bytes));
+            }
+        }
+        self.encoding
+    }
+
+    /// The next byte of the input, without consuming it
+    fn peek_byte(&mut self) -> Option<u8> {
+        if let Some(&byte) = self.read_ahead.front() {
+            return Some(byte);
+        }
+        if self.error.is_some() {
+            return None;
+        }
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return buf.first().copied(),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.error = Some(err);
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Consumes the next byte of the input
+    fn next_byte(&mut self) -> Option<u8> {
+        if let Some(byte) = self.read_ahead.pop_front() {
+            return Some(byte);
+        }
+        let byte = self.peek_byte()?;
+        self.reader.consume(1);
+        Some(byte)
+    }
+
+    fn next_utf8(&mut self) -> Option<char> {
+        let first = self.next_byte()?;
+
+        let (width, mut code) = match first {
+            0x00..=0x7F => return Some(char::from(first)),
+            0xC2..=0xDF => (2, u32::from(first & 0x1F)),
+            0xE0..=0xEF => (3, u32::from(first & 0x0F)),
+            0xF0..=0xF4 => (4, u32::from(first & 0x07)),
+            _ => return Some(char::REPLACEMENT_CHARACTER),
+        };
+
+        for i in 1..width {
+            // the second byte is restricted to rule out overlong forms, surrogates
+            // and code points past U+10FFFF
+            let continuation = match (i, first) {
+                (1, 0xE0) => 0xA0..=0xBF,
+                (1, 0xED) => 0x80..=0x9F,
+                (1, 0xF0) => 0x90..=0xBF,
+                (1, 0xF4) => 0x80..=0x8F,
+                _ => 0x80..=0xBF,
+            };
+            match self.peek_byte() {
+                Some(byte) if continuation.contains(&byte) => {
+                    self.next_byte();
+                    code = (code << 6) | u32::from(byte & 0x3F);
+                }
+                // leave the unexpected byte to start the next character
+                _ => return Some(char::REPLACEMENT_CHARACTER),
+            }
+        }
+
+        Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// The next UTF-16 code unit, or that of `U+FFFD` for an odd byte at the end of the input
+    fn next_utf16_unit(&mut self, from_bytes: fn([u8; 2]) -> u16) -> Option<u16> {
+        let first = self.next_byte()?;
+        Some(
+            self.next_byte()
+                .map_or(0xFFFD, |second| from_bytes([first, second])),
+        )
+    }
+
+    fn next_utf16(&mut self, from_bytes: fn([u8; 2]) -> u16) -> Option<char> {
+        let unit = self.next_utf16_unit(from_bytes)?;
+        if !(0xD800..=0xDBFF).contains(&unit) {
+            return Some(char::from_u32(u32::from(unit)).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+
+        match self.next_utf16_unit(from_bytes) {
+            Some(low @ 0xDC00..=0xDFFF) => {
+                let code = 0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+                Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+            }
+            // a high surrogate without its low half
+            Some(other) => {
+                let next = char::from_u32(u32::from(other)).unwrap_or(char::REPLACEMENT_CHARACTER);
+                self.pending.push_back(next);
+                Some(char::REPLACEMENT_CHARACTER)
+            }
+            None => Some(char::REPLACEMENT_CHARACTER),
+        }
+    }
+
+    fn next_ansel(&mut self) -> Option<char> {
+        // ANSEL puts combining diacritics before the character they modify
+        let mut marks = Vec::new();
+        let base = loop {
+            match self.next_byte() {
+                Some(byte) => match decode::ansel_combining(byte) {
+                    Some(mark) => marks.push(mark),
+                    None if byte.is_ascii() => break Some(char::from(byte)),
+                    None => {
+                        break Some(
+                            decode::ansel_spacing(byte).unwrap_or(char::REPLACEMENT_CHARACTER),
+                        )
+                    }
+                },
+                None => break None,
+            }
+        };
+
+        let base = match base {
+            Some(base) if !base.is_control() => base,
+            // diacritics with nothing to modify are kept on their own
+            _ => {
+                self.pending.extend(marks.iter().map(|&(mark, _)| mark));
+                self.pending.extend(base);
+                return self.pending.pop_front();
+            }
+        };
+
+        // put the diacritics in canonical order, then compose them with the base where
+        // Unicode has a precomposed character, as normalization form C does
+        marks.sort_by_key(|&(_, class)| class);
+        let mut composed = base;
+        let mut last_class = 0;
+        for (mark, class) in marks {
+            match decode::compose(composed, mark) {
+                Some(c) if last_class < class => composed = c,
+                _ => {
+                    self.pending.push_back(mark);
+                    last_class = class;
+                }
+            }
+        }
+        Some(composed)
+    }
+}
+
+impl<R: BufRead> Iterator for ReadChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(c) = self.pending.pop_front() {
+            return Some(c);
+        }
+
+        let c = match self.detect_encoding()? {
+            Encoding::Utf8 => self.next_utf8()?,
+            Encoding::Utf16Le => self.next_utf16(u16::from_le_bytes)?,
+            Encoding::Utf16Be => self.next_utf16(u16::from_be_bytes)?,
+            Encoding::Ansel => self.next_ansel()?,
+            Encoding::Ascii | Encoding::Windows1252 => decode::windows_1252(self.next_byte()?),
+            Encoding::Latin1 => char::from(self.next_byte()?),
+        };
+
+        if !self.started {
+            self.started = true;
+            if c == '\u{feff}' && self.drop_byte_order_mark {
+                return self.next();
+            }
+        }
+        Some(c)
+    }
+}
@@ -1,5 +1,7 @@
 //! Handles the tokenization of a GEDCOM file
-use std::str::Chars;
+use crate::error::ParseError;
+use crate::lexer::{self, LineParts};
+use crate::line::LineEnding;
 
 /// The base enum of Token types
 ///
@@ -24,26 +26,55 @@ pub enum Token {
 }
 
 /// The tokenizer that turns the gedcom characters into a list of tokens
-pub struct Tokenizer<'a> {
+///
+/// Each line is read into a buffer and split by the same rules as the borrowed
+/// `lexer::Lexer`, then its tokens are handed out as owned values one at a time.
+#[allow(clippy::struct_excessive_bools)]
+pub struct Tokenizer<I: Iterator<Item = char>> {
     /// The active token type
     pub current_token: Token,
-    /// Current character tokenizer is parsing
-    current_char: char,
     /// An iterator of charaters of the Gedcom file contents
-    chars: Chars<'a>,
+    chars: I,
     /// The current line number of the file we are parsing
     pub line: u32,
+    /// The contents of the current line, without its terminator
+    line_buf: String,
+    /// The parts of the current line not yet loaded as tokens
+    parts: LineParts,
+    /// Whether the previous line ended in a carriage return, which may be followed by a newline
+    after_cr: bool,
+    /// Whether the current line is written other than as `level [xref] tag [value]`
+    /// separated by single spaces
+    irregular: bool,
+    /// The terminator of the first line
+    line_ending: Option<LineEnding>,
+    /// Whether the last line read ended with a terminator
+    terminated: bool,
+    /// Whether the input started with a byte order mark
+    byte_order_mark: bool,
+    /// The level of the current line
+    level: u8,
+    /// The tags of the current line and each of its ancestors
+    tag_path: Vec<String>,
 }
 
-impl<'a> Tokenizer<'a> {
+impl<I: Iterator<Item = char>> Tokenizer<I> {
     /// Creates a new tokenizer for a char interator of gedcom file contents
     #[must_use]
-    pub fn new(chars: Chars<'a>) -> Tokenizer {
+    pub fn new(chars: I) -> Tokenizer<I> {
         Tokenizer {
-            current_char: '\n',
             current_token: Token::None,
             chars,
             line: 0,
+            line_buf: String::new(),
+            parts: LineParts::default(),
+            after_cr: false,
+            irregular: false,
+            line_ending: None,
+            terminated: false,
+            byte_order_mark: false,
+            level: 0,
+            tag_path: Vec::new(),
         }
     }
 
@@ -53,99 +84,124 @@ impl<'a> Tokenizer<'a> {
         self.current_token == Token::EOF
     }
 
-    /// Loads the next token into state
-    pub fn next_token(&mut self) {
-        if self.current_char == '\0' {
-            self.current_token = Token::EOF;
-            return;
-        }
-
-        // level number is at the start of each line.
-        if self.current_char == '\r' {
-            self.next_char();
-        }
-        if self.current_char == '\n' {
-            self.next_char();
-
-            self.current_token = Token::Level(self.extract_number());
-            self.line += 1;
-            return;
-        }
-
-        self.skip_whitespace();
+    /// The tags leading from the top-level record to the current line,
+    /// _ie._ `["INDI", "BIRT", "DATE"]`
+    #[must_use]
+    pub fn tag_path(&self) -> &[String] {
+        &self.tag_path
+    }
 
-        // handle tag with trailing whitespace
-        if self.current_char == '\n' {
-            // println!("line {}: trailing whitespace {:?}", self.line, self.current_token);
-            self.next_token();
-            return;
-        }
+    /// The terminator of the first line, or `None` if no line has ended yet
+    #[must_use]
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.line_ending
+    }
 
-        self.current_token = match self.current_token {
-            Token::Level(_) => {
-                if self.current_char == '@' {
-                    Token::Pointer(self.extract_word())
-                } else if self.current_char == '_' {
-                    Token::CustomTag(self.extract_word())
-                } else {
-                    Token::Tag(self.extract_word())
-                }
-            }
-            Token::Pointer(_) => Token::Tag(self.extract_word()),
-            Token::Tag(_) | Token::CustomTag(_) => Token::LineValue(self.extract_value()),
-            _ => panic!(
-                "line {}: Tokenization error! {:?}",
-                self.line, self.current_token
-            ),
-        };
+    /// Whether the last line read ended with a terminator, rather than the end of the input
+    #[must_use]
+    pub fn ends_with_line_ending(&self) -> bool {
+        self.terminated
     }
 
-    fn next_char(&mut self) {
-        self.current_char = self.chars.next().unwrap_or('\0');
+    /// Whether the input started with a byte order mark, which is not part of the first line
+    #[must_use]
+    pub fn byte_order_mark(&self) -> bool {
+        self.byte_order_mark
     }
 
-    fn extract_number(&mut self) -> u8 {
-        self.skip_whitespace();
-        let mut digits: Vec<char> = Vec::new();
-        while self.current_char.is_digit(10) {
-            digits.push(self.current_char);
-            self.next_char();
+    /// The text of the current line when it is not written as `level [xref] tag [value]`
+    /// separated by single spaces, such as when it is indented or has trailing spaces
+    pub(crate) fn irregular_line(&self) -> Option<&str> {
+        if self.irregular {
+            Some(&self.line_buf)
+        } else {
+            None
         }
+    }
 
-        digits.iter().collect::<String>().parse::<u8>().unwrap_or_else(|_|{
-            panic!("Valid level number not found on line {}", self.line)
-        })
+    /// The char iterator the tokenizer reads from
+    pub(crate) fn source_mut(&mut self) -> &mut I {
+        &mut self.chars
     }
 
-    fn extract_word(&mut self) -> String {
-        let mut letters: Vec<char> = Vec::new();
-        while !self.current_char.is_whitespace() && self.current_char != '\0' {
-            letters.push(self.current_char);
-            self.next_char();
+    /// Loads the next token into state
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` when a line has no valid level number.
+    pub fn next_token(&mut self) -> Result<(), ParseError> {
+        if let Some((token, _)) = self.parts.next_part(&self.line_buf) {
+            if let lexer::Token::Tag(tag) | lexer::Token::CustomTag(tag) = token {
+                self.tag_path.truncate(self.level.into());
+                self.tag_path.push(tag.to_string());
+            }
+            self.current_token = token.into();
+            return Ok(());
         }
 
-        letters.iter().collect::<String>()
-    }
-
-    fn extract_value(&mut self) -> String {
-        let mut letters: Vec<char> = Vec::new();
-        while self.current_char != '\n' && self.current_char != '\r' {
-            letters.push(self.current_char);
-            self.next_char();
+        while self.read_line() {
+            if self.line == 0 && self.line_buf.starts_with('\u{feff}') {
+                self.byte_order_mark = true;
+                self.line_buf.drain(..'\u{feff}'.len_utf8());
+            }
+            self.line += 1;
+            // skip blank lines
+            if let Some((level, level_span, parts)) = LineParts::split(&self.line_buf, self.line)? {
+                self.irregular = !parts.is_regular(&self.line_buf, &level_span);
+                self.level = level;
+                self.parts = parts;
+                self.current_token = Token::Level(level);
+                return Ok(());
+            }
         }
 
-        letters.iter().collect::<String>()
+        self.current_token = Token::EOF;
+        Ok(())
     }
 
-    fn skip_whitespace(&mut self) {
-        while self.is_nonnewline_whitespace() {
-            self.next_char();
+    /// Reads the next line into `line_buf`, returning false at the end of the input
+    fn read_line(&mut self) -> bool {
+        self.line_buf.clear();
+        let mut read_any = false;
+        for c in self.chars.by_ref() {
+            if std::mem::take(&mut self.after_cr) && c == '\n' {
+                // the first line turns out to end with CR LF
+                if self.line == 1 {
+                    self.line_ending = Some(LineEnding::CrLf);
+                }
+                continue;
+            }
+            read_any = true;
+            match c {
+                '\n' => {
+                    self.line_ending.get_or_insert(LineEnding::Lf);
+                    self.terminated = true;
+                    return true;
+                }
+                '\r' => {
+                    self.line_ending.get_or_insert(LineEnding::Cr);
+                    self.after_cr = true;
+                    self.terminated = true;
+                    return true;
+                }
+                _ => self.line_buf.push(c),
+            }
+        }
+        if read_any {
+            self.terminated = false;
         }
+        read_any
     }
+}
 
-    fn is_nonnewline_whitespace(&self) -> bool {
-        let is_zero_width_space = self.current_char as u32 == 65279_u32;
-        let not_a_newline = self.current_char != '\n';
-        (self.current_char.is_whitespace() || is_zero_width_space) && not_a_newline
+impl From<lexer::Token<'_>> for Token {
+    fn from(token: lexer::Token<'_>) -> Token {
+        match token {
+            lexer::Token::Level(level) => Token::Level(level),
+            lexer::Token::Tag(tag) => Token::Tag(tag.to_string()),
+            lexer::Token::LineValue(value) => Token::LineValue(value.to_string()),
+            lexer::Token::Pointer(xref) => Token::Pointer(xref.to_string()),
+            lexer::Token::CustomTag(tag) => Token::CustomTag(tag.to_string()),
+        }
     }
 }
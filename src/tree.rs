@@ -1,4 +1,8 @@
-use crate::types::{Family, Header, Individual, Media, Repository, Source, Submitter};
+use crate::node::GedcomNode;
+use crate::types::{
+    Family, Header, Individual, Media, MediaLink, Note, NoteStructure, Record, Repository, Source,
+    Submission, Submitter,
+};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +14,8 @@ pub struct GedcomData {
     pub header: Header,
     /// List of submitters of the facts
     pub submitters: Vec<Submitter>,
+    /// Submissions of the file for ordinance processing, of which there is at most one
+    pub submissions: Vec<Submission>,
     /// Individuals within the family tree
     pub individuals: Vec<Individual>,
     /// The family units of the tree, representing relationships between individuals
@@ -20,6 +26,10 @@ pub struct GedcomData {
     pub sources: Vec<Source>,
     /// A multimedia asset linked to a fact
     pub multimedia: Vec<Media>,
+    /// Notes shared between the records that reference them
+    pub notes: Vec<Note>,
+    /// Top-level records the parser does not handle, kept as they were read
+    pub custom_data: Vec<GedcomNode>,
 }
 
 // should maybe store these by xref if available?
@@ -49,17 +59,100 @@ impl GedcomData {
         self.submitters.push(submitter);
     }
 
+    /// Adds a `Submission` to the tree
+    pub fn add_submission(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// The submission the header of the file points to, from `HEAD.SUBN`
+    #[must_use]
+    pub fn submission(&self) -> Option<&Submission> {
+        let xref = self.header.submission_tag.as_deref()?;
+        self.submissions
+            .iter()
+            .find(|submission| submission.xref.as_deref() == Some(xref))
+    }
+
+    /// Adds a multimedia record to the tree
+    pub fn add_multimedia(&mut self, media: Media) {
+        self.multimedia.push(media);
+    }
+
+    /// Adds a shared `Note` to the tree
+    pub fn add_note(&mut self, note: Note) {
+        self.notes.push(note);
+    }
+
+    /// The shared `Note` record with `xref`, _ie._ `@N1@`
+    #[must_use]
+    pub fn note(&self, xref: &str) -> Option<&Note> {
+        self.notes
+            .iter()
+            .find(|note| note.xref.as_deref() == Some(xref))
+    }
+
+    /// The text of `note`, whether it is written in place or shared. `None` when the
+    /// shared note is not in the tree.
+    #[must_use]
+    pub fn note_text<'a>(&'a self, note: &'a NoteStructure) -> Option<&'a str> {
+        match note {
+            NoteStructure::Text(text) => Some(text),
+            NoteStructure::Shared(xref) => self.note(xref).map(|note| note.value.as_str()),
+        }
+    }
+
+    /// The multimedia record with `xref`, _ie._ `@M1@`
+    #[must_use]
+    pub fn media(&self, xref: &str) -> Option<&Media> {
+        self.multimedia
+            .iter()
+            .find(|media| media.xref.as_deref() == Some(xref))
+    }
+
+    /// The object `link` refers to, whether it is embedded or a record. `None` when the
+    /// record is not in the tree.
+    #[must_use]
+    pub fn linked_media<'a>(&'a self, link: &'a MediaLink) -> Option<&'a Media> {
+        match link {
+            MediaLink::Shared(xref) => self.media(xref),
+            MediaLink::Embedded(media) => Some(media.as_ref()),
+        }
+    }
+
+    /// Adds a top-level record to the tree, replacing the header if it is one
+    pub fn add_record(&mut self, record: Record) {
+        match record {
+            Record::Header(header) => self.header = header,
+            Record::Individual(individual) => self.add_individual(individual),
+            Record::Family(family) => self.add_family(family),
+            Record::Source(source) => self.add_source(source),
+            Record::Repository(repo) => self.add_repository(repo),
+            Record::Submitter(submitter) => self.add_submitter(submitter),
+            Record::Submission(submission) => self.add_submission(submission),
+            Record::Note(note) => self.add_note(note),
+            Record::Media(media) => self.add_multimedia(media),
+            Record::Custom(data) => self.add_custom_data(data),
+        }
+    }
+
+    /// Adds an unhandled top-level record to the tree
+    pub fn add_custom_data(&mut self, data: GedcomNode) {
+        self.custom_data.push(data);
+    }
+
     /// Outputs a summary of data contained in the tree to stdout
     pub fn stats(&self) {
         println!("----------------------");
         println!("| Gedcom Data Stats: |");
         println!("----------------------");
         println!("  submitters: {}", self.submitters.len());
+        println!("  submissions: {}", self.submissions.len());
         println!("  individuals: {}", self.individuals.len());
         println!("  families: {}", self.families.len());
         println!("  repositories: {}", self.repositories.len());
         println!("  sources: {}", self.sources.len());
         println!("  multimedia: {}", self.multimedia.len());
+        println!("  notes: {}", self.notes.len());
         println!("----------------------");
     }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Physical address at which a fact occurs
-#[derive(Default)]
+#[derive(Clone, Default, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Address {
     pub value: Option<String>,
@@ -0,0 +1,178 @@
+//! Ages of the `AGE_AT_EVENT` grammar of GEDCOM 5.5.1
+//!
+//! An `Age` keeps the text of an `AGE` line along with its parsed `AgeValue`, the same way
+//! a `GedcomDate` does.
+//!
+//! ```rust
+//! use gedcom::types::{Age, AgeBound, AgeDuration, AgeValue};
+//!
+//! let age = Age::parse("> 72y 3m");
+//! assert_eq!(
+//!     age.value,
+//!     Some(AgeValue::Duration(AgeDuration {
+//!         bound: Some(AgeBound::GreaterThan),
+//!         years: Some(72),
+//!         months: Some(3),
+//!         ..AgeDuration::default()
+//!     }))
+//! );
+//! assert_eq!(Age::parse("stillborn").value, Some(AgeValue::Stillborn));
+//! assert!(!Age::parse("about seventy").is_valid());
+//! ```
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The age of an `AGE` line, as it was read and as it was parsed
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Age {
+    /// The text of the `AGE` line
+    pub original: String,
+    /// The parsed age, `None` when the text does not follow the grammar
+    pub value: Option<AgeValue>,
+}
+
+impl Age {
+    /// Parses the text of an `AGE` line. The weeks of GEDCOM 7 are understood as well.
+    #[must_use]
+    pub fn parse(text: &str) -> Age {
+        Age {
+            original: text.to_string(),
+            value: text.parse().ok(),
+        }
+    }
+
+    /// An age for `value`, written the way GEDCOM 5.5.1 writes it
+    #[must_use]
+    pub fn new(value: AgeValue) -> Age {
+        Age {
+            original: value.to_string(),
+            value: Some(value),
+        }
+    }
+
+    /// Whether the text follows the `AGE_AT_EVENT` grammar
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.value.is_some()
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+
+/// A parsed `AGE_AT_EVENT`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum AgeValue {
+    /// A number of years, months, weeks and days, _ie._ `72y 3m`
+    Duration(AgeDuration),
+    /// `CHILD`, younger than 8 years
+    Child,
+    /// `INFANT`, younger than 1 year
+    Infant,
+    /// `STILLBORN`, died just before, at, or near birth
+    Stillborn,
+}
+
+impl FromStr for AgeValue {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        match text.to_uppercase().as_str() {
+            "CHILD" => return Ok(AgeValue::Child),
+            "INFANT" => return Ok(AgeValue::Infant),
+            "STILLBORN" => return Ok(AgeValue::Stillborn),
+            _ => {}
+        }
+
+        let (bound, rest) = match text.chars().next() {
+            Some('<') => (Some(AgeBound::LessThan), &text[1..]),
+            Some('>') => (Some(AgeBound::GreaterThan), &text[1..]),
+            _ => (None, text),
+        };
+        let mut duration = AgeDuration {
+            bound,
+            ..AgeDuration::default()
+        };
+        // the units go from the largest to the smallest, each at most once
+        let mut units = ['y', 'm', 'w', 'd'].iter();
+        let mut parts = rest.split_whitespace().peekable();
+        if parts.peek().is_none() {
+            return Err(());
+        }
+        for part in parts {
+            let last = part.chars().last().ok_or(())?;
+            let number = part[..part.len() - last.len_utf8()].parse().map_err(drop)?;
+            let unit = last.to_ascii_lowercase();
+            units.find(|known| **known == unit).ok_or(())?;
+            let field = match unit {
+                'y' => &mut duration.years,
+                'm' => &mut duration.months,
+                'w' => &mut duration.weeks,
+                _ => &mut duration.days,
+            };
+            *field = Some(number);
+        }
+        Ok(AgeValue::Duration(duration))
+    }
+}
+
+impl fmt::Display for AgeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgeValue::Duration(duration) => write!(f, "{duration}"),
+            AgeValue::Child => write!(f, "CHILD"),
+            AgeValue::Infant => write!(f, "INFANT"),
+            AgeValue::Stillborn => write!(f, "STILLBORN"),
+        }
+    }
+}
+
+/// A number of years, months, weeks and days, any of which may be left out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct AgeDuration {
+    /// Whether the age is only known to be less or greater than the duration
+    pub bound: Option<AgeBound>,
+    pub years: Option<u32>,
+    pub months: Option<u32>,
+    /// Weeks, which only GEDCOM 7 allows
+    pub weeks: Option<u32>,
+    pub days: Option<u32>,
+}
+
+impl fmt::Display for AgeDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bound {
+            Some(AgeBound::LessThan) => write!(f, "<")?,
+            Some(AgeBound::GreaterThan) => write!(f, ">")?,
+            None => {}
+        }
+        let parts = [
+            (self.years, 'y'),
+            (self.months, 'm'),
+            (self.weeks, 'w'),
+            (self.days, 'd'),
+        ];
+        let parts: Vec<String> = parts
+            .iter()
+            .filter_map(|(number, unit)| Some(format!("{}{unit}", (*number)?)))
+            .collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// `<` or `>`, for an age only known to be less or greater than a duration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum AgeBound {
+    LessThan,
+    GreaterThan,
+}
@@ -0,0 +1,186 @@
+use crate::types::Calendar;
+use std::convert::TryFrom;
+
+/// The Julian Day Number of 1 Tishrei of the first year of the Hebrew calendar
+const HEBREW_EPOCH: i64 = 347_998;
+
+/// The Julian Day Number of the day before 1 Vendémiaire of the year 0 of the French
+/// Republican calendar, such that year 1 begins on 22 September 1792
+const FRENCH_EPOCH: i64 = 2_375_474;
+
+impl Calendar {
+    /// The Julian Day Number of a day of the calendar, `None` when there is no such day.
+    ///
+    /// Years of the Gregorian and Julian calendars are astronomical, so 1 B.C. is the year
+    /// 0 and 2 B.C. the year -1. Months count from 1 in the order of `Calendar::months`.
+    ///
+    /// ```rust
+    /// use gedcom::types::Calendar;
+    ///
+    /// assert_eq!(Calendar::Gregorian.julian_day(2000, 1, 1), Some(2_451_545));
+    /// assert_eq!(Calendar::Julian.julian_day(1582, 10, 5), Some(2_299_161));
+    /// assert_eq!(Calendar::Gregorian.julian_day(1900, 2, 29), None);
+    /// ```
+    #[must_use]
+    pub fn julian_day(self, year: i64, month: u8, day: u8) -> Option<i64> {
+        if day == 0 || day > self.month_length(year, month)? {
+            return None;
+        }
+        let day = i64::from(day);
+        Some(match self {
+            Calendar::Gregorian | Calendar::Julian => {
+                let shift = i64::from(u8::from(month <= 2));
+                let year = year + 4800 - shift;
+                let month = i64::from(month) + 12 * shift - 3;
+                let days = day + (153 * month + 2) / 5 + 365 * year + year.div_euclid(4);
+                if self == Calendar::Gregorian {
+                    days - year.div_euclid(100) + year.div_euclid(400) - 32045
+                } else {
+                    days - 32083
+                }
+            }
+            Calendar::Hebrew => {
+                let months_before: i64 = (1..month)
+                    .filter_map(|month| Calendar::Hebrew.month_length(year, month))
+                    .map(i64::from)
+                    .sum();
+                hebrew_new_year(year) + months_before + day - 1
+            }
+            Calendar::FrenchRepublican => {
+                (year * 1461).div_euclid(4) + (i64::from(month) - 1) * 30 + day + FRENCH_EPOCH
+            }
+        })
+    }
+
+    /// The year, month and day of the calendar on a Julian Day Number, the inverse of
+    /// `Calendar::julian_day`. `None` for days before the epoch of the Hebrew and French
+    /// Republican calendars.
+    #[must_use]
+    pub fn from_julian_day(self, julian_day: i64) -> Option<(i64, u8, u8)> {
+        match self {
+            Calendar::Gregorian | Calendar::Julian => {
+                let mut f = julian_day + 1401;
+                if self == Calendar::Gregorian {
+                    f += ((4 * julian_day + 274_277).div_euclid(146_097) * 3).div_euclid(4) - 38;
+                }
+                let e = 4 * f + 3;
+                let h = 5 * e.rem_euclid(1461).div_euclid(4) + 2;
+                let day = h.rem_euclid(153).div_euclid(5) + 1;
+                let month = (h.div_euclid(153) + 2).rem_euclid(12) + 1;
+                let year = e.div_euclid(1461) - 4716 + (14 - month).div_euclid(12);
+                Some((year, u8::try_from(month).ok()?, u8::try_from(day).ok()?))
+            }
+            Calendar::Hebrew => {
+                if julian_day < HEBREW_EPOCH {
+                    return None;
+                }
+                // an estimate that is never past the year, then the years after it
+                let mut year = (julian_day - HEBREW_EPOCH) * 98_496 / 35_975_351;
+                while hebrew_new_year(year + 1) <= julian_day {
+                    year += 1;
+                }
+                let mut day = julian_day - hebrew_new_year(year);
+                for month in 1..=13 {
+                    let length = i64::from(self.month_length(year, month).unwrap_or(0));
+                    if day < length {
+                        return Some((year, month, u8::try_from(day + 1).ok()?));
+                    }
+                    day -= length;
+                }
+                None
+            }
+            Calendar::FrenchRepublican => {
+                if julian_day <= FRENCH_EPOCH + 365 {
+                    return None;
+                }
+                let days = (julian_day - FRENCH_EPOCH) * 4 - 1;
+                let year = days / 1461;
+                let day_of_year = days % 1461 / 4;
+                let month = u8::try_from(day_of_year / 30 + 1).ok()?;
+                let day = u8::try_from(day_of_year % 30 + 1).ok()?;
+                Some((year, month, day))
+            }
+        }
+    }
+
+    /// The number of days in a month of the calendar, `None` when the year has no such month,
+    /// _ie._ `ADS` in a Hebrew year that is not a leap year
+    #[must_use]
+    pub fn month_length(self, year: i64, month: u8) -> Option<u8> {
+        let length = match self {
+            Calendar::Gregorian | Calendar::Julian => match month {
+                1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+                4 | 6 | 9 | 11 => 30,
+                2 if self.is_leap_year(year) => 29,
+                2 => 28,
+                _ => return None,
+            },
+            Calendar::Hebrew => {
+                let year_length = hebrew_new_year(year + 1) - hebrew_new_year(year);
+                let leap = self.is_leap_year(year);
+                let long = match month {
+                    1 | 5 | 8 | 10 | 12 => true,
+                    4 | 9 | 11 | 13 => false,
+                    // Heshvan is long in years of 355 and 385 days
+                    2 => year_length % 10 == 5,
+                    // Kislev is short in years of 353 and 383 days
+                    3 => year_length % 10 != 3,
+                    // Adar I of a leap year, or Adar
+                    6 => leap,
+                    // Adar II, only in a leap year
+                    7 if leap => false,
+                    _ => return None,
+                };
+                if long {
+                    30
+                } else {
+                    29
+                }
+            }
+            Calendar::FrenchRepublican => match month {
+                1..=12 => 30,
+                13 if self.is_leap_year(year) => 6,
+                13 => 5,
+                _ => return None,
+            },
+        };
+        Some(length)
+    }
+
+    /// Whether a year of the calendar has a leap day or, in the Hebrew calendar, a leap month
+    #[must_use]
+    pub fn is_leap_year(self, year: i64) -> bool {
+        match self {
+            Calendar::Gregorian => {
+                year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+            }
+            Calendar::Julian => year.rem_euclid(4) == 0,
+            Calendar::Hebrew => (7 * year + 1).rem_euclid(19) < 7,
+            // the years 3, 7 and 11 the calendar was in use for, and every fourth after
+            Calendar::FrenchRepublican => (year + 1).rem_euclid(4) == 0,
+        }
+    }
+}
+
+/// The Julian Day Number of 1 Tishrei of a year of the Hebrew calendar
+fn hebrew_new_year(year: i64) -> i64 {
+    let delay = |year: i64| {
+        let months = (235 * year - 234).div_euclid(19);
+        let parts = 12_084 + 13_753 * months;
+        let day = months * 29 + parts.div_euclid(25_920);
+        // the new year is postponed when it would fall on a Sunday, Wednesday or Friday
+        if (3 * (day + 1)).rem_euclid(7) < 3 {
+            day + 1
+        } else {
+            day
+        }
+    };
+    // and when the year would have 356 days, or the previous year 382
+    let (last, this, next) = (delay(year - 1), delay(year), delay(year + 1));
+    let correction = if next - this == 356 {
+        2
+    } else {
+        i64::from(this - last == 382)
+    };
+    HEBREW_EPOCH + this + correction
+}
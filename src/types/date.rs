@@ -0,0 +1,583 @@
+//! Dates of the `DATE_VALUE` grammar of GEDCOM 5.5.1
+//!
+//! A `GedcomDate` keeps the text of a `DATE` line along with its parsed `DateValue`, so a
+//! date that does not follow the grammar is still written back the way it was read.
+//!
+//! ```rust
+//! use gedcom::types::{Approximation, Calendar, DateValue, GedcomDate};
+//!
+//! let date = GedcomDate::parse("ABT 12 FEB 1699/00");
+//! let Some(DateValue::Approximate(Approximation::About, date)) = date.value else {
+//!     panic!("expected an approximate date");
+//! };
+//! assert_eq!((date.day, date.month, date.year), (Some(12), Some(2), 1699));
+//! assert_eq!(date.dual_year, Some(1700));
+//!
+//! let date = GedcomDate::parse("BET @#DJULIAN@ 1700 AND @#DFRENCH R@ VEND 3");
+//! let Some(DateValue::Between(start, end)) = date.value else {
+//!     panic!("expected a range");
+//! };
+//! assert_eq!(start.calendar, Calendar::Julian);
+//! assert_eq!(end.calendar, Calendar::FrenchRepublican);
+//!
+//! assert!(!GedcomDate::parse("sometime in spring").is_valid());
+//! ```
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// The date of a `DATE` line, as it was read and as it was parsed
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct GedcomDate {
+    /// The text of the `DATE` line
+    pub original: String,
+    /// The parsed date, `None` when the text does not follow the grammar
+    pub value: Option<DateValue>,
+}
+
+impl GedcomDate {
+    /// Parses the text of a `DATE` line. The forms GEDCOM 7 writes differently, like the
+    /// `JULIAN` calendar and the `BCE` epoch, are understood as well.
+    #[must_use]
+    pub fn parse(text: &str) -> GedcomDate {
+        GedcomDate {
+            original: text.to_string(),
+            value: text.parse().ok(),
+        }
+    }
+
+    /// A date for `value`, written the way GEDCOM 5.5.1 writes it
+    #[must_use]
+    pub fn new(value: DateValue) -> GedcomDate {
+        GedcomDate {
+            original: value.to_string(),
+            value: Some(value),
+        }
+    }
+
+    /// Whether the text follows the `DATE_VALUE` grammar
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// The days the date may refer to, `None` for phrases and invalid dates
+    #[must_use]
+    pub fn day_range(&self) -> Option<DayRange> {
+        self.value.as_ref()?.day_range()
+    }
+}
+
+impl fmt::Display for GedcomDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+
+/// A parsed `DATE_VALUE`
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum DateValue {
+    /// A single date, which may be missing its day or month, _ie._ `MAR 1850`
+    Date(Date),
+    /// `ABT`, `CAL` or `EST` date
+    Approximate(Approximation, Date),
+    /// `BEF` date
+    Before(Date),
+    /// `AFT` date
+    After(Date),
+    /// `BET` date `AND` date
+    Between(Date, Date),
+    /// `FROM` date, `TO` date or `FROM` date `TO` date
+    Period(Option<Date>, Option<Date>),
+    /// `INT` date `(`phrase`)`, a date interpreted from the phrase
+    Interpreted(Date, String),
+    /// `(`phrase`)`, a date that could not be interpreted
+    Phrase(String),
+}
+
+impl FromStr for DateValue {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if let Some(phrase) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            return Ok(DateValue::Phrase(phrase.to_string()));
+        }
+
+        let (keyword, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let approximation = match keyword.to_uppercase().as_str() {
+            "ABT" => Approximation::About,
+            "CAL" => Approximation::Calculated,
+            "EST" => Approximation::Estimated,
+            "BEF" => return Ok(DateValue::Before(rest.parse()?)),
+            "AFT" => return Ok(DateValue::After(rest.parse()?)),
+            "BET" => {
+                let (start, end) = split_keyword(rest, "AND").ok_or(())?;
+                return Ok(DateValue::Between(start.parse()?, end.parse()?));
+            }
+            "FROM" => {
+                return Ok(match split_keyword(rest, "TO") {
+                    Some((start, end)) => {
+                        DateValue::Period(Some(start.parse()?), Some(end.parse()?))
+                    }
+                    None => DateValue::Period(Some(rest.parse()?), None),
+                })
+            }
+            "TO" => return Ok(DateValue::Period(None, Some(rest.parse()?))),
+            "INT" => {
+                let (date, phrase) = rest.split_once('(').ok_or(())?;
+                let phrase = phrase.trim_end().strip_suffix(')').ok_or(())?;
+                return Ok(DateValue::Interpreted(date.parse()?, phrase.to_string()));
+            }
+            _ => return Ok(DateValue::Date(text.parse()?)),
+        };
+        Ok(DateValue::Approximate(approximation, rest.parse()?))
+    }
+}
+
+impl DateValue {
+    /// The days the date may refer to, `None` for a phrase. `ABT`, `CAL` and `EST` dates
+    /// are taken at their word, as the specification gives them no margin.
+    #[must_use]
+    pub fn day_range(&self) -> Option<DayRange> {
+        let (first, last) = match self {
+            DateValue::Date(date)
+            | DateValue::Approximate(_, date)
+            | DateValue::Interpreted(date, _) => return date.day_range(),
+            DateValue::Before(date) => (None, Some(date.day_range()?.first? - 1)),
+            DateValue::After(date) => (Some(date.day_range()?.last? + 1), None),
+            DateValue::Between(start, end) | DateValue::Period(Some(start), Some(end)) => {
+                (start.day_range()?.first, end.day_range()?.last)
+            }
+            DateValue::Period(Some(start), None) => (start.day_range()?.first, None),
+            DateValue::Period(None, Some(end)) => (None, end.day_range()?.last),
+            DateValue::Period(None, None) | DateValue::Phrase(_) => return None,
+        };
+        Some(DayRange { first, last })
+    }
+}
+
+/// Splits `text` around the word `keyword`, _ie._ the `AND` of `BET 1850 AND 1860`
+fn split_keyword<'a>(text: &'a str, keyword: &str) -> Option<(&'a str, &'a str)> {
+    let mut offset = 0;
+    for word in text.split(' ') {
+        if word.eq_ignore_ascii_case(keyword) {
+            return Some((&text[..offset], &text[offset + word.len()..]));
+        }
+        offset += word.len() + 1;
+    }
+    None
+}
+
+impl fmt::Display for DateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateValue::Date(date) => write!(f, "{date}"),
+            DateValue::Approximate(approximation, date) => {
+                let keyword = match approximation {
+                    Approximation::About => "ABT",
+                    Approximation::Calculated => "CAL",
+                    Approximation::Estimated => "EST",
+                };
+                write!(f, "{keyword} {date}")
+            }
+            DateValue::Before(date) => write!(f, "BEF {date}"),
+            DateValue::After(date) => write!(f, "AFT {date}"),
+            DateValue::Between(start, end) => write!(f, "BET {start} AND {end}"),
+            DateValue::Period(start, end) => {
+                if let Some(start) = start {
+                    write!(f, "FROM {start}")?;
+                }
+                if let Some(end) = end {
+                    let space = if start.is_some() { " " } else { "" };
+                    write!(f, "{space}TO {end}")?;
+                }
+                Ok(())
+            }
+            DateValue::Interpreted(date, phrase) => write!(f, "INT {date} ({phrase})"),
+            DateValue::Phrase(phrase) => write!(f, "({phrase})"),
+        }
+    }
+}
+
+/// How a date was approximated, the keyword of an `ABT`, `CAL` or `EST` date
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Approximation {
+    /// `ABT`, about
+    About,
+    /// `CAL`, calculated from other dates
+    Calculated,
+    /// `EST`, estimated
+    Estimated,
+}
+
+/// The calendar of a date, from its escape, _ie._ `@#DJULIAN@`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Calendar {
+    /// `@#DGREGORIAN@`, assumed when a date has no escape
+    #[default]
+    Gregorian,
+    /// `@#DJULIAN@`
+    Julian,
+    /// `@#DHEBREW@`
+    Hebrew,
+    /// `@#DFRENCH R@`, the calendar of the French Republic
+    FrenchRepublican,
+}
+
+impl Calendar {
+    /// The abbreviations of the months of the calendar, in order
+    #[must_use]
+    pub fn months(self) -> &'static [&'static str] {
+        match self {
+            Calendar::Gregorian | Calendar::Julian => &[
+                "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+            ],
+            Calendar::Hebrew => &[
+                "TSH", "CSH", "KSL", "TVT", "SHV", "ADR", "ADS", "NSN", "IYR", "SVN", "TMZ", "AAV",
+                "ELL",
+            ],
+            Calendar::FrenchRepublican => &[
+                "VEND", "BRUM", "FRIM", "NIVO", "PLUV", "VENT", "GERM", "FLOR", "PRAI", "MESS",
+                "THER", "FRUC", "COMP",
+            ],
+        }
+    }
+
+    /// The calendar named by an escape or, as GEDCOM 7 writes it, a keyword
+    fn from_name(name: &str) -> Option<Calendar> {
+        let name = name
+            .strip_prefix("@#D")
+            .map_or(name, |name| name.strip_suffix('@').unwrap_or(name));
+        match name.to_uppercase().as_str() {
+            "GREGORIAN" => Some(Calendar::Gregorian),
+            "JULIAN" => Some(Calendar::Julian),
+            "HEBREW" => Some(Calendar::Hebrew),
+            "FRENCH R" | "FRENCH_R" => Some(Calendar::FrenchRepublican),
+            _ => None,
+        }
+    }
+}
+
+/// A single date of a calendar, which may be missing its day or month
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Date {
+    /// The calendar the date is given in
+    pub calendar: Calendar,
+    /// The day of the month
+    pub day: Option<u8>,
+    /// The month, counting from 1 in the order of `Calendar::months`
+    pub month: Option<u8>,
+    /// The year
+    pub year: u32,
+    /// The later year of a dual year, _ie._ 1751 for `1750/51`, given for dates between
+    /// the start of the year in March and in January
+    pub dual_year: Option<u32>,
+    /// Whether the year is before the common era, `B.C.`
+    pub before_common_era: bool,
+}
+
+impl FromStr for Date {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // the escape of the French Republican calendar is the one with a space in it
+        let text = text.replace("@#DFRENCH R@", "@#DFRENCH_R@");
+        let mut words: Vec<&str> = text.split_whitespace().collect();
+        let mut date = Date::default();
+
+        if let Some(calendar) = words.first().and_then(|word| Calendar::from_name(word)) {
+            date.calendar = calendar;
+            words.remove(0);
+        } else if words.first().is_some_and(|word| word.starts_with("@#")) {
+            // `@#DROMAN@`, `@#DUNKNOWN@` and escapes of other calendars
+            return Err(());
+        }
+        if words
+            .last()
+            .is_some_and(|word| ["B.C.", "BC", "BCE"].contains(&word.to_uppercase().as_str()))
+        {
+            date.before_common_era = true;
+            words.pop();
+        }
+
+        let (year, rest) = words.split_last().ok_or(())?;
+        let (year, dual_year) = match year.split_once('/') {
+            Some((year, dual)) if date.calendar == Calendar::Gregorian => (year, Some(dual)),
+            Some(_) => return Err(()),
+            None => (*year, None),
+        };
+        date.year = parse_digits(year)?;
+        if let Some(dual) = dual_year {
+            // the dual year is given by its last digits
+            let digits = u32::try_from(dual.len()).map_err(drop)?;
+            let place = 10_u32.checked_pow(digits).ok_or(())?;
+            let mut dual_year = (date.year - date.year % place)
+                .checked_add(parse_digits(dual)?)
+                .ok_or(())?;
+            if dual_year <= date.year {
+                dual_year = dual_year.checked_add(place).ok_or(())?;
+            }
+            date.dual_year = Some(dual_year);
+        }
+
+        let (month, rest) = match rest.split_last() {
+            Some((month, rest)) => (Some(*month), rest),
+            None => (None, rest),
+        };
+        if let Some(month) = month {
+            let position = date
+                .calendar
+                .months()
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(month))
+                .ok_or(())?;
+            date.month = Some(u8::try_from(position + 1).map_err(drop)?);
+        }
+
+        match rest {
+            [] => {}
+            [day] => {
+                let day = u8::try_from(parse_digits(day)?).map_err(drop)?;
+                if !(1..=31).contains(&day) {
+                    return Err(());
+                }
+                date.day = Some(day);
+            }
+            _ => return Err(()),
+        }
+        Ok(date)
+    }
+}
+
+impl Date {
+    /// The year as a number of the calendar, astronomical for the Gregorian and Julian
+    /// calendars, taking the later year of a dual year. `None` for a year before the common
+    /// era of the Hebrew or French Republican calendar.
+    fn calendar_year(&self) -> Option<i64> {
+        let year = i64::from(self.dual_year.unwrap_or(self.year));
+        match (self.before_common_era, self.calendar) {
+            (false, _) => Some(year),
+            (true, Calendar::Gregorian | Calendar::Julian) => Some(1 - year),
+            (true, _) => None,
+        }
+    }
+
+    /// The days the date may refer to, a whole month or year when it is missing its day or
+    /// month. `None` when the date does not exist in its calendar, _ie._ `30 FEB 1900`.
+    #[must_use]
+    pub fn day_range(&self) -> Option<DayRange> {
+        let year = self.calendar_year()?;
+        let (first, last) = match (self.month, self.day) {
+            (Some(month), Some(day)) => {
+                let day = self.calendar.julian_day(year, month, day)?;
+                (day, day)
+            }
+            (Some(month), None) => {
+                let first = self.calendar.julian_day(year, month, 1)?;
+                let length = self.calendar.month_length(year, month)?;
+                (first, first + i64::from(length) - 1)
+            }
+            (None, None) => (
+                self.calendar.julian_day(year, 1, 1)?,
+                self.calendar.julian_day(year + 1, 1, 1)? - 1,
+            ),
+            (None, Some(_)) => return None,
+        };
+        Some(DayRange {
+            first: Some(first),
+            last: Some(last),
+        })
+    }
+
+    /// The same day in another calendar, `None` unless the date has a day and month
+    ///
+    /// ```rust
+    /// use gedcom::types::{Calendar, DateValue, GedcomDate};
+    ///
+    /// let Some(DateValue::Date(date)) = GedcomDate::parse("@#DJULIAN@ 25 DEC 1642").value else {
+    ///     panic!("expected a single date");
+    /// };
+    /// let gregorian = date.to_calendar(Calendar::Gregorian).unwrap();
+    /// assert_eq!(gregorian.to_string(), "4 JAN 1643");
+    /// ```
+    #[must_use]
+    pub fn to_calendar(&self, calendar: Calendar) -> Option<Date> {
+        self.day?;
+        let range = self.day_range()?;
+        let (year, month, day) = calendar.from_julian_day(range.first?)?;
+        let before_common_era = year < 1;
+        let year = if before_common_era { 1 - year } else { year };
+        Some(Date {
+            calendar,
+            day: Some(day),
+            month: Some(month),
+            year: u32::try_from(year).ok()?,
+            dual_year: None,
+            before_common_era,
+        })
+    }
+}
+
+/// Parses a number of ASCII digits, refusing the signs `u32::from_str` takes
+fn parse_digits(text: &str) -> Result<u32, ()> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    text.parse().map_err(drop)
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.calendar {
+            Calendar::Gregorian => {}
+            Calendar::Julian => write!(f, "@#DJULIAN@ ")?,
+            Calendar::Hebrew => write!(f, "@#DHEBREW@ ")?,
+            Calendar::FrenchRepublican => write!(f, "@#DFRENCH R@ ")?,
+        }
+        if let Some(day) = self.day {
+            write!(f, "{day} ")?;
+        }
+        if let Some(month) = self.month.and_then(|month| {
+            self.calendar
+                .months()
+                .get(usize::from(month).checked_sub(1)?)
+        }) {
+            write!(f, "{month} ")?;
+        }
+        write!(f, "{}", self.year)?;
+        if let Some(dual_year) = self.dual_year {
+            write!(f, "/{:02}", dual_year % 100)?;
+        }
+        if self.before_common_era {
+            write!(f, " B.C.")?;
+        }
+        Ok(())
+    }
+}
+
+/// A range of days given by their Julian Day Number, which is open at the start or end for
+/// `BEF`, `AFT`, `FROM` and `TO` dates
+///
+/// Ranges are ordered when one is certainly before the other. Ranges that overlap cannot be
+/// ordered, unless they are the same range, so `chronology` tells how likely each order is.
+///
+/// ```rust
+/// use gedcom::types::{Chronology, GedcomDate};
+///
+/// // the Julian 10 January 1700 is the Gregorian 20 January
+/// let baptism = GedcomDate::parse("@#DJULIAN@ 10 JAN 1700").day_range().unwrap();
+/// let burial = GedcomDate::parse("15 JAN 1700").day_range().unwrap();
+/// assert!(burial < baptism);
+///
+/// let birth = GedcomDate::parse("BET 1848 AND 1850").day_range().unwrap();
+/// let christening = GedcomDate::parse("BET 1849 AND 1851").day_range().unwrap();
+/// assert_eq!(birth.partial_cmp(&christening), None);
+/// assert_eq!(birth.chronology(&christening), Chronology::PossiblyBefore);
+///
+/// let census = GedcomDate::parse("JUN 1850").day_range().unwrap();
+/// assert_eq!(birth.chronology(&census), Chronology::Overlapping);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct DayRange {
+    /// The first day, `None` when the range has no start
+    pub first: Option<i64>,
+    /// The last day, `None` when the range has no end
+    pub last: Option<i64>,
+}
+
+impl DayRange {
+    /// How this range lies in time relative to `other`
+    #[must_use]
+    pub fn chronology(&self, other: &DayRange) -> Chronology {
+        let (first, last) = self.bounds();
+        let (other_first, other_last) = other.bounds();
+        if last < other_first {
+            Chronology::Before
+        } else if first > other_last {
+            Chronology::After
+        } else if first < other_first && last < other_last {
+            Chronology::PossiblyBefore
+        } else if first > other_first && last > other_last {
+            Chronology::PossiblyAfter
+        } else {
+            Chronology::Overlapping
+        }
+    }
+
+    /// The number of days from this range to a `later` one, `None` when either is open
+    ///
+    /// ```rust
+    /// use gedcom::types::GedcomDate;
+    ///
+    /// let birth = GedcomDate::parse("1 JAN 1899").day_range().unwrap();
+    /// let death = GedcomDate::parse("DEC 1990").day_range().unwrap();
+    /// let lifetime = birth.duration_until(&death).unwrap();
+    /// assert_eq!((lifetime.min_days, lifetime.max_days), (33_571, 33_601));
+    /// ```
+    #[must_use]
+    pub fn duration_until(&self, later: &DayRange) -> Option<Duration> {
+        Some(Duration {
+            min_days: later.first? - self.last?,
+            max_days: later.last? - self.first?,
+        })
+    }
+
+    /// The first and last day, with an open start or end as the earliest or latest day
+    fn bounds(&self) -> (i64, i64) {
+        (
+            self.first.unwrap_or(i64::MIN),
+            self.last.unwrap_or(i64::MAX),
+        )
+    }
+}
+
+impl PartialOrd for DayRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        match self.chronology(other) {
+            Chronology::Before => Some(Ordering::Less),
+            Chronology::After => Some(Ordering::Greater),
+            _ => None,
+        }
+    }
+}
+
+/// How one `DayRange` lies in time relative to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Chronology {
+    /// Every day of the range is before the other
+    Before,
+    /// The ranges overlap, and the range starts and ends before the other
+    PossiblyBefore,
+    /// The ranges overlap, and one of them holds the other
+    Overlapping,
+    /// The ranges overlap, and the range starts and ends after the other
+    PossiblyAfter,
+    /// Every day of the range is after the other
+    After,
+}
+
+/// The number of days between two `DayRange`s, as few and as many as their days allow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Duration {
+    /// The fewest days between the ranges, negative when they overlap
+    pub min_days: i64,
+    /// The most days between the ranges
+    pub max_days: i64,
+}
@@ -1,8 +1,12 @@
-use crate::types::SourceCitation;
+use crate::types::{Address, Age, GedcomDate, MediaLink, NoteStructure, Place, SourceCitation};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
-use std::{fmt, string::ToString};
+use std::fmt;
 
+/// The kind of an event or attribute, named after its tag
+///
+/// Attributes, like `Occupation`, describe the individual rather than something that
+/// happened to them, and keep the description in the `value` of their `Event`.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
@@ -18,23 +22,228 @@ pub enum EventType {
 
     // "Other" is used to construct an event without requiring an explicit event type
     Other,
-    Unkown
+
+    /// `CHRA`
+    AdultChristening,
+    /// `BAPM`
+    Baptism,
+    /// `BARM`
+    BarMitzvah,
+    /// `BASM`
+    BasMitzvah,
+    /// `BLES`
+    Blessing,
+    /// `CENS`
+    Census,
+    /// `CONF`
+    Confirmation,
+    /// `CREM`
+    Cremation,
+    /// `EMIG`
+    Emigration,
+    /// `FCOM`
+    FirstCommunion,
+    /// `GRAD`
+    Graduation,
+    /// `IMMI`
+    Immigration,
+    /// `NATU`
+    Naturalization,
+    /// `ORDN`
+    Ordination,
+    /// `PROB`
+    Probate,
+    /// `RETI`
+    Retirement,
+    /// `WILL`
+    Will,
+
+    /// `ANUL`, a family event
+    Annulment,
+    /// `DIV`, a family event
+    Divorce,
+    /// `DIVF`, a family event
+    DivorceFiled,
+    /// `ENGA`, a family event
+    Engagement,
+    /// `MARB`, a family event
+    MarriageBann,
+    /// `MARC`, a family event
+    MarriageContract,
+    /// `MARL`, a family event
+    MarriageLicense,
+    /// `MARS`, a family event
+    MarriageSettlement,
+
+    /// `CAST`, an attribute
+    Caste,
+    /// `DSCR`, an attribute
+    PhysicalDescription,
+    /// `EDUC`, an attribute
+    Education,
+    /// `IDNO`, an attribute
+    IdentityNumber,
+    /// `NATI`, an attribute
+    Nationality,
+    /// `NCHI`, an attribute
+    ChildrenCount,
+    /// `NMR`, an attribute
+    MarriageCount,
+    /// `OCCU`, an attribute
+    Occupation,
+    /// `PROP`, an attribute
+    Property,
+    /// `RELI`, an attribute
+    Religion,
+    /// `SSN`, an attribute
+    SocialSecurityNumber,
+    /// `TITL`, an attribute
+    NobilityTitle,
+    /// `FACT`, an attribute
+    Fact,
+
+    /// An `EVEN` with the `TYPE` that names it, or an event of a tag not listed here
+    Custom(String),
 }
 
-impl ToString for EventType {
-    fn to_string(&self) -> String {
-        format!("{:?}", self)
+/// The tags of the event types
+const TAGS: &[(&str, EventType)] = &[
+    ("ADOP", EventType::Adoption),
+    ("BIRT", EventType::Birth),
+    ("BURI", EventType::Burial),
+    ("DEAT", EventType::Death),
+    ("CHR", EventType::Christening),
+    ("MARR", EventType::Marriage),
+    ("RESI", EventType::Residence),
+    ("EVEN", EventType::Other),
+    ("CHRA", EventType::AdultChristening),
+    ("BAPM", EventType::Baptism),
+    ("BARM", EventType::BarMitzvah),
+    ("BASM", EventType::BasMitzvah),
+    ("BLES", EventType::Blessing),
+    ("CENS", EventType::Census),
+    ("CONF", EventType::Confirmation),
+    ("CREM", EventType::Cremation),
+    ("EMIG", EventType::Emigration),
+    ("FCOM", EventType::FirstCommunion),
+    ("GRAD", EventType::Graduation),
+    ("IMMI", EventType::Immigration),
+    ("NATU", EventType::Naturalization),
+    ("ORDN", EventType::Ordination),
+    ("PROB", EventType::Probate),
+    ("RETI", EventType::Retirement),
+    ("WILL", EventType::Will),
+    ("ANUL", EventType::Annulment),
+    ("DIV", EventType::Divorce),
+    ("DIVF", EventType::DivorceFiled),
+    ("ENGA", EventType::Engagement),
+    ("MARB", EventType::MarriageBann),
+    ("MARC", EventType::MarriageContract),
+    ("MARL", EventType::MarriageLicense),
+    ("MARS", EventType::MarriageSettlement),
+    ("CAST", EventType::Caste),
+    ("DSCR", EventType::PhysicalDescription),
+    ("EDUC", EventType::Education),
+    ("IDNO", EventType::IdentityNumber),
+    ("NATI", EventType::Nationality),
+    ("NCHI", EventType::ChildrenCount),
+    ("NMR", EventType::MarriageCount),
+    ("OCCU", EventType::Occupation),
+    ("PROP", EventType::Property),
+    ("RELI", EventType::Religion),
+    ("SSN", EventType::SocialSecurityNumber),
+    ("TITL", EventType::NobilityTitle),
+    ("FACT", EventType::Fact),
+];
+
+impl EventType {
+    /// The tag of the event type, `EVEN` for `Other` and `SourceData`. A `Custom` type is
+    /// written as the tag it names when that is an extension tag, _ie._ `_MILT`, and as an
+    /// `EVEN` with a `TYPE` otherwise.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        match self {
+            EventType::Custom(tag) if tag.starts_with('_') => tag,
+            // `SourceData` and other `Custom` types are not in the table
+            event_type => TAGS
+                .iter()
+                .find(|(_, tagged)| tagged == event_type)
+                .map_or("EVEN", |(tag, _)| tag),
+        }
+    }
+
+    /// Whether the type is an attribute, describing an individual rather than an event
+    #[must_use]
+    pub fn is_attribute(&self) -> bool {
+        matches!(
+            self,
+            EventType::Caste
+                | EventType::PhysicalDescription
+                | EventType::Education
+                | EventType::IdentityNumber
+                | EventType::Nationality
+                | EventType::ChildrenCount
+                | EventType::MarriageCount
+                | EventType::Occupation
+                | EventType::Property
+                | EventType::Religion
+                | EventType::SocialSecurityNumber
+                | EventType::NobilityTitle
+                | EventType::Fact
+        )
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
     }
 }
 
 /// Event fact
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Event {
     pub event: EventType,
-    pub date: Option<String>,
-    pub place: Option<String>,
+    /// The value of the event line: the description of an attribute, `Y` for an event
+    /// only known to have happened, or what an `EVEN` was
+    pub value: Option<String>,
+    pub date: Option<GedcomDate>,
+    /// How the date was given in the source, from `DATE.PHRASE` (GEDCOM 7)
+    pub date_phrase: Option<String>,
+    pub place: Option<Place>,
     pub citations: Vec<SourceCitation>,
+    /// A classification of the event, _ie._ `Stillborn` for a birth or the kind of number
+    /// of an `IdentityNumber`, from `TYPE`. The `TYPE` of an `EVEN` makes it `Custom` instead.
+    pub classification: Option<String>,
+    /// The age of the individual at the event, from `AGE`
+    pub age: Option<Age>,
+    /// The age of the husband at a family event, from `HUSB.AGE`
+    pub husband_age: Option<Age>,
+    /// The age of the wife at a family event, from `WIFE.AGE`
+    pub wife_age: Option<Age>,
+    /// The authority responsible for the event, _ie._ a church or a court, from `AGNC`
+    pub agency: Option<String>,
+    /// What caused the event, _ie._ the cause of a death, from `CAUS`
+    pub cause: Option<String>,
+    /// The religion the event was held under, from `RELI`
+    pub religion: Option<String>,
+    /// The privacy of the event, _ie._ `confidential` or `locked`, from `RESN`
+    pub restriction: Option<String>,
+    /// The address at which the event happened, from `ADDR`
+    pub address: Option<Address>,
+    /// Phone numbers for the address of the event, from `PHON`
+    pub phones: Vec<String>,
+    /// Email addresses for the address of the event, from `EMAIL`
+    pub emails: Vec<String>,
+    /// Fax numbers for the address of the event, from `FAX`
+    pub faxes: Vec<String>,
+    /// Web pages for the address of the event, from `WWW`
+    pub websites: Vec<String>,
+    /// Notes about the event, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// The multimedia of the event, from `OBJE`
+    pub media: Vec<MediaLink>,
 }
 
 impl Event {
@@ -42,9 +251,26 @@ impl Event {
     pub fn new(etype: EventType) -> Event {
         Event {
             event: etype,
+            value: None,
             date: None,
+            date_phrase: None,
             place: None,
             citations: Vec::new(),
+            classification: None,
+            age: None,
+            husband_age: None,
+            wife_age: None,
+            agency: None,
+            cause: None,
+            religion: None,
+            restriction: None,
+            address: None,
+            phones: Vec::new(),
+            emails: Vec::new(),
+            faxes: Vec::new(),
+            websites: Vec::new(),
+            notes: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -53,27 +279,21 @@ impl Event {
         self.event = EventType::SourceData(value);
     }
 
+    /// An event of the type with `tag`, `Custom` when it is not a known event or attribute
     #[must_use]
     pub fn from_tag(tag: &str) -> Event {
         let etype = match tag {
-            "ADOP" => EventType::Adoption,
-            "BIRT" => EventType::Birth,
-            "BURI" => EventType::Burial,
-            "CHR" => EventType::Christening,
-            "DEAT" => EventType::Death,
-            "MARR" => EventType::Marriage,
-            "RESI" => EventType::Residence,
             "OTHER" => EventType::Other,
-            _ => {
-                println!("Unrecognized event tag: {}", tag);
-                EventType::Unkown
-            }
+            _ => TAGS.iter().find(|(tagged, _)| *tagged == tag).map_or_else(
+                || EventType::Custom(tag.to_string()),
+                |(_, etype)| etype.clone(),
+            ),
         };
         Event::new(etype)
     }
 
     pub fn add_citation(&mut self, citation: SourceCitation) {
-        self.citations.push(citation)
+        self.citations.push(citation);
     }
 
     #[must_use]
@@ -87,8 +307,26 @@ impl std::fmt::Debug for Event {
         let event_type = format!("{:?} Event", &self.event);
         let mut debug = f.debug_struct(&event_type);
 
+        fmt_optional_value!(debug, "value", &self.value);
         fmt_optional_value!(debug, "date", &self.date);
+        fmt_optional_value!(debug, "date_phrase", &self.date_phrase);
         fmt_optional_value!(debug, "place", &self.place);
+        debug.field("citations", &self.citations);
+        fmt_optional_value!(debug, "classification", &self.classification);
+        fmt_optional_value!(debug, "age", &self.age);
+        fmt_optional_value!(debug, "husband_age", &self.husband_age);
+        fmt_optional_value!(debug, "wife_age", &self.wife_age);
+        fmt_optional_value!(debug, "agency", &self.agency);
+        fmt_optional_value!(debug, "cause", &self.cause);
+        fmt_optional_value!(debug, "religion", &self.religion);
+        fmt_optional_value!(debug, "restriction", &self.restriction);
+        fmt_optional_value!(debug, "address", &self.address);
+        debug.field("phones", &self.phones);
+        debug.field("emails", &self.emails);
+        debug.field("faxes", &self.faxes);
+        debug.field("websites", &self.websites);
+        debug.field("notes", &self.notes);
+        debug.field("media", &self.media);
 
         debug.finish()
     }
@@ -98,8 +336,8 @@ impl std::fmt::Debug for Event {
 pub trait HasEvents {
     fn add_event(&mut self, event: Event) -> ();
     fn events(&self) -> Vec<Event>;
-    fn dates(&self) -> Vec<String> {
-        let mut dates: Vec<String> = Vec::new();
+    fn dates(&self) -> Vec<GedcomDate> {
+        let mut dates: Vec<GedcomDate> = Vec::new();
         for event in self.events() {
             if let Some(d) = &event.date {
                 dates.push(d.clone());
@@ -107,8 +345,8 @@ pub trait HasEvents {
         }
         dates
     }
-    fn places(&self) -> Vec<String> {
-        let mut places: Vec<String> = Vec::new();
+    fn places(&self) -> Vec<Place> {
+        let mut places: Vec<Place> = Vec::new();
         for event in self.events() {
             if let Some(p) = &event.place {
                 places.push(p.clone());
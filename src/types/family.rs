@@ -1,4 +1,6 @@
-use crate::types::{event::HasEvents, Event};
+use crate::types::{
+    event::HasEvents, CustomData, Event, ExternalId, GedcomNode, MediaLink, NoteStructure,
+};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
@@ -8,7 +10,7 @@ type Xref = String;
 ///
 /// This data representation understands that HUSB & WIFE are just poorly-named
 /// pointers to individuals. no gender "validating" is done on parse.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Family {
     pub xref: Option<Xref>,
@@ -16,7 +18,16 @@ pub struct Family {
     pub individual2: Option<Xref>, // mapped from WIFE
     pub children: Vec<Xref>,
     pub num_children: Option<u8>,
+    pub custom_data: Vec<CustomData>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// Notes about the family, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// Multimedia of the family, from `OBJE`
+    pub media: Vec<MediaLink>,
     events: Vec<Event>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 impl Family {
@@ -28,37 +39,52 @@ impl Family {
             individual2: None,
             children: Vec::new(),
             num_children: None,
+            custom_data: Vec::new(),
+            external_ids: Vec::new(),
+            notes: Vec::new(),
+            media: Vec::new(),
             events: Vec::new(),
+            node: None,
         }
     }
 
+    /// Sets the individual mapped from `HUSB`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the family already has a first individual.
     pub fn set_individual1(&mut self, xref: Xref) {
         match self.individual1 {
             Some(_) => panic!("First individual of family already exists."),
             None => self.individual1 = Some(xref),
-        };
+        }
     }
 
+    /// Sets the individual mapped from `WIFE`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the family already has a second individual.
     pub fn set_individual2(&mut self, xref: Xref) {
         match self.individual2 {
             Some(_) => panic!("Second individual of family already exists."),
             None => self.individual2 = Some(xref),
-        };
+        }
     }
 
     pub fn add_child(&mut self, xref: Xref) {
         self.children.push(xref);
     }
+
+    pub fn add_custom_data(&mut self, data: CustomData) {
+        self.custom_data.push(data);
+    }
 }
 
 impl HasEvents for Family {
-    fn add_event(&mut self, event: Event) -> () {
-        let event_type = &event.event;
-        for e in &self.events {
-            if &e.event == event_type {
-                panic!("Family already has a {:?} event", e.event);
-            }
-        }
+    /// Adds a family event. A family may have several events of the same kind, _ie._ a
+    /// census every ten years.
+    fn add_event(&mut self, event: Event) {
         self.events.push(event);
     }
     fn events(&self) -> Vec<Event> {
@@ -1,23 +1,36 @@
-use crate::types::Source;
+use crate::types::{Address, GedcomDate, GedcomNode};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 /// Header containing GEDCOM metadata
 pub struct Header {
+    /// The program that wrote the file, from `SOUR`
+    pub source: Option<HeaderSource>,
     pub encoding: Option<String>,
+    /// The version of the character set, from `CHAR.VERS`
+    pub encoding_version: Option<String>,
     pub copyright: Option<String>,
     pub corporation: Option<String>,
-    pub date: Option<String>,
+    /// The date the file was created
+    pub date: Option<GedcomDate>,
+    /// The time of day the file was created, from `DATE.TIME`
+    pub time: Option<String>,
     pub destinations: Vec<String>,
     pub gedcom_version: Option<String>,
     pub language: Option<String>,
+    /// The levels of the jurisdictions of every place in the file, _ie._
+    /// `City, County, State, Country`, from `PLAC.FORM`
+    pub place_form: Option<String>,
     pub filename: Option<String>,
     pub note: Option<String>,
-    pub sources: Vec<Source>,
     pub submitter_tag: Option<String>,
     pub submission_tag: Option<String>,
+    /// The extension tags declared by `SCHMA` (GEDCOM 7)
+    pub extension_tags: Vec<ExtensionTag>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 impl Header {
@@ -25,13 +38,97 @@ impl Header {
         self.destinations.push(destination);
     }
 
-    pub fn add_source(&mut self, source: Source) {
-        self.sources.push(source);
+    /// The version of the specification the file follows, from `GEDC.VERS`
+    #[must_use]
+    pub fn version(&self) -> GedcomVersion {
+        self.gedcom_version
+            .as_deref()
+            .map_or(GedcomVersion::default(), GedcomVersion::from_number)
     }
 }
 
-// pub struct HeaderSource {
-//     version: Option<String>,
-//     name: Option<String>,
-//     coroporation:
-// }
+/// The major version of the GEDCOM specification a file follows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum GedcomVersion {
+    /// GEDCOM 5.5 and 5.5.1, assumed when a file does not say
+    #[default]
+    V5,
+    /// GEDCOM 7.0 and its minor revisions
+    V7,
+}
+
+impl GedcomVersion {
+    /// The version for a `GEDC.VERS` value, _ie._ `5.5.1` or `7.0`
+    #[must_use]
+    pub fn from_number(number: &str) -> GedcomVersion {
+        if number.trim().starts_with('7') {
+            GedcomVersion::V7
+        } else {
+            GedcomVersion::V5
+        }
+    }
+}
+
+/// An extension tag declared in the `SCHMA` of a GEDCOM 7 header, _ie._
+/// `2 TAG _SKYPEID http://xmlns.com/foaf/0.1/skypeID`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ExtensionTag {
+    /// The tag, beginning with an underscore
+    pub tag: String,
+    /// The URI defining the meaning of the tag
+    pub uri: String,
+}
+
+/// The program that wrote a file, the `SOUR` of its header
+///
+/// Programs write files with quirks of their own, which the `system_id` and `version`
+/// tell apart, _ie._ `RootsMagic` and `9.1.3`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct HeaderSource {
+    /// The identifier of the program, the value of `SOUR`
+    pub system_id: String,
+    /// The version of the program, from `VERS`
+    pub version: Option<String>,
+    /// The name of the program, from `NAME`
+    pub name: Option<String>,
+    /// The business behind the program, from `CORP`
+    pub corporation: Option<Corporation>,
+    /// The data the file was made from, from `DATA`
+    pub data: Option<HeaderSourceData>,
+}
+
+/// The business behind a program, the `CORP` of a header source
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Corporation {
+    /// The name of the business
+    pub name: String,
+    /// The address of the business, from `ADDR`
+    pub address: Option<Address>,
+    /// Phone numbers of the business, from `PHON`
+    pub phones: Vec<String>,
+    /// Email addresses of the business, from `EMAIL`
+    pub emails: Vec<String>,
+    /// Fax numbers of the business, from `FAX`
+    pub faxes: Vec<String>,
+    /// Web pages of the business, from `WWW`
+    pub websites: Vec<String>,
+}
+
+/// The electronic data a file was made from, the `DATA` of a header source, _ie._ a CD
+/// a program exported the file from
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct HeaderSourceData {
+    /// The name of the data
+    pub name: String,
+    /// The date the data was published, from `DATE`
+    pub date: Option<GedcomDate>,
+    /// The time of day the data was published, from `DATE.TIME` (GEDCOM 7)
+    pub time: Option<String>,
+    /// The copyright of the data, from `COPR`
+    pub copyright: Option<String>,
+}
@@ -1,20 +1,44 @@
-use crate::types::{event::HasEvents, CustomData, Event};
+use crate::types::{
+    event::HasEvents, CustomData, Event, ExternalId, GedcomDate, GedcomNode, MediaLink,
+    NoteStructure, SourceCitation, UserReference,
+};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 type Xref = String;
 
 /// A Person within the family tree
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Individual {
     pub xref: Option<Xref>,
-    pub name: Option<Name>,
+    /// The names of the individual, the first being the preferred one
+    pub names: Vec<Name>,
     pub sex: Gender,
     pub families: Vec<FamilyLink>,
     pub custom_data: Vec<CustomData>,
-    pub last_updated: Option<String>,
+    /// The date of the last change to the record, from `CHAN.DATE`
+    pub last_updated: Option<GedcomDate>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// Notes about the individual, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// Portraits and other multimedia of the individual, from `OBJE`
+    pub media: Vec<MediaLink>,
+    /// Sources for the individual as a whole, from `SOUR`
+    pub citations: Vec<SourceCitation>,
+    /// Other records that may describe the same person, from `ALIA`
+    pub aliases: Vec<Xref>,
+    /// People the individual is related to other than by family, from `ASSO`
+    pub associations: Vec<Association>,
+    /// How the record is restricted, _ie._ `confidential` or `privacy`, from `RESN`
+    pub restriction: Option<String>,
+    /// Numbers the submitter gave the record, from `REFN`
+    pub user_references: Vec<UserReference>,
     events: Vec<Event>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 impl Individual {
@@ -22,15 +46,30 @@ impl Individual {
     pub fn new(xref: Option<Xref>) -> Individual {
         Individual {
             xref,
-            name: None,
+            names: Vec::new(),
             sex: Gender::Unknown,
             events: Vec::new(),
             families: Vec::new(),
             custom_data: Vec::new(),
             last_updated: None,
+            external_ids: Vec::new(),
+            notes: Vec::new(),
+            media: Vec::new(),
+            citations: Vec::new(),
+            aliases: Vec::new(),
+            associations: Vec::new(),
+            restriction: None,
+            user_references: Vec::new(),
+            node: None,
         }
     }
 
+    /// The preferred name of the individual, the first of its `names`
+    #[must_use]
+    pub fn name(&self) -> Option<&Name> {
+        self.names.first()
+    }
+
     pub fn add_family(&mut self, link: FamilyLink) {
         let mut do_add = true;
         let xref = &link.0;
@@ -45,12 +84,12 @@ impl Individual {
     }
 
     pub fn add_custom_data(&mut self, data: CustomData) {
-        self.custom_data.push(data)
+        self.custom_data.push(data);
     }
 }
 
 impl HasEvents for Individual {
-    fn add_event(&mut self, event: Event) -> () {
+    fn add_event(&mut self, event: Event) {
         self.events.push(event);
     }
     fn events(&self) -> Vec<Event> {
@@ -59,65 +98,220 @@ impl HasEvents for Individual {
 }
 
 /// Gender of an `Individual`
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub enum Gender {
     Male,
     Female,
     // come at me LDS, i support "N" as a gender value
     Nonbinary,
+    /// `X`, added by GEDCOM 7: does not fit the typical definition of only male or only female
+    Other,
     Unknown,
 }
 
-#[derive(Debug)]
+/// Whether an `Individual` is a child or a spouse within a linked `Family`
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
-enum FamilyLinkType {
+pub enum FamilyLinkType {
     Spouse,
     Child,
 }
 
-#[derive(Debug)]
+/// Relationship of a child to the family it is linked to, the `PEDI` tag
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
-enum Pedigree {
+pub enum Pedigree {
     Adopted,
     Birth,
     Foster,
     Sealing,
+    /// `OTHER`, added by GEDCOM 7, with the `PHRASE` describing it if there is one
+    Other(Option<String>),
 }
 
-#[derive(Debug)]
+impl FromStr for Pedigree {
+    type Err = ();
+
+    fn from_str(pedigree_text: &str) -> Result<Self, Self::Err> {
+        match pedigree_text.to_lowercase().as_str() {
+            "adopted" => Ok(Pedigree::Adopted),
+            "birth" => Ok(Pedigree::Birth),
+            "foster" => Ok(Pedigree::Foster),
+            "sealing" => Ok(Pedigree::Sealing),
+            "other" => Ok(Pedigree::Other(None)),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct FamilyLink(Xref, FamilyLinkType, Option<Pedigree>);
 
 impl FamilyLink {
     #[must_use]
-    pub fn new(xref: Xref, tag: &str) -> FamilyLink {
-        let link_type = match tag {
-            "FAMC" => FamilyLinkType::Child,
-            "FAMS" => FamilyLinkType::Spouse,
-            _ => panic!("Unrecognized family type tag: {}", tag),
-        };
+    pub fn new(xref: Xref, link_type: FamilyLinkType) -> FamilyLink {
         FamilyLink(xref, link_type, None)
     }
 
-    pub fn set_pedigree(&mut self, pedigree_text: &str) {
-        self.2 = match pedigree_text.to_lowercase().as_str() {
-            "adopted" => Some(Pedigree::Adopted),
-            "birth" => Some(Pedigree::Birth),
-            "foster" => Some(Pedigree::Foster),
-            "sealing" => Some(Pedigree::Sealing),
-            _ => panic!("Unrecognized family link pedigree: {}", pedigree_text),
-        };
+    pub fn set_pedigree(&mut self, pedigree: Pedigree) {
+        self.2 = Some(pedigree);
     }
+
+    /// Reference to the linked `Family`
+    #[must_use]
+    pub fn xref(&self) -> &Xref {
+        &self.0
+    }
+
+    /// Whether the individual is a child or spouse in the linked family
+    #[must_use]
+    pub fn link_type(&self) -> &FamilyLinkType {
+        &self.1
+    }
+
+    /// Pedigree of a child link, if one was given
+    #[must_use]
+    pub fn pedigree(&self) -> Option<&Pedigree> {
+        self.2.as_ref()
+    }
+}
+
+/// A link to someone an `Individual` is related to, _ie._ a godparent, the `ASSO` tag
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Association {
+    /// The xref of the associated individual
+    pub xref: Xref,
+    /// How they are related, from `RELA`, or the `ROLE` and its `PHRASE` of GEDCOM 7
+    pub relation: Option<String>,
+    /// Sources for the association, from `SOUR`
+    pub citations: Vec<SourceCitation>,
+    /// Notes about the association, from `NOTE`
+    pub notes: Vec<NoteStructure>,
 }
 
+/// A name of an `Individual`, the `NAME` tag
+///
+/// The value holds the whole name with the surname between slashes, _ie._
+/// `John /Smith/ Jr.`, while `given`, `surname` and the other pieces are only set when
+/// the file gives them on their own lines. `Name::pieces` falls back to the value for them.
 #[derive(Debug, Default, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Name {
     pub value: Option<String>,
+    /// The kind of name, _ie._ a married name, from `TYPE`
+    pub name_type: Option<NameType>,
     pub given: Option<String>,
     pub surname: Option<String>,
     pub prefix: Option<String>,
     pub surname_prefix: Option<String>,
     pub suffix: Option<String>,
+    /// A familiar name the individual was called by, from `NICK`
+    pub nickname: Option<String>,
+    /// The language of the name, from `LANG` (GEDCOM 7)
+    pub language: Option<String>,
+    /// Notes about the name, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// The sources the name was found in, from `SOUR`
+    pub citations: Vec<SourceCitation>,
+    /// How the name sounds, from `FONE`
+    pub phonetic: Vec<NameVariant>,
+    /// The name in Latin script, from `ROMN`
+    pub romanized: Vec<NameVariant>,
+    /// The name in other languages or scripts, from `TRAN` (GEDCOM 7)
+    pub translations: Vec<Name>,
+}
+
+impl Name {
+    /// The given name, surname and suffix of the name, taken from `GIVN`, `SURN` and `NSFX`
+    /// or, for those the file does not give, from the slashes of the value.
+    ///
+    /// ```rust
+    /// use gedcom::types::Name;
+    ///
+    /// let name = Name {
+    ///     value: Some("John Paul /Smith/ Jr.".to_string()),
+    ///     ..Name::default()
+    /// };
+    /// let pieces = name.pieces();
+    /// assert_eq!(pieces.given, Some("John Paul"));
+    /// assert_eq!(pieces.surname, Some("Smith"));
+    /// assert_eq!(pieces.suffix, Some("Jr."));
+    /// ```
+    #[must_use]
+    pub fn pieces(&self) -> NamePieces<'_> {
+        let mut slashed = self.value.as_deref().unwrap_or_default().splitn(3, '/');
+        let mut piece = || {
+            slashed
+                .next()
+                .map(str::trim)
+                .filter(|piece| !piece.is_empty())
+        };
+        let (given, surname, suffix) = (piece(), piece(), piece());
+        NamePieces {
+            given: self.given.as_deref().or(given),
+            surname: self.surname.as_deref().or(surname),
+            suffix: self.suffix.as_deref().or(suffix),
+        }
+    }
+}
+
+/// The pieces of a `Name`, as given by `Name::pieces`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NamePieces<'n> {
+    /// The given names, the part of the value before the slashes
+    pub given: Option<&'n str>,
+    /// The surname, the part of the value between the slashes
+    pub surname: Option<&'n str>,
+    /// The suffix, the part of the value after the slashes
+    pub suffix: Option<&'n str>,
+}
+
+/// The kind of a `Name`, the `TYPE` of a `NAME`
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum NameType {
+    /// Also known as, an alias
+    Aka,
+    /// The name given at birth
+    Birth,
+    /// A name taken on immigration
+    Immigrant,
+    /// The name before a first marriage
+    Maiden,
+    /// A name taken on marriage
+    Married,
+    /// A name used professionally, added by GEDCOM 7
+    Professional,
+    /// A user-defined type of GEDCOM 5.5.1, or `OTHER` with its `PHRASE` in GEDCOM 7
+    Other(Option<String>),
+}
+
+impl FromStr for NameType {
+    type Err = ();
+
+    fn from_str(name_type_text: &str) -> Result<Self, Self::Err> {
+        match name_type_text.to_lowercase().as_str() {
+            "aka" => Ok(NameType::Aka),
+            "birth" => Ok(NameType::Birth),
+            "immigrant" => Ok(NameType::Immigrant),
+            "maiden" => Ok(NameType::Maiden),
+            "married" => Ok(NameType::Married),
+            "professional" => Ok(NameType::Professional),
+            "other" => Ok(NameType::Other(None)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A phonetic or romanized variation of a `Name`, the `FONE` or `ROMN` of a `NAME`
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct NameVariant {
+    /// The name as written by the variation, with its pieces
+    pub name: Name,
+    /// How the variation was made, _ie._ `kana` or `pinyin`, from `TYPE`
+    pub variant_type: Option<String>,
 }
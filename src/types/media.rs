@@ -0,0 +1,74 @@
+use crate::types::{ExternalId, GedcomDate, GedcomNode, NoteStructure, SourceCitation};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+type Xref = String;
+
+/// Multimedia object, the `OBJE` record, or an object embedded where it is linked
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Media {
+    /// Optional reference to link to this multimedia record
+    pub xref: Option<Xref>,
+    /// The files of the object, from `FILE`
+    pub files: Vec<MediaFile>,
+    /// Numbers the submitter gave the record, from `REFN`
+    pub user_references: Vec<UserReference>,
+    /// The number the system that wrote the file gave the record, from `RIN`
+    pub record_id: Option<String>,
+    /// Notes about the object, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// The sources of the object, from `SOUR`
+    pub citations: Vec<SourceCitation>,
+    /// The date of the last change to the record, from `CHAN.DATE`
+    pub last_updated: Option<GedcomDate>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
+}
+
+impl Media {
+    /// Shorthand for creating a `Media` from its `xref`
+    #[must_use]
+    pub fn new(xref: Option<Xref>) -> Media {
+        Media {
+            xref,
+            ..Media::default()
+        }
+    }
+}
+
+/// A file of a multimedia object, the `FILE` structure
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct MediaFile {
+    /// The path or URL of the file
+    pub reference: String,
+    /// The format of the file, _ie._ `jpg`, or a media type in GEDCOM 7, from `FORM`
+    pub format: Option<String>,
+    /// What the file shows, _ie._ `photo` or `document`, from `FORM.TYPE` or `FORM.MEDI`
+    pub media_type: Option<String>,
+    /// The title of the file, from `TITL`
+    pub title: Option<String>,
+}
+
+/// A link to a multimedia object, the `OBJE` of a record or event
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum MediaLink {
+    /// The xref of a `Media` record, from an `OBJE @M1@`
+    Shared(Xref),
+    /// An object written where it is linked, which GEDCOM 7 no longer allows
+    Embedded(Box<Media>),
+}
+
+/// A number the submitter gave a record, the `REFN` structure
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct UserReference {
+    /// The number
+    pub number: String,
+    /// What kind of number it is, from `TYPE`
+    pub reference_type: Option<String>,
+}
@@ -7,12 +7,26 @@
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
+pub mod date;
+pub use date::{
+    Approximation, Calendar, Chronology, Date, DateValue, DayRange, Duration, GedcomDate,
+};
+mod calendar;
+
+pub mod age;
+pub use age::{Age, AgeBound, AgeDuration, AgeValue};
+
 pub mod event;
 pub use event::{Event, EventType};
 
 mod address;
 pub use address::*;
 
+mod place;
+pub use place::*;
+
+pub use crate::node::GedcomNode;
+
 type Xref = String;
 
 // top-level record types
@@ -28,17 +42,99 @@ pub use family::*;
 mod submitter;
 pub use submitter::*;
 
+mod submission;
+pub use submission::*;
+
 mod source;
 pub use source::*;
 
-// TODO
-/// Multimedia item
-#[derive(Debug)]
+mod media;
+pub use media::*;
+
+/// Shared note, the `NOTE` record, or the `SNOTE` record of GEDCOM 7
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Note {
+    /// Optional reference to link to this note
+    pub xref: Option<Xref>,
+    /// Text of the note, with `CONT` lines joined by newlines
+    pub value: String,
+    /// The media type of the text, _ie._ `text/html`, from `MIME` (GEDCOM 7)
+    pub mime: Option<String>,
+    /// The language of the text, from `LANG` (GEDCOM 7)
+    pub language: Option<String>,
+    /// The text in other languages or formats, from `TRAN` (GEDCOM 7)
+    pub translations: Vec<Translation>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
+}
+
+/// A `NOTE` of a record or structure, either written where it is or shared
+///
+/// `GedcomData::note_text` gives the text of either kind.
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
-pub struct Media {}
+pub enum NoteStructure {
+    /// The text of the note, with `CONT` lines joined by newlines
+    Text(String),
+    /// The xref of a shared `Note` record, from a `NOTE @N1@` or the `SNOTE` of GEDCOM 7
+    Shared(Xref),
+}
+
+/// A translation of a text, the `TRAN` of GEDCOM 7
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Translation {
+    /// The translated text
+    pub value: String,
+    /// The media type of the translated text
+    pub mime: Option<String>,
+    /// The language of the translated text
+    pub language: Option<String>,
+}
+
+/// An identifier of a record in another system, the `EXID` of GEDCOM 7
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ExternalId {
+    /// The identifier
+    pub id: String,
+    /// A URI for the system the identifier belongs to, from `TYPE`
+    pub id_type: Option<String>,
+}
+
+/// A single top-level record of a GEDCOM file, as yielded by `Parser::records`
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+// a file has a single header, so it is not worth boxing
+#[allow(clippy::large_enum_variant)]
+pub enum Record {
+    /// The `HEAD` record
+    Header(Header),
+    /// An `INDI` record
+    Individual(Individual),
+    /// A `FAM` record
+    Family(Family),
+    /// A `SOUR` record
+    Source(Source),
+    /// A `REPO` record
+    Repository(Repository),
+    /// A `SUBM` record
+    Submitter(Submitter),
+    /// A `SUBN` record
+    Submission(Submission),
+    /// A `NOTE` record, or an `SNOTE` record of GEDCOM 7
+    Note(Note),
+    /// An `OBJE` record
+    Media(Media),
+    /// A record the parser does not handle, kept as it was read
+    Custom(GedcomNode),
+}
 
 /// Data repository, the `REPO` tag
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Repository {
     /// Optional reference to link to this repo
@@ -47,20 +143,31 @@ pub struct Repository {
     pub name: Option<String>,
     /// Physical address of the data repository
     pub address: Option<Address>,
+    /// Notes about the repository, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 /// Citation linking a genealogy fact to a data `Source`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct SourceCitation {
     /// Reference to the `Source`
     pub xref: Xref,
     /// Page number of source
     pub page: Option<String>,
+    /// How reliable the evidence is, from `QUAY`: 0 for unreliable up to 3 for direct
+    /// evidence
+    pub quality: Option<String>,
+    /// Notes about the citation, from `NOTE`
+    pub notes: Vec<NoteStructure>,
 }
 
 /// Citation linking a `Source` to a data `Repository`
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct RepoCitation {
     /// Reference to the `Repository`
@@ -69,7 +176,7 @@ pub struct RepoCitation {
     pub call_number: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct CustomData {
     pub tag: String,
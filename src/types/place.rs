@@ -0,0 +1,87 @@
+use crate::types::Translation;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Place at which a fact occurs, the `PLAC` tag
+///
+/// The value names the jurisdictions of the place from the lowest to the highest, separated
+/// by commas. `FORM` names their levels in the same order.
+///
+/// ```rust
+/// use gedcom::types::Place;
+///
+/// let place = Place::new("Salt Lake City, Salt Lake, Utah, USA");
+/// let jurisdictions = place.jurisdictions(Some("City, County, State, Country"));
+/// assert_eq!(jurisdictions[1].name, "Salt Lake");
+/// assert_eq!(jurisdictions[1].level, Some("County"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Place {
+    /// The jurisdictions of the place, separated by commas
+    pub value: String,
+    /// The levels of the jurisdictions, when they differ from `HEAD.PLAC.FORM`
+    pub form: Option<String>,
+    /// The latitude in decimal degrees, negative to the south, from `MAP.LATI`
+    pub latitude: Option<f64>,
+    /// The longitude in decimal degrees, negative to the west, from `MAP.LONG`
+    pub longitude: Option<f64>,
+    /// How the name of the place sounds, from `FONE`
+    pub phonetic: Vec<PlaceVariant>,
+    /// The name of the place in Latin script, from `ROMN`
+    pub romanized: Vec<PlaceVariant>,
+    /// The name of the place in other languages or scripts, from `TRAN` (GEDCOM 7)
+    pub translations: Vec<Translation>,
+}
+
+impl Place {
+    /// A place with the given jurisdictions and nothing else
+    #[must_use]
+    pub fn new(value: &str) -> Place {
+        Place {
+            value: value.to_string(),
+            ..Place::default()
+        }
+    }
+
+    /// The jurisdictions of the place from the lowest to the highest, labelled by the
+    /// levels of its `form` or, when it has none, by `header_form`, the `HEAD.PLAC.FORM`
+    /// of the file. A jurisdiction left empty, as in `Provo, , Utah`, is kept so the others
+    /// keep their levels.
+    #[must_use]
+    pub fn jurisdictions<'p>(&'p self, header_form: Option<&'p str>) -> Vec<Jurisdiction<'p>> {
+        let mut levels = self
+            .form
+            .as_deref()
+            .or(header_form)
+            .map(|form| form.split(',').map(str::trim).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter();
+        self.value
+            .split(',')
+            .map(|name| Jurisdiction {
+                name: name.trim(),
+                level: levels.next().filter(|level| !level.is_empty()),
+            })
+            .collect()
+    }
+}
+
+/// A jurisdiction of a `Place`, _ie._ the county it is in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jurisdiction<'p> {
+    /// The name of the jurisdiction, empty when the place leaves it out
+    pub name: &'p str,
+    /// The level of the jurisdiction, _ie._ `County`, when the form names one
+    pub level: Option<&'p str>,
+}
+
+/// A phonetic or romanized variation of the name of a `Place`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct PlaceVariant {
+    /// The name of the place as written by the variation
+    pub value: String,
+    /// How the variation was made, _ie._ `hangul` or `pinyin`, from `TYPE`
+    pub variant_type: Option<String>,
+}
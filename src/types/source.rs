@@ -1,8 +1,8 @@
-use crate::types::{Event, RepoCitation};
+use crate::types::{Event, ExternalId, GedcomNode, MediaLink, NoteStructure, RepoCitation};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 /// Source for genealogy facts
 pub struct Source {
@@ -10,7 +10,18 @@ pub struct Source {
     pub data: SourceData,
     pub abbreviation: Option<String>,
     pub title: Option<String>,
-    repo_citations: Vec<RepoCitation>,
+    pub author: Option<String>,
+    pub publication: Option<String>,
+    pub text: Option<String>,
+    pub repo_citations: Vec<RepoCitation>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// Notes about the source, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// Scans and other multimedia of the source, from `OBJE`
+    pub media: Vec<MediaLink>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 impl Source {
@@ -24,7 +35,14 @@ impl Source {
             },
             abbreviation: None,
             title: None,
+            author: None,
+            publication: None,
+            text: None,
             repo_citations: Vec::new(),
+            external_ids: Vec::new(),
+            notes: Vec::new(),
+            media: Vec::new(),
+            node: None,
         }
     }
 
@@ -34,7 +52,7 @@ impl Source {
 }
 
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct SourceData {
     events: Vec<Event>,
@@ -45,4 +63,10 @@ impl SourceData {
     pub fn add_event(&mut self, event: Event) {
         self.events.push(event);
     }
+
+    /// The events recorded by the source, from `DATA.EVEN`
+    #[must_use]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
 }
@@ -0,0 +1,43 @@
+use crate::types::{GedcomNode, NoteStructure};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+type Xref = String;
+
+/// A request to the Family History Department for ordinance processing, the `SUBN`
+/// record of GEDCOM 5.5.1. GEDCOM 7 has no such record.
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Submission {
+    /// Optional reference to link to this submission, from the `SUBN` of the header
+    pub xref: Option<Xref>,
+    /// The xref of the `Submitter` sending the file, from `SUBM`
+    pub submitter: Option<Xref>,
+    /// The name of the family file the submission goes into, from `FAMF`
+    pub family_file: Option<String>,
+    /// The code of the temple to take the submission, from `TEMP`
+    pub temple: Option<String>,
+    /// How many generations of ancestors are included, from `ANCE`
+    pub ancestor_generations: Option<u32>,
+    /// How many generations of descendants are included, from `DESC`
+    pub descendant_generations: Option<u32>,
+    /// Whether the records are to be processed for ordinances, from `ORDI`
+    pub ordinance_process: Option<bool>,
+    /// The number the system that wrote the file gave the record, from `RIN`
+    pub record_id: Option<String>,
+    /// Notes about the submission, from `NOTE`
+    pub notes: Vec<NoteStructure>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
+}
+
+impl Submission {
+    /// Shorthand for creating a `Submission` from its `xref`
+    #[must_use]
+    pub fn new(xref: Option<Xref>) -> Submission {
+        Submission {
+            xref,
+            ..Submission::default()
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use crate::types::Address;
+use crate::types::{Address, ExternalId, GedcomNode};
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 type Xref = String;
 
 /// Submitter of the data, ie. who reported the genealogy fact
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Submitter {
     /// Optional reference to link to this submitter
@@ -16,6 +16,10 @@ pub struct Submitter {
     pub address: Option<Address>,
     /// Phone number of the submitter
     pub phone: Option<String>,
+    /// Identifiers of the record in other systems, from `EXID` (GEDCOM 7)
+    pub external_ids: Vec<ExternalId>,
+    /// The lines this record was parsed from, including those not handled by the parser
+    pub node: Option<GedcomNode>,
 }
 
 impl Submitter {
@@ -27,6 +31,8 @@ impl Submitter {
             name: None,
             address: None,
             phone: None,
+            external_ids: Vec::new(),
+            node: None,
         }
     }
 }
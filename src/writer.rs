@@ -0,0 +1,1070 @@
+//! Serialization of GEDCOM data back into GEDCOM 5.5.1 text
+//!
+//! ```rust
+//! use gedcom::writer::GedcomWriter;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+//! let data = gedcom::parse(source.chars());
+//!
+//! let mut output = Vec::new();
+//! GedcomWriter::new(&mut output).write_data(&data).unwrap();
+//!
+//! let text = String::from_utf8(output).unwrap();
+//! assert!(text.starts_with("0 HEAD\n"));
+//! assert!(text.contains("0 @I1@ INDI\n1 NAME John /Doe/\n"));
+//! assert!(text.ends_with("0 TRLR\n"));
+//! ```
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::convert::{Converter, ConverterOptions};
+use crate::lexer::{Lexer, Token};
+use crate::line::LineEnding;
+use crate::node::{is_pointer, GedcomNode};
+use crate::tree::GedcomData;
+use crate::types::{
+    event::HasEvents, Address, Calendar, CustomData, Event, EventType, ExternalId, Family,
+    FamilyLinkType, GedcomVersion, Gender, Header, HeaderSource, Individual, Media, MediaLink,
+    Name, NameType, Note, NoteStructure, Pedigree, Place, Record, RepoCitation, Repository, Source,
+    SourceCitation, Submission, Submitter, UserReference,
+};
+
+/// The longest a line may be, including its terminator
+pub const MAX_LINE_LENGTH: usize = 255;
+
+/// Options controlling how a `GedcomWriter` lays out a file.
+///
+/// `Parser::writer_options` gives the options for writing a parsed file back the way it was
+/// read, so that saving a file without changes leaves it byte for byte the same.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriterOptions {
+    /// The terminator written after each line
+    pub line_ending: LineEnding,
+    /// Whether to start the output with a byte order mark
+    pub byte_order_mark: bool,
+    /// Whether to end the `TRLR` line with a terminator too
+    pub final_line_ending: bool,
+    /// Whether to write the header of the data when it is unchanged since it was read,
+    /// rather than generating one. Its `CHAR` is set to `UTF-8` either way.
+    pub keep_header: bool,
+    /// The version of the specification the lines are written for. GEDCOM 7 has no limit
+    /// on the length of a line, so long values are only split over `CONC` lines for
+    /// GEDCOM 5.5.1. Use `convert::Converter` to change the structures of the records too.
+    pub version: GedcomVersion,
+}
+
+impl Default for WriterOptions {
+    fn default() -> Self {
+        WriterOptions {
+            line_ending: LineEnding::Lf,
+            byte_order_mark: false,
+            final_line_ending: true,
+            keep_header: false,
+            version: GedcomVersion::default(),
+        }
+    }
+}
+
+/// Writes GEDCOM records as UTF-8 text.
+///
+/// A record that still holds what was parsed from its `node` is written from that node,
+/// so its unknown tags and the order of its lines are kept, and each line comes out as it
+/// was read. Other records are written from their typed data, keeping the order of the lines
+/// of their original node where the tags match, but losing anything the typed data does not
+/// hold. New values are escaped and split
+/// over `CONT` lines at newlines and over `CONC` lines where they would make a line longer
+/// than `MAX_LINE_LENGTH`. Levels are written from the depth of each node in its record,
+/// whatever their `level` field holds.
+///
+/// Records of a GEDCOM 7 file are kept the same way. Edited ones are converted to GEDCOM 7
+/// structures by `write_data`, but written as GEDCOM 5.5.1 structures by `write_record`.
+pub struct GedcomWriter<W: Write> {
+    out: W,
+    options: WriterOptions,
+    /// Whether anything has been written yet, so the byte order mark can go first
+    started: bool,
+}
+
+impl<W: Write> GedcomWriter<W> {
+    /// Creates a writer of GEDCOM text to `out`
+    pub fn new(out: W) -> GedcomWriter<W> {
+        GedcomWriter::with_options(out, WriterOptions::default())
+    }
+
+    /// Creates a writer of GEDCOM text to `out` that follows the given `WriterOptions`
+    pub fn with_options(out: W, options: WriterOptions) -> GedcomWriter<W> {
+        GedcomWriter {
+            out,
+            options,
+            started: false,
+        }
+    }
+
+    /// Returns the underlying output
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Writes a whole file: the header, the records of `data`, and the trailer.
+    ///
+    /// Records read from a file are written in the order they were read, followed by new
+    /// records grouped by type. When the header of `data` says it follows GEDCOM 7, the
+    /// records built from typed data are converted to GEDCOM 7 structures to match.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to the output.
+    pub fn write_data(&mut self, data: &GedcomData) -> io::Result<()> {
+        if data.header.version() == GedcomVersion::V7 {
+            self.options.version = GedcomVersion::V7;
+            let options = ConverterOptions {
+                writer: self.options.clone(),
+                extension_base_uri: None,
+            };
+            let records = Converter::with_options(GedcomVersion::V7, options).convert(data);
+            for node in &records {
+                self.write_node(node)?;
+            }
+        } else {
+            for node in self.record_nodes(data) {
+                self.write_node(&node)?;
+            }
+        }
+        self.write_trailer()
+    }
+
+    /// The nodes `write_data` writes for `data`, the header first. A borrowed node is the
+    /// one its record was read from, an owned one was built for it.
+    pub(crate) fn record_nodes<'d>(&mut self, data: &'d GedcomData) -> Vec<Cow<'d, GedcomNode>> {
+        let header = self.header(&data.header);
+
+        let mut records = Vec::new();
+        records.extend(data.submitters.iter().map(|r| placed_node(r)));
+        records.extend(data.submissions.iter().map(|r| placed_node(r)));
+        records.extend(data.individuals.iter().map(|r| placed_node(r)));
+        records.extend(data.families.iter().map(|r| placed_node(r)));
+        records.extend(data.sources.iter().map(|r| placed_node(r)));
+        records.extend(data.repositories.iter().map(|r| placed_node(r)));
+        records.extend(data.notes.iter().map(|r| placed_node(r)));
+        records.extend(data.multimedia.iter().map(|r| placed_node(r)));
+        records.extend(
+            data.custom_data
+                .iter()
+                .map(|node| (place(Some(node)), Cow::Borrowed(node))),
+        );
+
+        records.sort_by_key(|(place, _)| *place);
+        std::iter::once(header)
+            .chain(records.into_iter().map(|(_, node)| node))
+            .collect()
+    }
+
+    /// Writes a single top-level record. A `Record::Header` is written as the header
+    /// of a file, the same as `write_data` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to the output.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let node = match record {
+            Record::Header(header) => self.header(header),
+            Record::Individual(individual) => record_node(individual),
+            Record::Family(family) => record_node(family),
+            Record::Source(source) => record_node(source),
+            Record::Repository(repo) => record_node(repo),
+            Record::Submitter(submitter) => record_node(submitter),
+            Record::Submission(submission) => record_node(submission),
+            Record::Note(note) => record_node(note),
+            Record::Media(media) => record_node(media),
+            Record::Custom(node) => Cow::Borrowed(node),
+        };
+        self.write_node(&node)
+    }
+
+    /// Writes `node` and its descendants as a top-level record. Values are written
+    /// as they are, without escaping.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to the output.
+    pub fn write_node(&mut self, node: &GedcomNode) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            if self.options.byte_order_mark {
+                write!(self.out, "\u{feff}")?;
+            }
+        }
+        self.write_subtree(node, 0)
+    }
+
+    /// Writes the `TRLR` line ending a file
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to the output.
+    pub fn write_trailer(&mut self) -> io::Result<()> {
+        write!(self.out, "0 TRLR")?;
+        if self.options.final_line_ending {
+            write!(self.out, "{}", self.options.line_ending.as_str())?;
+        }
+        Ok(())
+    }
+
+    /// The node to write for the header: the one it was read from when the options say
+    /// to keep it and it is unchanged, or a generated one
+    fn header<'h>(&mut self, header: &'h Header) -> Cow<'h, GedcomNode> {
+        if !self.options.keep_header {
+            return Cow::Owned(header_node(header));
+        }
+        let Some(node) = original(header) else {
+            return Cow::Owned(header_node(header));
+        };
+        // the output is always UTF-8, whatever the input was
+        let charset = node
+            .child("CHAR")
+            .and_then(|charset| charset.value.as_deref());
+        if charset.is_none_or(|charset| charset == "UTF-8") {
+            return Cow::Borrowed(node);
+        }
+        let mut node = node.clone();
+        if let Some(charset) = node.children.iter_mut().find(|child| child.tag == "CHAR") {
+            charset.value = Some("UTF-8".to_string());
+            charset.children.clear();
+        }
+        Cow::Owned(node)
+    }
+
+    fn write_subtree(&mut self, node: &GedcomNode, level: u8) -> io::Result<()> {
+        let mut prefix = level.to_string();
+        if let Some(xref) = &node.xref {
+            prefix.push(' ');
+            prefix.push_str(xref);
+        }
+        prefix.push(' ');
+        prefix.push_str(&node.tag);
+
+        match &node.value {
+            // a line read from a file is written as it was read
+            Some(value) if node.line != 0 && !value.contains('\n') => {
+                match node
+                    .raw
+                    .as_deref()
+                    .filter(|raw| same_line(raw, node, level))
+                {
+                    Some(raw) => self.end_line(raw)?,
+                    None => self.end_line(&format!("{prefix} {value}"))?,
+                }
+            }
+            Some(value) => {
+                let mut lines = value.split('\n').map(|line| line.trim_end_matches('\r'));
+                let first = lines.next().unwrap_or_default();
+                let continued = format!("{} ", level + 1);
+                self.write_line(&prefix, first, &format!("{continued}CONC"))?;
+                for line in lines {
+                    self.write_line(
+                        &format!("{continued}CONT"),
+                        line,
+                        &format!("{continued}CONC"),
+                    )?;
+                }
+            }
+            None => match node
+                .raw
+                .as_deref()
+                .filter(|raw| same_line(raw, node, level))
+            {
+                Some(raw) => self.end_line(raw)?,
+                None => self.end_line(&prefix)?,
+            },
+        }
+
+        for child in &node.children {
+            self.write_subtree(child, level + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` on a line starting with `prefix`, carrying whatever does not fit
+    /// over to lines starting with `concatenation`
+    fn write_line(&mut self, prefix: &str, value: &str, concatenation: &str) -> io::Result<()> {
+        if value.is_empty() {
+            return self.end_line(prefix);
+        }
+        if self.options.version == GedcomVersion::V7 {
+            return self.end_line(&format!("{prefix} {value}"));
+        }
+        let terminator = self.options.line_ending.as_str().len();
+        let mut prefix = prefix;
+        let mut rest = value;
+        loop {
+            // the delimiter before the value, and the terminator
+            let room = MAX_LINE_LENGTH.saturating_sub(prefix.chars().count() + 1 + terminator);
+            let end = split_point(rest, room);
+            self.end_line(&format!("{} {}", prefix, &rest[..end]))?;
+            rest = &rest[end..];
+            if rest.is_empty() {
+                return Ok(());
+            }
+            prefix = concatenation;
+        }
+    }
+
+    /// Writes `line` followed by the line terminator
+    fn end_line(&mut self, line: &str) -> io::Result<()> {
+        write!(self.out, "{}{}", line, self.options.line_ending.as_str())
+    }
+}
+
+/// The node to write for a record: the one it was read from if it is unchanged,
+/// otherwise one built from its typed data
+fn record_node<T: TypedRecord>(record: &T) -> Cow<'_, GedcomNode> {
+    let mut node = record.to_node();
+    let Some(original) = record.node() else {
+        return Cow::Owned(node);
+    };
+    if original.digest == Some(digest(&node)) {
+        return Cow::Borrowed(original);
+    }
+    follow_order(&mut node, original);
+    Cow::Owned(node)
+}
+
+/// The node to write for a record, along with where it goes in the file
+fn placed_node<T: TypedRecord>(record: &T) -> (u32, Cow<'_, GedcomNode>) {
+    (place(record.node()), record_node(record))
+}
+
+/// The node a record was parsed from, as long as its typed data is unchanged since
+fn original<T: TypedRecord>(record: &T) -> Option<&GedcomNode> {
+    let node = record.node()?;
+    (node.digest == Some(digest(&record.to_node()))).then_some(node)
+}
+
+/// A digest of the lines of `node`
+fn digest(node: &GedcomNode) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Keeps a digest of what the typed data of a record just parsed writes on the node it
+/// was parsed from, which `GedcomWriter` compares against to tell whether it was edited
+pub(crate) fn seal(record: &mut Record) {
+    match record {
+        Record::Header(header) => seal_typed(header),
+        Record::Individual(individual) => seal_typed(individual),
+        Record::Family(family) => seal_typed(family),
+        Record::Source(source) => seal_typed(source),
+        Record::Repository(repo) => seal_typed(repo),
+        Record::Submitter(submitter) => seal_typed(submitter),
+        Record::Submission(submission) => seal_typed(submission),
+        Record::Note(note) => seal_typed(note),
+        Record::Media(media) => seal_typed(media),
+        Record::Custom(_) => {}
+    }
+}
+
+fn seal_typed<T: TypedRecord>(record: &mut T) {
+    let digest = digest(&record.to_node());
+    if let Some(node) = record.node_mut() {
+        node.digest = Some(digest);
+    }
+}
+
+/// Puts the children of `node` in the order of the children of `original` with the same
+/// tags, so that an edited record keeps the layout it was read with
+fn follow_order(node: &mut GedcomNode, original: &GedcomNode) {
+    node.children.sort_by_key(|child| {
+        original
+            .children
+            .iter()
+            .position(|other| other.tag == child.tag)
+            .unwrap_or(usize::MAX)
+    });
+    // pair up the children with each tag in turn
+    let mut originals = original.children.iter().collect::<Vec<_>>();
+    for child in &mut node.children {
+        if let Some(i) = originals.iter().position(|other| other.tag == child.tag) {
+            follow_order(child, originals.remove(i));
+        }
+    }
+}
+
+/// Where a record goes in a file: where it was read from, or after the records read
+/// from the file if it is new
+fn place(node: Option<&GedcomNode>) -> u32 {
+    match node {
+        Some(node) if node.line != 0 => node.line,
+        _ => u32::MAX,
+    }
+}
+
+/// A top-level record with a typed representation
+trait TypedRecord {
+    /// The node the record was parsed from
+    fn node(&self) -> Option<&GedcomNode>;
+    /// The node the record was parsed from, to keep its digest on
+    fn node_mut(&mut self) -> Option<&mut GedcomNode>;
+    /// Builds a node from the typed data
+    fn to_node(&self) -> GedcomNode;
+}
+
+macro_rules! typed_record {
+    ($type:ident, $to_node:ident) => {
+        impl TypedRecord for $type {
+            fn node(&self) -> Option<&GedcomNode> {
+                self.node.as_ref()
+            }
+
+            fn node_mut(&mut self) -> Option<&mut GedcomNode> {
+                self.node.as_mut()
+            }
+
+            fn to_node(&self) -> GedcomNode {
+                $to_node(self)
+            }
+        }
+    };
+}
+
+// the date and time a header is written at are left out, so they do not count as edits
+typed_record!(Header, header_contents);
+typed_record!(Individual, individual_node);
+typed_record!(Family, family_node);
+typed_record!(Source, source_node);
+typed_record!(Repository, repository_node);
+typed_record!(Submitter, submitter_node);
+typed_record!(Submission, submission_node);
+typed_record!(Note, note_node);
+typed_record!(Media, media_node);
+
+impl GedcomData {
+    /// Writes the data as a file with a generated header, see `GedcomWriter`. The file
+    /// follows GEDCOM 7 when the header of the data says so, and GEDCOM 5.5.1 otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `out`.
+    pub fn write_to(&self, out: impl Write) -> io::Result<()> {
+        GedcomWriter::new(out).write_data(self)
+    }
+}
+
+/// Whether `raw` is still the line for `node` at `level`, told apart only by its whitespace
+fn same_line(raw: &str, node: &GedcomNode, level: u8) -> bool {
+    let mut tokens = Lexer::new(raw).map(|token| token.map(|spanned| spanned.token));
+    let mut next = || tokens.next().and_then(Result::ok);
+    if next() != Some(Token::Level(level)) {
+        return false;
+    }
+    let mut token = next();
+    if let Some(xref) = &node.xref {
+        if token != Some(Token::Pointer(xref)) {
+            return false;
+        }
+        token = next();
+    }
+    if !matches!(token, Some(Token::Tag(tag) | Token::CustomTag(tag)) if tag == node.tag) {
+        return false;
+    }
+    token = next();
+    if let Some(value) = &node.value {
+        if token != Some(Token::LineValue(value)) {
+            return false;
+        }
+        token = next();
+    }
+    token.is_none()
+}
+
+/// The byte offset to end the part of `value` written on a line with room for `room`
+/// characters. Readers drop the spaces around a value, and `@@` stands for a single `@`,
+/// so the value is not split next to a space or between two `@`s where it can be avoided.
+fn split_point(value: &str, room: usize) -> usize {
+    let room = room.max(1);
+    let chars: Vec<(usize, char)> = value.char_indices().take(room + 1).collect();
+    if chars.len() <= room {
+        return value.len();
+    }
+    (1..room)
+        .rev()
+        .find(|&i| {
+            let (before, after) = (chars[i - 1].1, chars[i].1);
+            before != ' ' && after != ' ' && !(before == '@' && after == '@')
+        })
+        .map_or(chars[room].0, |i| chars[i].0)
+}
+
+/// Escapes the `@` signs of a text value as `@@`. Pointers and escape sequences, like
+/// the `@#DJULIAN@` of a date, are kept as they are wherever they appear.
+pub(crate) fn escape(value: &str) -> String {
+    if is_pointer(value) {
+        return value.to_string();
+    }
+    let mut escaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(at) = rest.find('@') {
+        escaped.push_str(&rest[..at]);
+        rest = &rest[at..];
+        // the length of an escape sequence, or of an `@` sign to double
+        let length = rest
+            .strip_prefix("@#")
+            .and_then(|after| after.find('@'))
+            .map_or(1, |end| end + 3);
+        if length == 1 {
+            escaped.push('@');
+        }
+        escaped.push_str(&rest[..length]);
+        rest = &rest[length..];
+    }
+    escaped.push_str(rest);
+    escaped
+}
+
+/// Adds a child for a text value, if there is one
+fn push_text(node: &mut GedcomNode, tag: &str, value: Option<&String>) {
+    if let Some(value) = value {
+        node.push_child(tag, Some(escape(value)));
+    }
+}
+
+fn record(tag: &str, xref: Option<&String>) -> GedcomNode {
+    GedcomNode {
+        xref: xref.cloned(),
+        ..GedcomNode::new(0, tag)
+    }
+}
+
+/// The header of a file written by this crate, keeping the facts of `header` that
+/// describe the contents rather than the file. The program that wrote the file it was
+/// read from is kept too, as the data still comes from it.
+fn header_node(header: &Header) -> GedcomNode {
+    let mut node = header_contents(header);
+    let (date, time) = now();
+    let mut date = GedcomNode {
+        value: Some(date),
+        ..GedcomNode::new(1, "DATE")
+    };
+    date.push_child("TIME", Some(time));
+    // after the `SOUR` and each `DEST`
+    node.children.insert(1 + header.destinations.len(), date);
+    node
+}
+
+/// The header `header_node` writes, without the date and time it is written at
+fn header_contents(header: &Header) -> GedcomNode {
+    let mut node = GedcomNode::new(0, "HEAD");
+
+    if let Some(source) = &header.source {
+        push_header_source(&mut node, source);
+    } else {
+        let source = node.push_child("SOUR", Some(env!("CARGO_PKG_NAME").to_uppercase()));
+        source.push_child("VERS", Some(env!("CARGO_PKG_VERSION").to_string()));
+        source.push_child("NAME", Some("rust-gedcom".to_string()));
+    }
+
+    for destination in &header.destinations {
+        push_text(&mut node, "DEST", Some(destination));
+    }
+    push_text(&mut node, "SUBM", header.submitter_tag.as_ref());
+    push_text(&mut node, "SUBN", header.submission_tag.as_ref());
+    push_text(&mut node, "FILE", header.filename.as_ref());
+    push_text(&mut node, "COPR", header.copyright.as_ref());
+
+    let gedcom = node.push_child("GEDC", None);
+    gedcom.push_child("VERS", Some("5.5.1".to_string()));
+    gedcom.push_child("FORM", Some("LINEAGE-LINKED".to_string()));
+
+    let charset = node.push_child("CHAR", Some("UTF-8".to_string()));
+    // the version of another character set does not apply to the UTF-8 written
+    if header
+        .encoding
+        .as_deref()
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("UTF-8"))
+    {
+        push_text(charset, "VERS", header.encoding_version.as_ref());
+    }
+    push_text(&mut node, "LANG", header.language.as_ref());
+    if let Some(form) = &header.place_form {
+        node.push_child("PLAC", None)
+            .push_child("FORM", Some(escape(form)));
+    }
+    push_text(&mut node, "NOTE", header.note.as_ref());
+    if !header.extension_tags.is_empty() {
+        let schema = node.push_child("SCHMA", None);
+        for extension in &header.extension_tags {
+            schema.push_child("TAG", Some(format!("{} {}", extension.tag, extension.uri)));
+        }
+    }
+    node
+}
+
+/// Adds the `SOUR` of a header, the program that wrote the file
+fn push_header_source(node: &mut GedcomNode, source: &HeaderSource) {
+    let node = node.push_child("SOUR", Some(escape(&source.system_id)));
+    push_text(node, "VERS", source.version.as_ref());
+    push_text(node, "NAME", source.name.as_ref());
+    if let Some(corporation) = &source.corporation {
+        let node = node.push_child("CORP", Some(escape(&corporation.name)));
+        if let Some(address) = &corporation.address {
+            push_address(node, address);
+        }
+        for (tag, values) in [
+            ("PHON", &corporation.phones),
+            ("EMAIL", &corporation.emails),
+            ("FAX", &corporation.faxes),
+            ("WWW", &corporation.websites),
+        ] {
+            for value in values {
+                push_text(node, tag, Some(value));
+            }
+        }
+    }
+    if let Some(data) = &source.data {
+        let node = node.push_child("DATA", Some(escape(&data.name)));
+        if let Some(date) = &data.date {
+            let date = node.push_child("DATE", Some(escape(&date.original)));
+            push_text(date, "TIME", data.time.as_ref());
+        }
+        push_text(node, "COPR", data.copyright.as_ref());
+    }
+}
+
+fn submitter_node(submitter: &Submitter) -> GedcomNode {
+    let mut node = record("SUBM", submitter.xref.as_ref());
+    push_text(&mut node, "NAME", submitter.name.as_ref());
+    if let Some(address) = &submitter.address {
+        push_address(&mut node, address);
+    }
+    push_text(&mut node, "PHON", submitter.phone.as_ref());
+    push_external_ids(&mut node, &submitter.external_ids);
+    node
+}
+
+fn submission_node(submission: &Submission) -> GedcomNode {
+    let mut node = record("SUBN", submission.xref.as_ref());
+    push_text(&mut node, "SUBM", submission.submitter.as_ref());
+    push_text(&mut node, "FAMF", submission.family_file.as_ref());
+    push_text(&mut node, "TEMP", submission.temple.as_ref());
+    for (tag, generations) in [
+        ("ANCE", submission.ancestor_generations),
+        ("DESC", submission.descendant_generations),
+    ] {
+        if let Some(generations) = generations {
+            node.push_child(tag, Some(generations.to_string()));
+        }
+    }
+    if let Some(process) = submission.ordinance_process {
+        let flag = if process { "yes" } else { "no" };
+        node.push_child("ORDI", Some(flag.to_string()));
+    }
+    push_text(&mut node, "RIN", submission.record_id.as_ref());
+    push_notes(&mut node, &submission.notes);
+    node
+}
+
+fn individual_node(individual: &Individual) -> GedcomNode {
+    let mut node = record("INDI", individual.xref.as_ref());
+    push_text(&mut node, "RESN", individual.restriction.as_ref());
+    for name in &individual.names {
+        push_name(&mut node, name);
+    }
+    // an unknown sex is left out, unless the record it was parsed from said so
+    let had_sex = individual
+        .node
+        .as_ref()
+        .is_some_and(|parsed| parsed.child("SEX").is_some());
+    let sex = match individual.sex {
+        Gender::Male => Some("M"),
+        Gender::Female => Some("F"),
+        Gender::Nonbinary => Some("N"),
+        Gender::Other => Some("X"),
+        Gender::Unknown => had_sex.then_some("U"),
+    };
+    if let Some(sex) = sex {
+        node.push_child("SEX", Some(sex.to_string()));
+    }
+    for event in individual.events() {
+        push_event(&mut node, &event);
+    }
+    for link in &individual.families {
+        let tag = match link.link_type() {
+            FamilyLinkType::Child => "FAMC",
+            FamilyLinkType::Spouse => "FAMS",
+        };
+        let family = node.push_child(tag, Some(link.xref().clone()));
+        if let Some(pedigree) = link.pedigree() {
+            let (pedigree, phrase) = match pedigree {
+                Pedigree::Adopted => ("adopted", None),
+                Pedigree::Birth => ("birth", None),
+                Pedigree::Foster => ("foster", None),
+                Pedigree::Sealing => ("sealing", None),
+                Pedigree::Other(phrase) => ("other", phrase.as_ref()),
+            };
+            let pedigree = family.push_child("PEDI", Some(pedigree.to_string()));
+            push_text(pedigree, "PHRASE", phrase);
+        }
+    }
+    for association in &individual.associations {
+        let asso = node.push_child("ASSO", Some(association.xref.clone()));
+        push_text(asso, "RELA", association.relation.as_ref());
+        for citation in &association.citations {
+            push_citation(asso, citation);
+        }
+        push_notes(asso, &association.notes);
+    }
+    for alias in &individual.aliases {
+        node.push_child("ALIA", Some(alias.clone()));
+    }
+    push_user_references(&mut node, &individual.user_references);
+    push_notes(&mut node, &individual.notes);
+    for citation in &individual.citations {
+        push_citation(&mut node, citation);
+    }
+    push_media_links(&mut node, &individual.media);
+    push_custom_data(&mut node, &individual.custom_data);
+    push_external_ids(&mut node, &individual.external_ids);
+    if let Some(date) = &individual.last_updated {
+        node.push_child("CHAN", None)
+            .push_child("DATE", Some(escape(&date.original)));
+    }
+    node
+}
+
+fn family_node(family: &Family) -> GedcomNode {
+    let mut node = record("FAM", family.xref.as_ref());
+    push_text(&mut node, "HUSB", family.individual1.as_ref());
+    push_text(&mut node, "WIFE", family.individual2.as_ref());
+    for child in &family.children {
+        push_text(&mut node, "CHIL", Some(child));
+    }
+    if let Some(count) = family.num_children {
+        node.push_child("NCHI", Some(count.to_string()));
+    }
+    for event in family.events() {
+        push_event(&mut node, &event);
+    }
+    push_notes(&mut node, &family.notes);
+    push_media_links(&mut node, &family.media);
+    push_custom_data(&mut node, &family.custom_data);
+    push_external_ids(&mut node, &family.external_ids);
+    node
+}
+
+fn source_node(source: &Source) -> GedcomNode {
+    let mut node = record("SOUR", source.xref.as_ref());
+    if !source.data.events().is_empty() || source.data.agency.is_some() {
+        let data = node.push_child("DATA", None);
+        for event in source.data.events() {
+            push_event(data, event);
+        }
+        push_text(data, "AGNC", source.data.agency.as_ref());
+    }
+    push_text(&mut node, "AUTH", source.author.as_ref());
+    push_text(&mut node, "TITL", source.title.as_ref());
+    push_text(&mut node, "ABBR", source.abbreviation.as_ref());
+    push_text(&mut node, "PUBL", source.publication.as_ref());
+    push_text(&mut node, "TEXT", source.text.as_ref());
+    for citation in &source.repo_citations {
+        push_repo_citation(&mut node, citation);
+    }
+    push_notes(&mut node, &source.notes);
+    push_media_links(&mut node, &source.media);
+    push_external_ids(&mut node, &source.external_ids);
+    node
+}
+
+fn repository_node(repo: &Repository) -> GedcomNode {
+    let mut node = record("REPO", repo.xref.as_ref());
+    push_text(&mut node, "NAME", repo.name.as_ref());
+    if let Some(address) = &repo.address {
+        push_address(&mut node, address);
+    }
+    push_notes(&mut node, &repo.notes);
+    push_external_ids(&mut node, &repo.external_ids);
+    node
+}
+
+fn note_node(shared: &Note) -> GedcomNode {
+    let mut node = GedcomNode {
+        xref: shared.xref.clone(),
+        value: Some(escape(&shared.value)),
+        ..GedcomNode::new(0, "NOTE")
+    };
+    push_text(&mut node, "MIME", shared.mime.as_ref());
+    push_text(&mut node, "LANG", shared.language.as_ref());
+    for translation in &shared.translations {
+        let tran = node.push_child("TRAN", Some(escape(&translation.value)));
+        push_text(tran, "MIME", translation.mime.as_ref());
+        push_text(tran, "LANG", translation.language.as_ref());
+    }
+    push_external_ids(&mut node, &shared.external_ids);
+    node
+}
+
+fn media_node(media: &Media) -> GedcomNode {
+    let mut node = record("OBJE", media.xref.as_ref());
+    push_media_object(&mut node, media, false);
+    node
+}
+
+fn push_media_links(node: &mut GedcomNode, links: &[MediaLink]) {
+    for link in links {
+        match link {
+            MediaLink::Shared(xref) => {
+                node.push_child("OBJE", Some(xref.clone()));
+            }
+            MediaLink::Embedded(media) => {
+                let object = node.push_child("OBJE", None);
+                push_media_object(object, media, true);
+            }
+        }
+    }
+}
+
+/// Adds the substructures of `media`. GEDCOM 5.5.1 writes the title of an embedded
+/// object next to its file rather than under it, and its type as `MEDI` over `TYPE`.
+fn push_media_object(node: &mut GedcomNode, media: &Media, embedded: bool) {
+    for (index, file) in media.files.iter().enumerate() {
+        let file_node = node.push_child("FILE", Some(escape(&file.reference)));
+        if let Some(format) = &file.format {
+            let form = file_node.push_child("FORM", Some(escape(format)));
+            let type_tag = if embedded { "MEDI" } else { "TYPE" };
+            push_text(form, type_tag, file.media_type.as_ref());
+        }
+        if !embedded || index > 0 {
+            push_text(file_node, "TITL", file.title.as_ref());
+        }
+    }
+    if embedded {
+        let title = media.files.first().and_then(|file| file.title.as_ref());
+        push_text(node, "TITL", title);
+    }
+    push_user_references(node, &media.user_references);
+    push_text(node, "RIN", media.record_id.as_ref());
+    push_notes(node, &media.notes);
+    for citation in &media.citations {
+        push_citation(node, citation);
+    }
+    push_external_ids(node, &media.external_ids);
+    if let Some(date) = &media.last_updated {
+        node.push_child("CHAN", None)
+            .push_child("DATE", Some(escape(&date.original)));
+    }
+}
+
+fn push_name(node: &mut GedcomNode, name: &Name) {
+    push_name_as(node, "NAME", name);
+}
+
+fn push_name_as(node: &mut GedcomNode, tag: &str, name: &Name) {
+    let node = node.push_child(tag, name.value.as_deref().map(escape));
+    if let Some(name_type) = &name.name_type {
+        let name_type = match name_type {
+            NameType::Aka => "aka",
+            NameType::Birth => "birth",
+            NameType::Immigrant => "immigrant",
+            NameType::Maiden => "maiden",
+            NameType::Married => "married",
+            NameType::Professional => "professional",
+            // GEDCOM 5.5.1 takes the description as the type itself
+            NameType::Other(Some(description)) => description,
+            NameType::Other(None) => "other",
+        };
+        node.push_child("TYPE", Some(escape(name_type)));
+    }
+    push_name_pieces(node, name);
+    push_text(node, "LANG", name.language.as_ref());
+    for (tag, variants) in [("FONE", &name.phonetic), ("ROMN", &name.romanized)] {
+        for variant in variants {
+            let variant_node = node.push_child(tag, variant.name.value.as_deref().map(escape));
+            push_text(variant_node, "TYPE", variant.variant_type.as_ref());
+            push_name_pieces(variant_node, &variant.name);
+        }
+    }
+    for translation in &name.translations {
+        push_name_as(node, "TRAN", translation);
+    }
+}
+
+fn push_name_pieces(node: &mut GedcomNode, name: &Name) {
+    push_text(node, "NPFX", name.prefix.as_ref());
+    push_text(node, "GIVN", name.given.as_ref());
+    push_text(node, "NICK", name.nickname.as_ref());
+    push_text(node, "SPFX", name.surname_prefix.as_ref());
+    push_text(node, "SURN", name.surname.as_ref());
+    push_text(node, "NSFX", name.suffix.as_ref());
+    push_notes(node, &name.notes);
+    for citation in &name.citations {
+        push_citation(node, citation);
+    }
+}
+
+fn push_event(node: &mut GedcomNode, event: &Event) {
+    let value = match &event.event {
+        EventType::SourceData(events) => Some(events),
+        _ => event.value.as_ref(),
+    };
+    let node = node.push_child(event.event.tag(), value.map(|value| escape(value)));
+    for (tag, age) in [("HUSB", &event.husband_age), ("WIFE", &event.wife_age)] {
+        if let Some(age) = age {
+            let spouse = node.push_child(tag, None);
+            spouse.push_child("AGE", Some(escape(&age.original)));
+        }
+    }
+    match &event.event {
+        EventType::Custom(event_type) if event.event.tag() == "EVEN" => {
+            node.push_child("TYPE", Some(escape(event_type)));
+        }
+        _ => push_text(node, "TYPE", event.classification.as_ref()),
+    }
+    if let Some(date) = &event.date {
+        let date = node.push_child("DATE", Some(escape(&date.original)));
+        push_text(date, "PHRASE", event.date_phrase.as_ref());
+    }
+    if let Some(place) = &event.place {
+        push_place(node, place);
+    }
+    if let Some(address) = &event.address {
+        push_address(node, address);
+    }
+    for (tag, values) in [
+        ("PHON", &event.phones),
+        ("EMAIL", &event.emails),
+        ("FAX", &event.faxes),
+        ("WWW", &event.websites),
+    ] {
+        for value in values {
+            push_text(node, tag, Some(value));
+        }
+    }
+    push_text(node, "AGNC", event.agency.as_ref());
+    push_text(node, "RELI", event.religion.as_ref());
+    push_text(node, "CAUS", event.cause.as_ref());
+    push_text(node, "RESN", event.restriction.as_ref());
+    push_notes(node, &event.notes);
+    for citation in &event.citations {
+        push_citation(node, citation);
+    }
+    push_media_links(node, &event.media);
+    if let Some(age) = &event.age {
+        node.push_child("AGE", Some(escape(&age.original)));
+    }
+}
+
+fn push_place(node: &mut GedcomNode, place: &Place) {
+    let node = node.push_child("PLAC", Some(escape(&place.value)));
+    push_text(node, "FORM", place.form.as_ref());
+    for (tag, variants) in [("FONE", &place.phonetic), ("ROMN", &place.romanized)] {
+        for variant in variants {
+            let variant_node = node.push_child(tag, Some(escape(&variant.value)));
+            push_text(variant_node, "TYPE", variant.variant_type.as_ref());
+        }
+    }
+    for translation in &place.translations {
+        let tran = node.push_child("TRAN", Some(escape(&translation.value)));
+        push_text(tran, "LANG", translation.language.as_ref());
+    }
+    if place.latitude.is_some() || place.longitude.is_some() {
+        let map = node.push_child("MAP", None);
+        if let Some(latitude) = place.latitude {
+            map.push_child("LATI", Some(coordinate(latitude, 'N', 'S')));
+        }
+        if let Some(longitude) = place.longitude {
+            map.push_child("LONG", Some(coordinate(longitude, 'E', 'W')));
+        }
+    }
+}
+
+/// A `LATI` or `LONG` value, _ie._ `N18.150944` for a latitude of 18.150944
+fn coordinate(degrees: f64, positive: char, negative: char) -> String {
+    let direction = if degrees < 0.0 { negative } else { positive };
+    format!("{direction}{}", degrees.abs())
+}
+
+fn push_citation(node: &mut GedcomNode, citation: &SourceCitation) {
+    let node = node.push_child("SOUR", Some(citation.xref.clone()));
+    push_text(node, "PAGE", citation.page.as_ref());
+    push_text(node, "QUAY", citation.quality.as_ref());
+    push_notes(node, &citation.notes);
+}
+
+fn push_user_references(node: &mut GedcomNode, references: &[UserReference]) {
+    for reference in references {
+        let refn = node.push_child("REFN", Some(escape(&reference.number)));
+        push_text(refn, "TYPE", reference.reference_type.as_ref());
+    }
+}
+
+/// Adds a `NOTE` for each of `notes`, with its text or the xref of its note record
+fn push_notes(node: &mut GedcomNode, notes: &[NoteStructure]) {
+    for note in notes {
+        match note {
+            NoteStructure::Text(text) => push_text(node, "NOTE", Some(text)),
+            NoteStructure::Shared(xref) => {
+                node.push_child("NOTE", Some(xref.clone()));
+            }
+        }
+    }
+}
+
+fn push_repo_citation(node: &mut GedcomNode, citation: &RepoCitation) {
+    let node = node.push_child("REPO", Some(citation.xref.clone()));
+    push_text(node, "CALN", citation.call_number.as_ref());
+}
+
+fn push_address(node: &mut GedcomNode, address: &Address) {
+    let node = node.push_child("ADDR", address.value.as_deref().map(escape));
+    push_text(node, "ADR1", address.adr1.as_ref());
+    push_text(node, "ADR2", address.adr2.as_ref());
+    push_text(node, "ADR3", address.adr3.as_ref());
+    push_text(node, "CITY", address.city.as_ref());
+    push_text(node, "STAE", address.state.as_ref());
+    push_text(node, "POST", address.post.as_ref());
+    push_text(node, "CTRY", address.country.as_ref());
+}
+
+fn push_external_ids(node: &mut GedcomNode, external_ids: &[ExternalId]) {
+    for external_id in external_ids {
+        let exid = node.push_child("EXID", Some(escape(&external_id.id)));
+        push_text(exid, "TYPE", external_id.id_type.as_ref());
+    }
+}
+
+fn push_custom_data(node: &mut GedcomNode, custom_data: &[CustomData]) {
+    for data in custom_data {
+        let value = Some(escape(&data.value)).filter(|value| !value.is_empty());
+        node.push_child(&data.tag, value);
+    }
+}
+
+/// The current date and time in UTC, as the `DATE` and `TIME` of a header
+fn now() -> (String, String) {
+    /// The Julian Day Number of 1 JAN 1970, the day Unix time counts from
+    const UNIX_EPOCH_DAY: i64 = 2_440_588;
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let days = i64::try_from(seconds / 86_400).unwrap_or_default();
+    let date = Calendar::Gregorian
+        .from_julian_day(UNIX_EPOCH_DAY + days)
+        .map(|(year, month, day)| {
+            let month = Calendar::Gregorian.months()[usize::from(month) - 1];
+            format!("{day} {month} {year}")
+        })
+        .unwrap_or_default();
+    let time = seconds % 86_400;
+    (
+        date,
+        format!(
+            "{:02}:{:02}:{:02}",
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        ),
+    )
+}
@@ -0,0 +1,56 @@
+//! Ages are parsed per the `AGE_AT_EVENT` grammar of GEDCOM 5.5.1, keeping their text.
+use gedcom::types::{Age, AgeBound, AgeDuration, AgeValue};
+
+fn duration(text: &str) -> AgeDuration {
+    match Age::parse(text).value {
+        Some(AgeValue::Duration(duration)) => duration,
+        other => panic!("{} should be a duration, found {:?}", text, other),
+    }
+}
+
+#[test]
+fn parses_durations() {
+    assert_eq!(
+        duration("72y 3m"),
+        AgeDuration {
+            years: Some(72),
+            months: Some(3),
+            ..AgeDuration::default()
+        }
+    );
+    assert_eq!(duration("45d").days, Some(45));
+    assert_eq!(duration("<1y").bound, Some(AgeBound::LessThan));
+    // GEDCOM 7 puts a space after the bound and allows weeks
+    let age = duration("> 2w 3d");
+    assert_eq!(age.bound, Some(AgeBound::GreaterThan));
+    assert_eq!((age.weeks, age.days), (Some(2), Some(3)));
+}
+
+#[test]
+fn parses_keywords() {
+    assert_eq!(Age::parse("CHILD").value, Some(AgeValue::Child));
+    assert_eq!(Age::parse("Infant").value, Some(AgeValue::Infant));
+    assert_eq!(Age::parse("STILLBORN").value, Some(AgeValue::Stillborn));
+}
+
+#[test]
+fn keeps_ages_that_break_the_grammar() {
+    for text in ["about 70", "3m 72y", "72y 72y", "<", "72", ""] {
+        let age = Age::parse(text);
+        assert!(!age.is_valid(), "{}", text);
+        assert_eq!(age.to_string(), text);
+    }
+}
+
+#[test]
+fn writes_ages_built_from_values() {
+    let age = Age::new(AgeValue::Duration(AgeDuration {
+        bound: Some(AgeBound::LessThan),
+        years: Some(8),
+        days: Some(10),
+        ..AgeDuration::default()
+    }));
+    assert_eq!(age.original, "<8y 10d");
+    assert_eq!(Age::parse(&age.original), age);
+    assert_eq!(Age::new(AgeValue::Infant).original, "INFANT");
+}
@@ -0,0 +1,159 @@
+//! Dates of every calendar are converted to Julian Day Numbers to be ordered and measured.
+use gedcom::types::{Calendar, Chronology, DateValue, DayRange, GedcomDate};
+
+fn day(text: &str) -> i64 {
+    let range = GedcomDate::parse(text).day_range().unwrap();
+    assert_eq!(range.first, range.last, "{} should be a single day", text);
+    range.first.unwrap()
+}
+
+fn range(text: &str) -> DayRange {
+    GedcomDate::parse(text).day_range().unwrap()
+}
+
+#[test]
+fn converts_gregorian_and_julian_dates() {
+    assert_eq!(day("1 JAN 2000"), 2_451_545);
+    assert_eq!(day("4 JUL 1776"), 2_369_916);
+    // the first day of the Gregorian calendar follows the last of the Julian
+    assert_eq!(day("15 OCT 1582"), 2_299_161);
+    assert_eq!(day("@#DJULIAN@ 4 OCT 1582"), 2_299_160);
+    // the epoch of Julian Day Numbers
+    assert_eq!(day("@#DJULIAN@ 1 JAN 4713 B.C."), 0);
+    assert_eq!(day("24 NOV 4714 B.C."), 0);
+    // a dual year counts from the start of the year in January
+    assert_eq!(day("12 FEB 1750/51"), day("12 FEB 1751"));
+}
+
+#[test]
+fn converts_hebrew_dates() {
+    assert_eq!(day("@#DHEBREW@ 1 TSH 5783"), day("26 SEP 2022"));
+    assert_eq!(day("@#DHEBREW@ 14 ADR 5783"), day("7 MAR 2023"));
+    assert_eq!(day("@#DHEBREW@ 1 TSH 5784"), day("16 SEP 2023"));
+    // 5784 is a leap year, with a second Adar
+    assert_eq!(day("@#DHEBREW@ 14 ADS 5784"), day("24 MAR 2024"));
+    assert_eq!(day("@#DHEBREW@ 15 NSN 5784"), day("23 APR 2024"));
+    assert_eq!(day("@#DHEBREW@ 1 TSH 5785"), day("3 OCT 2024"));
+    assert_eq!(
+        GedcomDate::parse("@#DHEBREW@ 14 ADS 5783").day_range(),
+        None
+    );
+
+    let year = range("@#DHEBREW@ 5784");
+    assert_eq!(year.last.unwrap() - year.first.unwrap() + 1, 383);
+}
+
+#[test]
+fn converts_french_republican_dates() {
+    assert_eq!(day("@#DFRENCH R@ 1 VEND 1"), day("22 SEP 1792"));
+    assert_eq!(day("@#DFRENCH R@ 9 THER 2"), day("27 JUL 1794"));
+    assert_eq!(day("@#DFRENCH R@ 18 BRUM 8"), day("9 NOV 1799"));
+    // the year 3 had a sixth complementary day
+    assert_eq!(day("@#DFRENCH R@ 6 COMP 3"), day("22 SEP 1795"));
+    assert_eq!(GedcomDate::parse("@#DFRENCH R@ 6 COMP 4").day_range(), None);
+}
+
+#[test]
+fn converts_days_back_to_every_calendar() {
+    let calendars = [
+        Calendar::Gregorian,
+        Calendar::Julian,
+        Calendar::Hebrew,
+        Calendar::FrenchRepublican,
+    ];
+    for calendar in calendars {
+        for julian_day in (2_375_840..2_500_000).step_by(7) {
+            let (year, month, day) = calendar.from_julian_day(julian_day).unwrap();
+            assert_eq!(
+                calendar.julian_day(year, month, day),
+                Some(julian_day),
+                "{:?} {}-{}-{}",
+                calendar,
+                year,
+                month,
+                day
+            );
+        }
+    }
+
+    let Some(DateValue::Date(date)) = GedcomDate::parse("@#DHEBREW@ 15 NSN 5784").value else {
+        panic!("expected a single date");
+    };
+    assert_eq!(
+        date.to_calendar(Calendar::Gregorian).unwrap().to_string(),
+        "23 APR 2024"
+    );
+    let Some(DateValue::Date(date)) = GedcomDate::parse("9 NOV 1799").value else {
+        panic!("expected a single date");
+    };
+    assert_eq!(
+        date.to_calendar(Calendar::FrenchRepublican)
+            .unwrap()
+            .to_string(),
+        "@#DFRENCH R@ 18 BRUM 8"
+    );
+}
+
+#[test]
+fn gives_partial_dates_and_ranges_their_days() {
+    let february = range("FEB 1900");
+    assert_eq!(february.first, Some(day("1 FEB 1900")));
+    assert_eq!(february.last, Some(day("28 FEB 1900")));
+    assert_eq!(range("1900").last, Some(day("31 DEC 1900")));
+
+    assert_eq!(range("BEF 1900").first, None);
+    assert_eq!(range("BEF 1900").last, Some(day("31 DEC 1899")));
+    assert_eq!(range("AFT 1900").first, Some(day("1 JAN 1901")));
+    assert_eq!(range("FROM 1899 TO 2000").last, Some(day("31 DEC 2000")));
+    assert_eq!(range("TO 2000").first, None);
+    assert_eq!(GedcomDate::parse("(after the war)").day_range(), None);
+    assert_eq!(GedcomDate::parse("30 FEB 1900").day_range(), None);
+}
+
+#[test]
+fn orders_dates_across_calendars() {
+    let baptism = range("@#DJULIAN@ 28 FEB 1700");
+    let burial = range("1 MAR 1700");
+    let death = range("@#DHEBREW@ 1 ADR 5460");
+    // the Julian 28 February 1700 is the Gregorian 10 March
+    assert!(burial < baptism);
+    assert!(death < burial);
+    assert_eq!(baptism.chronology(&burial), Chronology::After);
+
+    assert_eq!(
+        range("BET 1848 AND 1850").chronology(&range("BET 1849 AND 1851")),
+        Chronology::PossiblyBefore
+    );
+    assert_eq!(
+        range("AFT JUN 1850").chronology(&range("1850")),
+        Chronology::PossiblyAfter
+    );
+    assert_eq!(
+        range("ABT 1850").chronology(&range("JUN 1850")),
+        Chronology::Overlapping
+    );
+    assert_eq!(
+        range("BEF 1850").chronology(&range("1850")),
+        Chronology::Before
+    );
+    assert_eq!(range("ABT 1850").partial_cmp(&range("JUN 1850")), None);
+    assert_eq!(
+        range("1850").partial_cmp(&range("@#DJULIAN@ 20 DEC 1849")),
+        None
+    );
+    assert!(range("1850") == range("FROM 1 JAN 1850 TO 31 DEC 1850"));
+}
+
+#[test]
+fn measures_the_days_between_dates() {
+    let duration = range("@#DJULIAN@ 25 DEC 1642")
+        .duration_until(&range("@#DJULIAN@ 20 MAR 1726"))
+        .unwrap();
+    assert_eq!((duration.min_days, duration.max_days), (30_401, 30_401));
+
+    let duration = range("1850").duration_until(&range("MAR 1851")).unwrap();
+    assert_eq!(duration.min_days, day("1 MAR 1851") - day("31 DEC 1850"));
+    assert_eq!(duration.max_days, day("31 MAR 1851") - day("1 JAN 1850"));
+
+    assert_eq!(range("1850").duration_until(&range("AFT 1900")), None);
+}
@@ -0,0 +1,240 @@
+//! Converting between GEDCOM 5.5.1 and GEDCOM 7.0 changes the structures each version
+//! writes differently, and reports whatever cannot be carried over.
+use gedcom::convert::{Converter, ConverterOptions};
+use gedcom::parser::Parser;
+use gedcom::types::GedcomVersion;
+use gedcom::DiagnosticKind;
+
+fn convert(source: &str, converter: &mut Converter) -> String {
+    let mut parser = Parser::new(source.chars());
+    let data = parser.try_parse().unwrap();
+    let mut output = Vec::new();
+    converter.write(&data, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn lossy_lines(converter: &Converter) -> Vec<u32> {
+    converter
+        .diagnostics()
+        .iter()
+        .filter(|diagnostic| diagnostic.kind == DiagnosticKind::LossyConversion)
+        .map(|diagnostic| diagnostic.line)
+        .collect()
+}
+
+const GEDCOM5: &str = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+1 FILE family.ged
+1 LANG Spanish
+1 SUBN @U1@
+0 @I1@ INDI
+1 NAME Ana /García/
+2 ROMN Ana /Garcia/
+3 TYPE manual
+1 _UID 8D4A3B6C1E2F4A5B9C0D1E2F3A4B5C6D
+1 RFN 12345
+1 BIRT
+2 DATE INT 3 March 1850 (third day of March)
+2 SOUR Parish register of San Pedro
+3 TEXT Baptized the same day
+1 DEAT
+2 DATE @#DJULIAN@ 12 FEB 1699/00
+2 AGE CHILD
+1 BURI
+2 DATE (after the war)
+1 FAMC @F1@
+2 PEDI birth
+1 OBJE
+2 FILE portrait.jpg
+3 FORM jpg
+2 TITL Portrait of Ana
+1 ASSO @I2@
+2 RELA Godmother
+1 NOTE @N1@
+1 _HAIR brown
+0 @N1@ NOTE Her email is ana@@example.com, and her name is spelled Ana Garc
+1 CONC ía in every record.
+0 @U1@ SUBN
+0 TRLR
+";
+
+const GEDCOM7: &str = "\
+0 HEAD
+1 GEDC
+2 VERS 7.0
+1 LANG es
+0 @I1@ INDI
+1 NAME Ana /García/
+2 TRAN Ana /Garcia/
+3 LANG und-Latn
+1 UID 8D4A3B6C1E2F4A5B9C0D1E2F3A4B5C6D
+1 EXID 12345
+2 TYPE https://gedcom.io/terms/v7/RFN
+1 BIRT
+2 DATE 3 MAR 1850
+3 PHRASE third day of March
+2 SOUR @VOID@
+3 PAGE Parish register of San Pedro
+3 DATA
+4 TEXT Baptized the same day
+1 DEAT
+2 DATE JULIAN 12 FEB 1700
+3 PHRASE @@#DJULIAN@ 12 FEB 1699/00
+2 AGE < 8y
+3 PHRASE Child
+1 BURI
+2 DATE
+3 PHRASE after the war
+1 FAMC @F1@
+2 PEDI BIRTH
+1 OBJE @M1@
+1 ASSO @I2@
+2 ROLE OTHER
+3 PHRASE Godmother
+1 SNOTE @N1@
+1 _HAIR brown
+0 @N1@ SNOTE Her email is ana@example.com, and her name is spelled Ana García in every record.
+0 @M1@ OBJE
+1 FILE portrait.jpg
+2 FORM image/jpeg
+2 TITL Portrait of Ana
+0 TRLR
+";
+
+#[test]
+fn converts_to_gedcom7() {
+    let mut converter = Converter::new(GedcomVersion::V7);
+    assert_eq!(convert(GEDCOM5, &mut converter), GEDCOM7);
+    // the file name, the romanization method, the dual year and the submission
+    assert_eq!(lossy_lines(&converter), vec![6, 8, 12, 20, 36]);
+
+    let undeclared: Vec<&str> = converter
+        .diagnostics()
+        .iter()
+        .filter(|diagnostic| diagnostic.kind == DiagnosticKind::UndeclaredExtension)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        undeclared,
+        vec!["Extension tag _HAIR is not declared in the header"]
+    );
+}
+
+#[test]
+fn declares_extension_tags() {
+    let options = ConverterOptions {
+        extension_base_uri: Some("https://example.com/tags/".to_string()),
+        ..ConverterOptions::default()
+    };
+    let mut converter = Converter::with_options(GedcomVersion::V7, options);
+    let written = convert(GEDCOM5, &mut converter);
+    assert!(written.starts_with(
+        "0 HEAD\n1 GEDC\n2 VERS 7.0\n1 SCHMA\n2 TAG _HAIR https://example.com/tags/_HAIR\n"
+    ));
+    assert!(converter
+        .diagnostics()
+        .iter()
+        .all(|diagnostic| diagnostic.kind != DiagnosticKind::UndeclaredExtension));
+}
+
+#[test]
+fn converts_back_to_gedcom5() {
+    let mut converter = Converter::new(GedcomVersion::V5);
+    let written = convert(GEDCOM7, &mut converter);
+    assert_eq!(
+        written,
+        "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+1 LANG Spanish
+0 @I1@ INDI
+1 NAME Ana /García/
+2 ROMN Ana /Garcia/
+3 TYPE und-Latn
+1 _UID 8D4A3B6C1E2F4A5B9C0D1E2F3A4B5C6D
+1 RFN 12345
+1 BIRT
+2 DATE INT 3 MAR 1850 (third day of March)
+2 SOUR Parish register of San Pedro
+3 TEXT Baptized the same day
+1 DEAT
+2 DATE INT @#DJULIAN@ 12 FEB 1700 (@#DJULIAN@ 12 FEB 1699/00)
+2 AGE CHILD
+1 BURI
+2 DATE (after the war)
+1 FAMC @F1@
+2 PEDI birth
+1 OBJE @M1@
+1 ASSO @I2@
+2 RELA Godmother
+1 NOTE @N1@
+1 _HAIR brown
+0 @N1@ NOTE Her email is ana@@example.com, and her name is spelled Ana García in every record.
+0 @M1@ OBJE
+1 FILE portrait.jpg
+2 FORM jpg
+2 TITL Portrait of Ana
+0 TRLR
+"
+    );
+    assert!(converter.diagnostics().is_empty());
+}
+
+#[test]
+fn reports_gedcom7_structures_dropped_by_the_downgrade() {
+    let original = std::fs::read("./tests/fixtures/gedcom7.ged").unwrap();
+    let data = Parser::from_reader(&original[..]).try_parse().unwrap();
+    let mut converter = Converter::new(GedcomVersion::V5);
+    let mut output = Vec::new();
+    converter.write(&data, &mut output).unwrap();
+    let written = String::from_utf8(output).unwrap();
+
+    assert!(written.contains("1 SEX U\n"));
+    assert!(written.contains("0 @F1@ FAM\n1 WIFE @I2@\n"));
+    assert!(written.contains("0 @N1@ NOTE @@lucia is her handle on most sites"));
+    let dropped: Vec<Vec<String>> = converter
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.tag_path.clone())
+        .collect();
+    assert_eq!(
+        dropped,
+        vec![
+            vec!["HEAD", "SCHMA"],
+            vec!["INDI", "SEX"],
+            vec!["INDI", "FAMC", "PEDI"],
+            vec!["FAM", "HUSB"],
+            vec!["SNOTE", "LANG"],
+            vec!["SNOTE", "TRAN"],
+        ]
+    );
+}
+
+#[test]
+fn keeps_overflowing_dates_and_ages_as_phrases() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 _MILT\n2 DATE 4294967295/00\n\
+        1 DEAT\n2 AGE 900000000w\n0 TRLR\n";
+    let mut converter = Converter::new(GedcomVersion::V7);
+    let written = convert(source, &mut converter);
+    assert!(written.contains("1 _MILT\n2 DATE\n3 PHRASE 4294967295/00\n"));
+    assert!(written.contains("1 DEAT\n2 AGE\n3 PHRASE 900000000w\n"));
+    assert_eq!(lossy_lines(&converter), vec![6, 8]);
+}
+
+#[test]
+fn counts_weeks_as_days_and_reports_unknown_media_types() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 DEAT\n2 AGE > 2w 1d\n\
+        0 @M1@ OBJE\n1 FILE scan.raw\n2 FORM image/x-raw\n0 TRLR\n";
+    let mut converter = Converter::new(GedcomVersion::V5);
+    let written = convert(source, &mut converter);
+    assert!(written.contains("1 DEAT\n2 AGE >15d\n"));
+    assert!(written.contains("2 FORM x-raw\n"));
+    assert_eq!(lossy_lines(&converter), vec![9]);
+}
@@ -0,0 +1,185 @@
+//! Dates are parsed per the `DATE_VALUE` grammar of GEDCOM 5.5.1, keeping their text.
+use gedcom::types::{event::HasEvents, Approximation, Calendar, Date, DateValue, GedcomDate};
+use gedcom::DiagnosticKind;
+
+fn value(text: &str) -> DateValue {
+    GedcomDate::parse(text)
+        .value
+        .unwrap_or_else(|| panic!("{} should be a valid date", text))
+}
+
+fn gregorian(day: Option<u8>, month: Option<u8>, year: u32) -> Date {
+    Date {
+        day,
+        month,
+        year,
+        ..Date::default()
+    }
+}
+
+#[test]
+fn parses_exact_and_partial_dates() {
+    assert_eq!(
+        value("31 DEC 1990"),
+        DateValue::Date(gregorian(Some(31), Some(12), 1990))
+    );
+    assert_eq!(
+        value("MAR 1850"),
+        DateValue::Date(gregorian(None, Some(3), 1850))
+    );
+    assert_eq!(value("1899"), DateValue::Date(gregorian(None, None, 1899)));
+    // keywords and months are matched regardless of case
+    assert_eq!(
+        value("abt jul 1950"),
+        DateValue::Approximate(Approximation::About, gregorian(None, Some(7), 1950))
+    );
+}
+
+#[test]
+fn parses_approximations_ranges_and_periods() {
+    let year = |year| gregorian(None, None, year);
+    assert_eq!(
+        value("CAL 1850"),
+        DateValue::Approximate(Approximation::Calculated, year(1850))
+    );
+    assert_eq!(
+        value("EST 1850"),
+        DateValue::Approximate(Approximation::Estimated, year(1850))
+    );
+    assert_eq!(value("BEF 1850"), DateValue::Before(year(1850)));
+    assert_eq!(value("AFT 1850"), DateValue::After(year(1850)));
+    assert_eq!(
+        value("BET 1850 AND 1860"),
+        DateValue::Between(year(1850), year(1860))
+    );
+    assert_eq!(
+        value("FROM 1899 TO 2000"),
+        DateValue::Period(Some(year(1899)), Some(year(2000)))
+    );
+    assert_eq!(
+        value("FROM 1899"),
+        DateValue::Period(Some(year(1899)), None)
+    );
+    assert_eq!(value("TO 2000"), DateValue::Period(None, Some(year(2000))));
+}
+
+#[test]
+fn parses_phrases() {
+    assert_eq!(
+        value("INT 3 MAR 1850 (third day of March)"),
+        DateValue::Interpreted(
+            gregorian(Some(3), Some(3), 1850),
+            "third day of March".to_string()
+        )
+    );
+    assert_eq!(
+        value("(after the war)"),
+        DateValue::Phrase("after the war".to_string())
+    );
+}
+
+#[test]
+fn parses_dual_years_calendars_and_epochs() {
+    let DateValue::Date(date) = value("12 FEB 1750/51") else {
+        panic!("expected a single date");
+    };
+    assert_eq!(date.year, 1750);
+    assert_eq!(date.dual_year, Some(1751));
+
+    let calendar = |text| match value(text) {
+        DateValue::Date(date) => (date.calendar, date.day, date.month, date.year),
+        other => panic!("expected a single date, found {:?}", other),
+    };
+    assert_eq!(
+        calendar("@#DGREGORIAN@ 1 JAN 1900"),
+        (Calendar::Gregorian, Some(1), Some(1), 1900)
+    );
+    assert_eq!(
+        calendar("@#DJULIAN@ 25 DEC 1642"),
+        (Calendar::Julian, Some(25), Some(12), 1642)
+    );
+    assert_eq!(
+        calendar("@#DHEBREW@ 15 NSN 5784"),
+        (Calendar::Hebrew, Some(15), Some(8), 5784)
+    );
+    assert_eq!(
+        calendar("@#DFRENCH R@ 18 BRUM 8"),
+        (Calendar::FrenchRepublican, Some(18), Some(2), 8)
+    );
+    // the forms of GEDCOM 7
+    assert_eq!(
+        calendar("JULIAN 25 DEC 1642"),
+        (Calendar::Julian, Some(25), Some(12), 1642)
+    );
+
+    let DateValue::Date(date) = value("44 B.C.") else {
+        panic!("expected a single date");
+    };
+    assert!(date.before_common_era);
+    assert_eq!(date.year, 44);
+}
+
+#[test]
+fn keeps_dates_that_break_the_grammar() {
+    for text in [
+        "2 February 1902",
+        "32 JAN 1900",
+        "15 NSN 1900",
+        "@#DJULIAN@ 1699/00",
+        "@#DROMAN@ 753",
+        "BET 1850",
+        "INT 1850",
+        "1850 or so",
+    ] {
+        let date = GedcomDate::parse(text);
+        assert!(!date.is_valid(), "{} should not be a valid date", text);
+        assert_eq!(date.to_string(), text);
+    }
+}
+
+#[test]
+fn writes_dates_built_from_values() {
+    let date = GedcomDate::new(DateValue::Between(
+        Date {
+            calendar: Calendar::Julian,
+            ..gregorian(Some(1), Some(3), 1699)
+        },
+        Date {
+            dual_year: Some(1700),
+            ..gregorian(Some(24), Some(3), 1699)
+        },
+    ));
+    assert_eq!(
+        date.original,
+        "BET @#DJULIAN@ 1 MAR 1699 AND 24 MAR 1699/00"
+    );
+    assert_eq!(GedcomDate::parse(&date.original), date);
+}
+
+#[test]
+fn reports_unrecognized_dates() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 DATE 1 JAN 2020\n2 TIME 12:00:00\n\
+        0 @I1@ INDI\n1 BIRT\n2 DATE 2 February 1902\n1 DEAT\n2 DATE ABT 1950\n0 TRLR";
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(source.chars()).unwrap();
+
+    assert_eq!(data.header.date, Some(GedcomDate::parse("1 JAN 2020")));
+    assert_eq!(data.header.time.as_deref(), Some("12:00:00"));
+    let dates = data.individuals[0].dates();
+    assert_eq!(dates[0].original, "2 February 1902");
+    assert!(dates[1].is_valid());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnrecognizedValue);
+    assert_eq!(diagnostics[0].line, 8);
+}
+
+#[test]
+fn dual_years_past_the_largest_year_are_invalid() {
+    for text in ["4294967295/9", "4294967295/00", "ABT 4294967290/99"] {
+        assert!(!GedcomDate::parse(text).is_valid(), "{}", text);
+    }
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 BIRT\n2 DATE 4294967295/9\n0 TRLR\n";
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(source.chars()).unwrap();
+    assert_eq!(data.individuals[0].dates()[0].original, "4294967295/9");
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnrecognizedValue);
+}
@@ -0,0 +1,110 @@
+//! Bytes are decoded in the encoding declared by their byte order mark or `HEAD.CHAR`.
+use std::io::{self, BufReader, Read};
+
+use gedcom::decode::{self, Encoding};
+use gedcom::parser::Parser;
+use gedcom::reader::ReadChars;
+
+/// The name of the first individual of a file read from `bytes`
+fn first_name(bytes: &[u8]) -> String {
+    let data = Parser::from_reader(bytes).try_parse().unwrap();
+    data.individuals[0].names[0].value.clone().unwrap()
+}
+
+/// `text` in UTF-16, with a byte order mark
+fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    std::iter::once(0xFEFF)
+        .chain(text.encode_utf16())
+        .flat_map(to_bytes)
+        .collect()
+}
+
+#[test]
+fn decodes_utf16_of_either_byte_order() {
+    let source = "0 HEAD\n1 CHAR UNICODE\n0 @I1@ INDI\n1 NAME Zoë /Ŝmith/ 🌳\n0 TRLR\n";
+    let little = utf16(source, u16::to_le_bytes);
+    let big = utf16(source, u16::to_be_bytes);
+    assert_eq!(decode::detect(&little), Encoding::Utf16Le);
+    assert_eq!(decode::detect(&big), Encoding::Utf16Be);
+    assert_eq!(decode::decode(&little), source);
+    assert_eq!(decode::decode(&big), source);
+    assert_eq!(first_name(&little), "Zoë /Ŝmith/ 🌳");
+    assert_eq!(first_name(&big), "Zoë /Ŝmith/ 🌳");
+
+    // without a byte order mark, the zero byte next to the first level gives it away
+    let unmarked: Vec<u8> = source.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    assert_eq!(decode::detect(&unmarked), Encoding::Utf16Le);
+    assert_eq!(first_name(&unmarked), "Zoë /Ŝmith/ 🌳");
+}
+
+#[test]
+fn decodes_windows_code_pages() {
+    let bytes =
+        b"0 HEAD\n1 CHAR ANSI\n0 @I1@ INDI\n1 NAME Ren\xe9e /\x93Bo\x94 M\xfcller/ \x80\n0 TRLR\n";
+    assert_eq!(decode::detect(bytes), Encoding::Windows1252);
+    assert_eq!(first_name(bytes), "Renée /\u{201C}Bo\u{201D} Müller/ €");
+
+    // files labelled ASCII are often Windows-1252
+    let ascii = b"0 HEAD\n1 CHAR ASCII\n0 @I1@ INDI\n1 NAME Ren\xe9e\n0 TRLR\n";
+    assert_eq!(first_name(ascii), "Renée");
+
+    let latin1 = b"0 HEAD\n1 CHAR ISO-8859-1\n0 @I1@ INDI\n1 NAME \x80 Ren\xe9e\n0 TRLR\n";
+    assert_eq!(first_name(latin1), "\u{80} Renée");
+}
+
+#[test]
+fn composes_ansel_diacritics() {
+    // the acute, umlaut and cedilla come before the letter they belong to
+    let bytes = b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME Fran\xf0cois /M\xe8uller/ \xe2q\n\
+        2 NOTE S\xb2ren \xa5\n0 TRLR\n";
+    assert_eq!(decode::detect(bytes), Encoding::Ansel);
+    // composed into single characters where Unicode has them, as in normalization form C
+    assert_eq!(first_name(bytes), "François /Müller/ q\u{301}");
+    assert!(decode::decode(bytes).contains("Søren Æ"));
+}
+
+#[test]
+fn reads_the_declared_encoding_into_the_header() {
+    let bytes = b"0 HEAD\n1 CHAR ANSEL\n0 TRLR\n";
+    let data = Parser::from_reader(&bytes[..]).try_parse().unwrap();
+    assert_eq!(data.header.encoding.as_deref(), Some("ANSEL"));
+
+    // files without a byte order mark or a known character set are read as UTF-8
+    let unlabelled = "0 HEAD\n0 @I1@ INDI\n1 NAME Zoë\n0 TRLR\n".as_bytes();
+    assert_eq!(decode::detect(unlabelled), Encoding::Utf8);
+    assert_eq!(first_name(unlabelled), "Zoë");
+}
+
+#[test]
+fn reads_ahead_to_the_character_set_however_short_the_reads() {
+    /// A reader giving out a few bytes at a time, as a slow network stream might
+    struct Trickle<'a>(&'a [u8]);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.0.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    let bytes = b"0 HEAD\n1 SOUR PAF\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n0 @I1@ INDI\n\
+        1 NAME Fran\xf0cois /M\xe8uller/\n0 TRLR\n";
+    let mut chars = ReadChars::new(BufReader::new(Trickle(bytes)));
+    let text: String = chars.by_ref().collect();
+    assert_eq!(chars.encoding(), Some(Encoding::Ansel));
+    assert_eq!(text, decode::decode(bytes));
+    let data = Parser::from_reader(BufReader::new(Trickle(bytes)))
+        .try_parse()
+        .unwrap();
+    assert_eq!(
+        data.individuals[0].names[0].value.as_deref(),
+        Some("François /Müller/")
+    );
+
+    // a header without a character set is read up to the next record
+    let unlabelled = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Zoë\n0 TRLR\n";
+    let mut chars = ReadChars::new(BufReader::new(Trickle(unlabelled.as_bytes())));
+    assert_eq!(chars.by_ref().collect::<String>(), unlabelled);
+    assert_eq!(chars.encoding(), Some(Encoding::Utf8));
+}
@@ -0,0 +1,64 @@
+//! Problems the parser recovers from are collected as diagnostics, with where they were found.
+use gedcom::parser::Parser;
+use gedcom::{Diagnostic, DiagnosticKind, Severity};
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 _ROOT @I1@\n0 @I1@ INDI\n\
+    1 NAME Tove /Dahl/\n1 AFN 12AB-3CD\n1 BIRT\n2 DATE sometime\n0 @S1@ SOUR\n1 TITL\n";
+
+#[test]
+fn collects_diagnostics_with_their_location() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(SOURCE.chars()).unwrap();
+    assert_eq!(data.individuals.len(), 1);
+    assert_eq!(
+        diagnostics[1],
+        Diagnostic {
+            severity: Severity::Warning,
+            line: 7,
+            tag_path: vec!["INDI".to_string(), "AFN".to_string()],
+            xref: Some("@I1@".to_string()),
+            message: "Unhandled Individual Tag: AFN".to_string(),
+            kind: DiagnosticKind::UnhandledTag,
+        }
+    );
+
+    let found: Vec<(Severity, u32, DiagnosticKind)> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.line, diagnostic.kind))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Severity::Warning, 4, DiagnosticKind::SkippedCustomTag),
+            (Severity::Warning, 7, DiagnosticKind::UnhandledTag),
+            (Severity::Error, 9, DiagnosticKind::UnrecognizedValue),
+            (Severity::Error, 11, DiagnosticKind::MissingValue),
+            (Severity::Error, 11, DiagnosticKind::MissingTrailer),
+        ]
+    );
+}
+
+#[test]
+fn describes_diagnostics_with_their_location() {
+    let (_, diagnostics) = gedcom::parse_with_diagnostics(SOURCE.chars()).unwrap();
+    let described: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        described[1..],
+        [
+            "WARNING: line 7: @I1@ INDI.AFN: Unhandled Individual Tag: AFN",
+            "ERROR: line 9: @I1@ INDI.BIRT.DATE: Unrecognized date: sometime",
+            "ERROR: line 11: @S1@ SOUR.TITL: Expected a value for TITL",
+            "ERROR: line 11: File ended without a TRLR record",
+        ]
+    );
+}
+
+#[test]
+fn keeps_diagnostics_on_the_parser_until_taken() {
+    let mut parser = Parser::new(SOURCE.chars());
+    parser.try_parse().unwrap();
+    assert_eq!(parser.diagnostics().len(), 5);
+
+    let taken = parser.take_diagnostics();
+    assert_eq!(taken.len(), 5);
+    assert!(parser.diagnostics().is_empty());
+}
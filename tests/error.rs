@@ -0,0 +1,113 @@
+//! Malformed input is reported as a `ParseError` pointing at the offending line.
+use gedcom::parser::{Parser, ParserOptions, ValuePolicy};
+use gedcom::ParseError;
+
+/// The error from parsing `records` after a 5.5.1 header
+fn parse_error(records: &str) -> ParseError {
+    let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n{records}0 TRLR\n");
+    gedcom::try_parse(source.chars()).unwrap_err()
+}
+
+#[test]
+fn locates_each_error() {
+    let cases = [
+        (
+            "0 @I1@ INDI\n1 NAME Ann\nX NAME Bo\n",
+            6,
+            None,
+            Some("@I1@"),
+        ),
+        (
+            "0 @I1@ INDI\n1 FAMC @F1@\n2 PEDI cousin\n",
+            6,
+            Some("PEDI"),
+            Some("@I1@"),
+        ),
+        (
+            "0 @F1@ FAM\n1 WIFE @I1@\n1 WIFE @I2@\n",
+            6,
+            Some("WIFE"),
+            Some("@F1@"),
+        ),
+    ];
+    for (records, line, tag, xref) in cases {
+        let error = parse_error(records);
+        assert_eq!(
+            (error.line(), error.tag(), error.xref()),
+            (line, tag, xref),
+            "{:?}",
+            error
+        );
+    }
+
+    let error = gedcom::try_parse("0 HEAD\n1 TIME 12:00\n0 TRLR\n".chars()).unwrap_err();
+    assert_eq!(error, ParseError::TimeWithoutDate { line: 2 });
+    assert_eq!(error.tag(), Some("TIME"));
+}
+
+#[test]
+fn carries_the_offending_values() {
+    assert_eq!(
+        parse_error("0 @I1@ INDI\nX NAME Bo\n"),
+        ParseError::InvalidLevel {
+            line: 5,
+            found: "X".to_string(),
+            xref: None,
+        }
+    );
+    assert_eq!(
+        parse_error("0 @I1@ INDI\n1 FAMC @F1@\n2 PEDI cousin\n"),
+        ParseError::UnknownPedigree {
+            line: 6,
+            value: "cousin".to_string(),
+            xref: Some("@I1@".to_string()),
+        }
+    );
+    assert_eq!(
+        parse_error("0 @F1@ FAM\n1 HUSB @I1@\n1 HUSB @I2@\n"),
+        ParseError::DuplicateSpouse {
+            line: 6,
+            tag: "HUSB".to_string(),
+            xref: Some("@F1@".to_string()),
+        }
+    );
+
+    let options = ParserOptions {
+        unrecognized_sex: ValuePolicy::Error,
+        ..ParserOptions::default()
+    };
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 SEX Q\n0 TRLR\n";
+    let error = Parser::with_options(source.chars(), options)
+        .try_parse()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::UnknownSex {
+            line: 5,
+            value: "Q".to_string(),
+            xref: Some("@I1@".to_string()),
+        }
+    );
+}
+
+#[test]
+fn describes_errors_with_their_line_and_record() {
+    assert_eq!(
+        parse_error("0 @I1@ INDI\n1 FAMC @F1@\n2 PEDI cousin\n").to_string(),
+        "line 6: unrecognized family link pedigree \"cousin\" (in record @I1@)"
+    );
+    assert_eq!(
+        parse_error("0 @F1@ FAM\n1 HUSB @I1@\n1 HUSB @I2@\n").to_string(),
+        "line 6: family already has a HUSB individual (in record @F1@)"
+    );
+    assert_eq!(
+        parse_error("X INDI\n").to_string(),
+        "line 4: expected a level number, found \"X\""
+    );
+}
+
+#[test]
+#[should_panic(expected = "line 4")]
+fn parse_panics_with_the_error() {
+    let _ = gedcom::parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\nX INDI\n0 TRLR\n".chars());
+}
@@ -0,0 +1,230 @@
+//! Every event and attribute of individuals and families is typed, keeping the value of its line.
+use gedcom::parser::{Parser, ParserOptions};
+use gedcom::types::{
+    event::HasEvents, Age, Event, EventType, Individual, MediaLink, NoteStructure,
+};
+use gedcom::writer::GedcomWriter;
+use gedcom::{DiagnosticKind, ParseError};
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Ole /Berg/\n\
+    1 BAPM\n2 DATE 3 MAR 1861\n1 CENS\n2 DATE 1865\n1 EMIG Y\n2 DATE 1882\n1 GRAD\n\
+    1 OCCU Ship's carpenter\n2 DATE FROM 1880 TO 1895\n1 DSCR Tall, with a scar\n2 CONT on the left hand\n\
+    1 NCHI 4\n1 SSN 123-45-6789\n1 TITL Sir\n\
+    1 EVEN Fought at Gettysburg\n2 TYPE Military service\n2 DATE JUL 1863\n1 EVEN\n0 TRLR\n";
+
+#[test]
+fn types_every_event_and_attribute() {
+    let data = gedcom::parse(SOURCE.chars());
+    let events = data.individuals[0].events();
+    let types: Vec<&EventType> = events.iter().map(|event| &event.event).collect();
+    assert_eq!(
+        types,
+        vec![
+            &EventType::Baptism,
+            &EventType::Census,
+            &EventType::Emigration,
+            &EventType::Graduation,
+            &EventType::Occupation,
+            &EventType::PhysicalDescription,
+            &EventType::ChildrenCount,
+            &EventType::SocialSecurityNumber,
+            &EventType::NobilityTitle,
+            &EventType::Custom("Military service".to_string()),
+            &EventType::Other,
+        ]
+    );
+    assert!(events[4].event.is_attribute());
+    assert!(!events[0].event.is_attribute());
+}
+
+#[test]
+fn keeps_the_values_of_events_and_attributes() {
+    let data = gedcom::parse(SOURCE.chars());
+    let events = data.individuals[0].events();
+    let value = |index: usize| events[index].value.as_deref();
+    assert_eq!(value(0), None);
+    assert_eq!(value(2), Some("Y"));
+    assert_eq!(value(4), Some("Ship's carpenter"));
+    assert_eq!(value(5), Some("Tall, with a scar\non the left hand"));
+    assert_eq!(value(6), Some("4"));
+    assert_eq!(value(9), Some("Fought at Gettysburg"));
+    assert_eq!(
+        events[4].date.as_ref().unwrap().original,
+        "FROM 1880 TO 1895"
+    );
+}
+
+#[test]
+fn reads_custom_event_types_from_tags() {
+    assert_eq!(Event::from_tag("NATU").event, EventType::Naturalization);
+    assert_eq!(
+        Event::from_tag("_MILT").event,
+        EventType::Custom("_MILT".to_string())
+    );
+    assert_eq!(EventType::Custom("_MILT".to_string()).tag(), "_MILT");
+    assert_eq!(
+        EventType::Custom("Military service".to_string()).tag(),
+        "EVEN"
+    );
+    assert_eq!(EventType::Religion.tag(), "RELI");
+}
+
+#[test]
+fn writes_events_with_their_values_and_types() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    let mut individual = Individual::new(Some("@I2@".to_string()));
+    // a record without a node is written from its typed data
+    for event in data.individuals.remove(0).events() {
+        individual.add_event(event);
+    }
+    let mut milt = Event::from_tag("_MILT");
+    milt.value = Some("Navy".to_string());
+    individual.add_event(milt);
+    data.add_individual(individual);
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("1 EMIG Y\n2 DATE 1882\n1 GRAD\n1 OCCU Ship's carpenter\n"));
+    assert!(written.contains("1 DSCR Tall, with a scar\n2 CONT on the left hand\n"));
+    assert!(written.contains(
+        "1 EVEN Fought at Gettysburg\n2 TYPE Military service\n2 DATE JUL 1863\n1 EVEN\n"
+    ));
+    assert!(written.contains("1 _MILT Navy\n"));
+
+    let reparsed = gedcom::parse(written.chars());
+    let events = data.individuals[0].events();
+    // the extension tag is kept as custom data rather than as an event
+    assert_eq!(reparsed.individuals[0].events(), events[..events.len() - 1]);
+}
+
+const FAMILY: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n\
+    1 ENGA\n2 DATE 1880\n1 MARR\n2 DATE 1881\n2 HUSB\n3 AGE 24y\n2 WIFE\n3 AGE 19y\n\
+    1 CENS\n2 DATE 1890\n1 CENS\n2 DATE 1900\n1 DIV\n2 DATE 1905\n1 MARR\n2 DATE 1910\n\
+    1 EVEN\n2 TYPE Separation\n0 TRLR\n";
+
+#[test]
+fn keeps_every_family_event() {
+    let data = gedcom::parse(FAMILY.chars());
+    let events = data.families[0].events();
+    let types: Vec<&EventType> = events.iter().map(|event| &event.event).collect();
+    // remarriages and repeated censuses are all kept
+    assert_eq!(
+        types,
+        vec![
+            &EventType::Engagement,
+            &EventType::Marriage,
+            &EventType::Census,
+            &EventType::Census,
+            &EventType::Divorce,
+            &EventType::Marriage,
+            &EventType::Custom("Separation".to_string()),
+        ]
+    );
+    assert_eq!(events[1].husband_age, Some(Age::parse("24y")));
+    assert_eq!(events[1].wife_age, Some(Age::parse("19y")));
+    assert_eq!(events[5].husband_age, None);
+}
+
+#[test]
+fn keeps_repeated_family_events_unless_they_are_errors() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(FAMILY.chars()).unwrap();
+    assert_eq!(data.families[0].events().len(), 7);
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    let options = ParserOptions {
+        duplicate_family_event_is_error: true,
+        ..ParserOptions::default()
+    };
+    let error = Parser::with_options(FAMILY.chars(), options)
+        .try_parse()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::DuplicateEvent {
+            line: 17,
+            tag: "CENS".to_string(),
+            xref: Some("@F1@".to_string()),
+        }
+    );
+}
+
+#[test]
+fn writes_the_ages_of_spouses() {
+    let mut data = gedcom::parse(FAMILY.chars());
+    data.families[0].node = None;
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("1 MARR\n2 HUSB\n3 AGE 24y\n2 WIFE\n3 AGE 19y\n2 DATE 1881\n"));
+    assert_eq!(
+        gedcom::parse(written.chars()).families[0].events(),
+        data.families[0].events()
+    );
+}
+
+const DETAILS: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Ada /Holm/\n\
+    1 DEAT Y\n2 TYPE Drowned\n2 DATE 4 MAY 1902\n2 PLAC Bergen\n2 ADDR Bryggen 4\n3 CITY Bergen\n\
+    2 PHON 555-0100\n2 EMAIL harbour@@example.org\n2 AGNC Harbour police\n2 RELI Lutheran\n\
+    2 CAUS Fell from the pier\n2 RESN confidential\n2 NOTE Found by her brother\n2 SOUR @S1@\n\
+    2 OBJE @M1@\n2 AGE 72y 3m\n1 BURI\n2 AGE about 72\n0 @S1@ SOUR\n1 DATA\n2 EVEN DEAT\n\
+    3 DATE 1902\n3 NOTE Parish register\n0 TRLR\n";
+
+#[test]
+fn keeps_the_details_of_events() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(DETAILS.chars()).unwrap();
+    let death = &data.individuals[0].events()[0];
+    assert_eq!(death.classification.as_deref(), Some("Drowned"));
+    assert_eq!(death.age, Some(Age::parse("72y 3m")));
+    assert!(death.age.as_ref().unwrap().is_valid());
+    assert_eq!(death.agency.as_deref(), Some("Harbour police"));
+    assert_eq!(death.religion.as_deref(), Some("Lutheran"));
+    assert_eq!(death.cause.as_deref(), Some("Fell from the pier"));
+    assert_eq!(death.restriction.as_deref(), Some("confidential"));
+    let address = death.address.as_ref().unwrap();
+    assert_eq!(address.value.as_deref(), Some("Bryggen 4"));
+    assert_eq!(address.city.as_deref(), Some("Bergen"));
+    assert_eq!(death.phones, vec!["555-0100"]);
+    assert_eq!(death.emails, vec!["harbour@example.org"]);
+    assert_eq!(
+        death.notes,
+        vec![NoteStructure::Text("Found by her brother".to_string())]
+    );
+    assert_eq!(death.media, vec![MediaLink::Shared("@M1@".to_string())]);
+
+    // the events recorded by a source have the same details
+    let recorded = &data.sources[0].data.events()[0];
+    assert_eq!(
+        recorded.notes,
+        vec![NoteStructure::Text("Parish register".to_string())]
+    );
+
+    // an age that breaks the grammar is kept as it was read
+    let burial = &data.individuals[0].events()[1];
+    assert_eq!(burial.age.as_ref().unwrap().original, "about 72");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnrecognizedValue);
+    assert_eq!(diagnostics[0].line, 23);
+}
+
+#[test]
+fn writes_the_details_of_events() {
+    let mut data = gedcom::parse(DETAILS.chars());
+    data.individuals[0].node = None;
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    // GEDCOM 5.5.1 doubles every `@` of a text value
+    assert!(written.contains(
+        "1 DEAT Y\n2 TYPE Drowned\n2 DATE 4 MAY 1902\n2 PLAC Bergen\n2 ADDR Bryggen 4\n\
+         3 CITY Bergen\n2 PHON 555-0100\n2 EMAIL harbour@@example.org\n2 AGNC Harbour police\n\
+         2 RELI Lutheran\n2 CAUS Fell from the pier\n2 RESN confidential\n\
+         2 NOTE Found by her brother\n2 SOUR @S1@\n2 OBJE @M1@\n2 AGE 72y 3m\n"
+    ));
+    assert_eq!(
+        gedcom::parse(written.chars()).individuals[0].events(),
+        data.individuals[0].events()
+    );
+}
@@ -0,0 +1,37 @@
+//! The C bindings return the parsed data, or write the error to the caller's buffer.
+use std::ffi::{c_char, CStr, CString};
+
+use gedcom::ffi::{free_parse, parse};
+
+#[test]
+fn parses_into_c_data() {
+    let content = CString::new("0 HEAD\n0 @I1@ INDI\n1 NAME Åse /Lund/\n0 TRLR\n").unwrap();
+    let mut err_str = [0 as c_char; 64];
+    let data = unsafe { parse(content.as_ptr(), err_str.as_mut_ptr(), err_str.len()) };
+    assert!(!data.is_null());
+    unsafe {
+        let individuals = &(*data).individuals;
+        assert_eq!(individuals.len, 1);
+        let name = &*(*individuals.data).name.data;
+        assert_eq!(CStr::from_ptr(name.value.data).to_str(), Ok("Åse /Lund/"));
+        free_parse(data);
+    }
+}
+
+#[test]
+fn cuts_the_error_at_a_character_boundary() {
+    let content = CString::new("0 HEAD\nø NAME\n").unwrap();
+    let message = "line 2: expected a level number, found \"";
+    // room for the message and the first byte of the `ø` after it, along with the nul
+    let mut err_str = vec![1 as c_char; message.len() + 2];
+    let data = unsafe { parse(content.as_ptr(), err_str.as_mut_ptr(), err_str.len()) };
+    assert!(data.is_null());
+    let written = unsafe { CStr::from_ptr(err_str.as_ptr()) };
+    assert_eq!(written.to_str(), Ok(message));
+
+    // nothing is written without room for the nul
+    let mut err_str = [1 as c_char; 1];
+    let data = unsafe { parse(content.as_ptr(), err_str.as_mut_ptr(), 0) };
+    assert!(data.is_null());
+    assert_eq!(err_str[0], 1);
+}
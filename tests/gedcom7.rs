@@ -0,0 +1,90 @@
+//! GEDCOM 7.0 files are parsed into the same model as GEDCOM 5.5.1 files.
+use gedcom::parser::Parser;
+use gedcom::types::{event::HasEvents, GedcomDate, GedcomVersion, Gender, Pedigree};
+use gedcom::writer::GedcomWriter;
+use gedcom::DiagnosticKind;
+
+fn read_fixture() -> Vec<u8> {
+    std::fs::read("./tests/fixtures/gedcom7.ged").unwrap()
+}
+
+#[test]
+fn parses_gedcom7_structures() {
+    let original = read_fixture();
+    let mut parser = Parser::from_reader(&original[..]);
+    let data = parser.try_parse().unwrap();
+
+    assert_eq!(parser.version(), GedcomVersion::V7);
+    assert_eq!(data.header.version(), GedcomVersion::V7);
+    assert_eq!(data.header.extension_tags.len(), 2);
+    assert_eq!(data.header.extension_tags[0].tag, "_SKYPEID");
+    assert_eq!(
+        data.header.extension_tags[0].uri,
+        "http://xmlns.com/foaf/0.1/skypeID"
+    );
+
+    let lucia = &data.individuals[0];
+    assert_eq!(lucia.sex, Gender::Other);
+    let name = lucia.name().unwrap();
+    assert_eq!(
+        name.translations[0].value.as_deref(),
+        Some("Lucia /Fernandez/")
+    );
+    assert_eq!(name.translations[0].language.as_deref(), Some("en"));
+    let birth = &lucia.events()[0];
+    assert_eq!(birth.date, Some(GedcomDate::parse("1 JAN 1900")));
+    assert_eq!(birth.date_phrase.as_deref(), Some("New Year's Day, 1900"));
+    assert_eq!(
+        lucia.families[0].pedigree(),
+        Some(&Pedigree::Other(Some("Raised by her aunt".to_string())))
+    );
+    assert_eq!(lucia.external_ids[0].id, "123456");
+    assert_eq!(
+        lucia.external_ids[0].id_type.as_deref(),
+        Some("https://www.example.com/people")
+    );
+
+    // the father is not known
+    let family = &data.families[0];
+    assert_eq!(family.individual1, None);
+    assert_eq!(family.individual2.as_deref(), Some("@I2@"));
+
+    let note = &data.notes[0];
+    assert_eq!(note.xref.as_deref(), Some("@N1@"));
+    assert!(note.value.starts_with(
+        "@lucia is her handle on most sites, her email is lucia@example.com.\nThe second line"
+    ));
+    assert_eq!(note.mime.as_deref(), Some("text/plain"));
+    assert_eq!(note.translations[0].language.as_deref(), Some("es"));
+
+    let kinds: Vec<DiagnosticKind> = parser.diagnostics().iter().map(|d| d.kind).collect();
+    assert!(!kinds.contains(&DiagnosticKind::InvalidForVersion));
+}
+
+#[test]
+fn conc_is_reported_in_gedcom7() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @N1@ SNOTE Split\n1 CONC ted\n0 TRLR";
+    let mut parser = Parser::new(source.chars());
+    let data = parser.try_parse().unwrap();
+    assert_eq!(data.notes[0].value, "Splitted");
+    assert_eq!(
+        parser.diagnostics()[0].kind,
+        DiagnosticKind::InvalidForVersion
+    );
+}
+
+#[test]
+fn gedcom7_round_trip() {
+    let original = read_fixture();
+    let mut parser = Parser::from_reader(&original[..]);
+    let data = parser.try_parse().unwrap();
+
+    let mut output = Vec::new();
+    GedcomWriter::with_options(&mut output, parser.writer_options())
+        .write_data(&data)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        String::from_utf8(original).unwrap()
+    );
+}
@@ -0,0 +1,250 @@
+//! GEDCOM X JSON exported from parsed data, and parsed back into it.
+#![cfg(feature = "gedcomx")]
+use gedcom::gedcomx;
+use gedcom::types::{
+    event::HasEvents, EventType, FamilyLinkType, GedcomDate, Gender, Pedigree, Place,
+};
+use serde_json::json;
+
+const SOURCE: &str = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+0 @I1@ INDI
+1 NAME Karl /Weber/
+2 GIVN Karl
+2 SURN Weber
+1 SEX M
+1 BIRT
+2 DATE 4 MAY 1870
+2 PLAC Hamburg, Germany
+3 MAP
+4 LATI N53.55
+4 LONG E9.993
+2 SOUR @S1@
+3 PAGE folio 12
+0 @I2@ INDI
+1 NAME Greta /Weber/
+1 SEX F
+1 DEAT
+2 PLAC Hamburg, Germany
+0 @I3@ INDI
+1 NAME Hans /Weber/
+1 FAMC @F1@
+2 PEDI adopted
+0 @F1@ FAM
+1 HUSB @I1@
+1 WIFE @I2@
+1 CHIL @I3@
+1 MARR
+2 DATE 1895
+0 @S1@ SOUR
+1 TITL Hamburg civil registers
+1 AUTH Standesamt Hamburg
+1 TEXT Geboren den vierten Mai
+0 TRLR
+";
+
+#[test]
+fn exports_persons_relationships_sources_and_places() {
+    let data = gedcom::parse(SOURCE.chars());
+    let json: serde_json::Value =
+        serde_json::from_str(&gedcomx::to_string(&data).unwrap()).unwrap();
+
+    assert_eq!(
+        json["persons"][0],
+        json!({
+            "id": "I1",
+            "gender": { "type": "http://gedcomx.org/Male" },
+            "names": [{ "nameForms": [{
+                "fullText": "Karl Weber",
+                "parts": [
+                    { "type": "http://gedcomx.org/Given", "value": "Karl" },
+                    { "type": "http://gedcomx.org/Surname", "value": "Weber" },
+                ],
+            }] }],
+            "facts": [{
+                "type": "http://gedcomx.org/Birth",
+                "date": { "original": "4 MAY 1870" },
+                "place": { "original": "Hamburg, Germany", "description": "#P1" },
+                "sources": [{ "description": "#S1-1" }],
+            }],
+        })
+    );
+    assert_eq!(
+        json["relationships"],
+        json!([
+            {
+                "id": "F1",
+                "type": "http://gedcomx.org/Couple",
+                "person1": { "resource": "#I1" },
+                "person2": { "resource": "#I2" },
+                "facts": [{ "type": "http://gedcomx.org/Marriage", "date": { "original": "1895" } }],
+            },
+            {
+                "type": "http://gedcomx.org/ParentChild",
+                "person1": { "resource": "#I1" },
+                "person2": { "resource": "#I3" },
+                "facts": [{ "type": "http://gedcomx.org/AdoptiveParent" }],
+            },
+            {
+                "type": "http://gedcomx.org/ParentChild",
+                "person1": { "resource": "#I2" },
+                "person2": { "resource": "#I3" },
+                "facts": [{ "type": "http://gedcomx.org/AdoptiveParent" }],
+            },
+        ])
+    );
+    assert_eq!(
+        json["sourceDescriptions"],
+        json!([
+            {
+                "id": "S1",
+                "citations": [{ "value": "Standesamt Hamburg. Hamburg civil registers" }],
+                "titles": [{ "value": "Hamburg civil registers" }],
+                "notes": [{ "text": "Geboren den vierten Mai" }],
+            },
+            {
+                "id": "S1-1",
+                "citations": [{ "value": "folio 12" }],
+                "componentOf": { "description": "#S1" },
+            },
+        ])
+    );
+    // both events in Hamburg share one description of it
+    assert_eq!(
+        json["places"],
+        json!([{
+            "id": "P1",
+            "names": [{ "value": "Hamburg, Germany" }],
+            "latitude": 53.55,
+            "longitude": 9.993,
+        }])
+    );
+}
+
+#[test]
+fn round_trips_through_gedcomx() {
+    let data = gedcom::parse(SOURCE.chars());
+    let imported = gedcomx::from_str(&gedcomx::to_string(&data).unwrap()).unwrap();
+
+    let karl = &imported.individuals[0];
+    assert_eq!(karl.xref.as_deref(), Some("@I1@"));
+    assert_eq!(karl.sex, Gender::Male);
+    let name = karl.name().unwrap();
+    assert_eq!(name.value.as_deref(), Some("Karl /Weber/"));
+    assert_eq!(name.given.as_deref(), Some("Karl"));
+    let birth = &karl.events()[0];
+    assert_eq!(birth.event, EventType::Birth);
+    let place = birth.place.as_ref().unwrap();
+    assert_eq!(place.value, "Hamburg, Germany");
+    assert_eq!(
+        (place.latitude, place.longitude),
+        (Some(53.55), Some(9.993))
+    );
+    assert_eq!(birth.citations[0].xref, "@S1@");
+    assert_eq!(birth.citations[0].page.as_deref(), Some("folio 12"));
+
+    let family = &imported.families[0];
+    assert_eq!(family.xref.as_deref(), Some("@F1@"));
+    assert_eq!(family.individual1.as_deref(), Some("@I1@"));
+    assert_eq!(family.individual2.as_deref(), Some("@I2@"));
+    assert_eq!(family.children, vec!["@I3@".to_string()]);
+    assert_eq!(family.events()[0].date, Some(GedcomDate::parse("1895")));
+
+    let link = &imported.individuals[2].families[0];
+    assert_eq!(link.xref(), "@F1@");
+    assert_eq!(link.link_type(), &FamilyLinkType::Child);
+    assert_eq!(link.pedigree(), Some(&Pedigree::Adopted));
+
+    assert_eq!(imported.sources.len(), 1);
+    assert_eq!(
+        imported.sources[0].title.as_deref(),
+        Some("Hamburg civil registers")
+    );
+}
+
+#[test]
+fn imports_children_of_a_single_parent() {
+    let json = r##"{
+        "persons": [
+            { "id": "p1", "names": [{ "nameForms": [{ "fullText": "Ida Lind" }] }] },
+            { "id": "p2", "facts": [{ "type": "http://gedcomx.org/Birth", "place": { "description": "#pl1" } }] },
+            { "id": "p3", "facts": [{ "type": "http://gedcomx.org/Stillbirth" }] }
+        ],
+        "relationships": [
+            { "type": "http://gedcomx.org/ParentChild", "person1": { "resource": "#p1" }, "person2": { "resource": "#p2" } },
+            { "type": "http://gedcomx.org/ParentChild", "person1": { "resource": "#p1" }, "person2": { "resource": "#p3" } }
+        ],
+        "places": [{ "id": "pl1", "names": [{ "value": "Uppsala" }] }]
+    }"##;
+    let data = gedcomx::from_str(json).unwrap();
+
+    assert_eq!(
+        data.individuals[0].name().unwrap().value.as_deref(),
+        Some("Ida Lind")
+    );
+    assert_eq!(
+        data.individuals[1].events()[0].place,
+        Some(Place::new("Uppsala"))
+    );
+    // facts of a type without a counterpart are left out
+    assert!(data.individuals[2].events().is_empty());
+
+    assert_eq!(data.families.len(), 1);
+    let family = &data.families[0];
+    assert_eq!(family.individual1.as_deref(), Some("@p1@"));
+    assert_eq!(family.individual2, None);
+    assert_eq!(
+        family.children,
+        vec!["@p2@".to_string(), "@p3@".to_string()]
+    );
+    assert_eq!(
+        data.individuals[0].families[0].link_type(),
+        &FamilyLinkType::Spouse
+    );
+}
+
+#[test]
+fn gives_families_of_a_single_parent_a_free_xref() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n0 @I2@ INDI\n0 @I3@ INDI\n\
+        0 @I4@ INDI\n0 @I5@ INDI\n0 @I6@ INDI\n0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n\
+        0 @F2@ FAM\n1 WIFE @I5@\n1 CHIL @I6@\n0 @F3@ FAM\n1 HUSB @I3@\n1 WIFE @I4@\n0 TRLR\n";
+    let data = gedcom::parse(source.chars());
+    let imported = gedcomx::from_str(&gedcomx::to_string(&data).unwrap()).unwrap();
+
+    let mut xrefs: Vec<&str> = imported
+        .families
+        .iter()
+        .map(|family| family.xref.as_deref().unwrap())
+        .collect();
+    xrefs.sort_unstable();
+    assert_eq!(xrefs, vec!["@F1@", "@F2@", "@F3@"]);
+    let single = imported
+        .families
+        .iter()
+        .find(|family| family.individual1.as_deref() == Some("@I5@"))
+        .unwrap();
+    assert_eq!(single.xref.as_deref(), Some("@F2@"));
+    assert_eq!(single.children, vec!["@I6@".to_string()]);
+    assert_eq!(imported.individuals[5].families[0].xref(), "@F2@");
+}
+
+#[test]
+fn exports_a_sex_gedcomx_lacks_as_an_unknown_gender() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    data.individuals[0].sex = Gender::Nonbinary;
+    data.individuals[1].sex = Gender::Other;
+    let json = gedcomx::to_string(&data).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    for person in 0..2 {
+        assert_eq!(
+            value["persons"][person]["gender"],
+            json!({ "type": "http://gedcomx.org/Unknown" })
+        );
+    }
+
+    let imported = gedcomx::from_str(&json).unwrap();
+    assert_eq!(imported.individuals[0].sex, Gender::Unknown);
+    assert_eq!(imported.individuals[1].sex, Gender::Unknown);
+}
@@ -0,0 +1,74 @@
+//! The header tells which program wrote a file, along with its character set.
+use gedcom::parser::Parser;
+use gedcom::types::Corporation;
+
+fn read_fixture(name: &str) -> Vec<u8> {
+    std::fs::read(format!("./tests/fixtures/{}", name)).unwrap()
+}
+
+#[test]
+fn reads_the_program_that_wrote_a_file() {
+    let rootsmagic = read_fixture("rootsmagic.ged");
+    let data = Parser::from_reader(rootsmagic.as_slice())
+        .try_parse()
+        .unwrap();
+    let source = data.header.source.unwrap();
+    assert_eq!(source.system_id, "RootsMagic");
+    assert_eq!(source.version.as_deref(), Some("8.0"));
+    assert_eq!(source.name.as_deref(), Some("RootsMagic"));
+    let corporation = source.corporation.unwrap();
+    assert_eq!(corporation.name, "RootsMagic, Inc.");
+    assert_eq!(
+        corporation.address.unwrap().value.as_deref(),
+        Some("PO Box 495\nSpringville, UT 84663\nUSA")
+    );
+    assert_eq!(corporation.phones, vec!["1-800-ROOTSMAGIC"]);
+
+    let ancestry = read_fixture("ancestry.ged");
+    let data = Parser::from_reader(ancestry.as_slice())
+        .try_parse()
+        .unwrap();
+    let source = data.header.source.unwrap();
+    assert_eq!(source.system_id, "Ancestry.com Family Trees");
+    assert_eq!(source.version.as_deref(), Some("2021.07"));
+    assert_eq!(
+        source.corporation,
+        Some(Corporation {
+            name: "Ancestry.com".to_string(),
+            ..Corporation::default()
+        })
+    );
+}
+
+#[test]
+fn reads_the_source_data_and_character_set_version() {
+    let source = "0 HEAD\n1 SOUR PAF\n2 VERS 5.2\n2 DATA Ancestral File\n3 DATE 1 JAN 1998\n\
+        3 COPR Copyright 1998 by\n4 CONT Intellectual Reserve\n1 GEDC\n2 VERS 5.5.1\n\
+        2 FORM LINEAGE-LINKED\n1 CHAR ANSEL\n2 VERS 1.0\n0 TRLR\n";
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(source.chars()).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    let header = data.header;
+    assert_eq!(header.encoding.as_deref(), Some("ANSEL"));
+    assert_eq!(header.encoding_version.as_deref(), Some("1.0"));
+
+    let data = header.source.unwrap().data.unwrap();
+    assert_eq!(data.name, "Ancestral File");
+    assert_eq!(data.date.unwrap().original, "1 JAN 1998");
+    assert_eq!(
+        data.copyright.as_deref(),
+        Some("Copyright 1998 by\nIntellectual Reserve")
+    );
+}
+
+#[test]
+fn reads_the_source_data_of_gedcom7() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n1 SOUR https://gedcom.io/\n2 DATA Sample data\n\
+        3 DATE 2 JUN 2021\n4 TIME 12:30\n0 TRLR\n";
+    let data = gedcom::parse(source.chars());
+    let source = data.header.source.unwrap();
+    assert_eq!(source.system_id, "https://gedcom.io/");
+    assert_eq!(
+        source.data.map(|data| (data.name, data.time)),
+        Some(("Sample data".to_string(), Some("12:30".to_string())))
+    );
+}
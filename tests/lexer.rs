@@ -0,0 +1,92 @@
+//! The lexer borrows its tokens from the input, along with where they were found.
+use gedcom::lexer::{Lexer, Token};
+use gedcom::tokenizer::{self, Tokenizer};
+use gedcom::ParseError;
+
+/// The tokens of `source` with their text, as found at their spans
+fn tokens_with_text(source: &str) -> Vec<(Token<'_>, &str, u32)> {
+    Lexer::new(source)
+        .map(|spanned| {
+            let spanned = spanned.unwrap();
+            (spanned.token, &source[spanned.span], spanned.line)
+        })
+        .collect()
+}
+
+#[test]
+fn spans_the_text_of_each_token() {
+    let source = "0 @I1@ INDI\n1 _UID 42\n1 NAME Anna /Berg/\n";
+    assert_eq!(
+        tokens_with_text(source),
+        vec![
+            (Token::Level(0), "0", 1),
+            (Token::Pointer("@I1@"), "@I1@", 1),
+            (Token::Tag("INDI"), "INDI", 1),
+            (Token::Level(1), "1", 2),
+            (Token::CustomTag("_UID"), "_UID", 2),
+            (Token::LineValue("42"), "42", 2),
+            (Token::Level(1), "1", 3),
+            (Token::Tag("NAME"), "NAME", 3),
+            (Token::LineValue("Anna /Berg/"), "Anna /Berg/", 3),
+        ]
+    );
+}
+
+#[test]
+fn reads_every_line_ending_and_skips_blank_lines() {
+    let source = "0 HEAD\r\n\r\n1 CHAR UTF-8\r2 VERS 1.0\n  \n0 TRLR";
+    let found: Vec<(Token<'_>, u32)> = tokens_with_text(source)
+        .into_iter()
+        .map(|(token, _, line)| (token, line))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Token::Level(0), 1),
+            (Token::Tag("HEAD"), 1),
+            (Token::Level(1), 3),
+            (Token::Tag("CHAR"), 3),
+            (Token::LineValue("UTF-8"), 3),
+            (Token::Level(2), 4),
+            (Token::Tag("VERS"), 4),
+            (Token::LineValue("1.0"), 4),
+            (Token::Level(0), 6),
+            (Token::Tag("TRLR"), 6),
+        ]
+    );
+}
+
+#[test]
+fn fails_on_a_line_without_a_level() {
+    let mut lexer = Lexer::new("0 HEAD\nHEAD\n");
+    assert_eq!(lexer.next().unwrap().unwrap().token, Token::Level(0));
+    assert_eq!(lexer.next().unwrap().unwrap().token, Token::Tag("HEAD"));
+    assert_eq!(
+        lexer.next().unwrap().unwrap_err(),
+        ParseError::InvalidLevel {
+            line: 2,
+            found: "HEAD".to_string(),
+            xref: None,
+        }
+    );
+}
+
+#[test]
+fn gives_the_same_tokens_as_the_owned_tokenizer() {
+    let source = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
+    let borrowed: Vec<tokenizer::Token> = Lexer::new(&source)
+        .map(|spanned| spanned.unwrap().token.into())
+        .collect();
+
+    let mut owned = Vec::new();
+    let mut tokenizer = Tokenizer::new(source.chars());
+    tokenizer.next_token().unwrap();
+    while !tokenizer.done() {
+        owned.push(std::mem::replace(
+            &mut tokenizer.current_token,
+            tokenizer::Token::None,
+        ));
+        tokenizer.next_token().unwrap();
+    }
+    assert_eq!(borrowed, owned);
+}
@@ -0,0 +1,124 @@
+//! Multimedia records and the objects linked to or embedded in records and events.
+use gedcom::types::{event::HasEvents, MediaFile, MediaLink, NoteStructure, UserReference};
+use gedcom::writer::GedcomWriter;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Eir /Lund/\n\
+    1 OBJE @M1@\n1 BIRT\n2 DATE 1901\n2 OBJE\n3 FILE birth.jpg\n4 FORM jpg\n5 MEDI photo\n\
+    3 TITL Baptism photo\n0 @F1@ FAM\n1 WIFE @I1@\n1 OBJE @VOID@\n1 OBJE\n2 FILE wedding.png\n\
+    0 @S1@ SOUR\n1 TITL Census 1910\n1 OBJE @M2@\n\
+    0 @M1@ OBJE\n1 FILE portraits/eir.jpg\n2 FORM jpg\n3 TYPE photo\n2 TITL Eir in 1925\n\
+    1 FILE portraits/eir-back.jpg\n1 REFN 1925-04\n2 TYPE album\n1 RIN 88\n1 NOTE Taken in Oslo\n\
+    1 SOUR @S1@\n1 CHAN\n2 DATE 4 JUL 2020\n0 @M2@ OBJE\n1 FILE https://example.org/census.pdf\n\
+    2 FORM pdf\n0 TRLR\n";
+
+#[test]
+fn parses_multimedia_records() {
+    let data = gedcom::parse(SOURCE.chars());
+    assert_eq!(data.multimedia.len(), 2);
+    let portrait = data.media("@M1@").unwrap();
+    assert_eq!(
+        portrait.files,
+        vec![
+            MediaFile {
+                reference: "portraits/eir.jpg".to_string(),
+                format: Some("jpg".to_string()),
+                media_type: Some("photo".to_string()),
+                title: Some("Eir in 1925".to_string()),
+            },
+            MediaFile {
+                reference: "portraits/eir-back.jpg".to_string(),
+                ..MediaFile::default()
+            },
+        ]
+    );
+    assert_eq!(
+        portrait.user_references,
+        vec![UserReference {
+            number: "1925-04".to_string(),
+            reference_type: Some("album".to_string()),
+        }]
+    );
+    assert_eq!(portrait.record_id.as_deref(), Some("88"));
+    assert_eq!(
+        portrait.notes,
+        vec![NoteStructure::Text("Taken in Oslo".to_string())]
+    );
+    assert_eq!(portrait.citations[0].xref, "@S1@");
+    assert_eq!(
+        portrait.last_updated.as_ref().unwrap().original,
+        "4 JUL 2020"
+    );
+}
+
+#[test]
+fn links_and_embeds_multimedia() {
+    let data = gedcom::parse(SOURCE.chars());
+    let individual = &data.individuals[0];
+    assert_eq!(
+        individual.media,
+        vec![MediaLink::Shared("@M1@".to_string())]
+    );
+    let portrait = data.linked_media(&individual.media[0]).unwrap();
+    assert_eq!(portrait.files[0].reference, "portraits/eir.jpg");
+
+    // the title of a GEDCOM 5.5.1 embedded object is that of its file
+    let birth = &individual.events()[0];
+    let embedded = data.linked_media(&birth.media[0]).unwrap();
+    assert_eq!(
+        embedded.files,
+        vec![MediaFile {
+            reference: "birth.jpg".to_string(),
+            format: Some("jpg".to_string()),
+            media_type: Some("photo".to_string()),
+            title: Some("Baptism photo".to_string()),
+        }]
+    );
+
+    // a void pointer is dropped
+    let family = &data.families[0];
+    assert_eq!(family.media.len(), 1);
+    assert!(matches!(&family.media[0], MediaLink::Embedded(media) if media.xref.is_none()));
+
+    let scan = data.linked_media(&data.sources[0].media[0]).unwrap();
+    assert_eq!(scan.files[0].format.as_deref(), Some("pdf"));
+    assert_eq!(
+        data.linked_media(&MediaLink::Shared("@M9@".to_string())),
+        None
+    );
+}
+
+#[test]
+fn writes_multimedia_of_edited_records() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    data.individuals[0].node = None;
+    data.families[0].node = None;
+    data.sources[0].node = None;
+    for media in &mut data.multimedia {
+        media.node = None;
+    }
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written
+        .contains("2 OBJE\n3 FILE birth.jpg\n4 FORM jpg\n5 MEDI photo\n3 TITL Baptism photo\n"));
+    assert!(written.contains(
+        "0 @M1@ OBJE\n1 FILE portraits/eir.jpg\n2 FORM jpg\n3 TYPE photo\n2 TITL Eir in 1925\n\
+         1 FILE portraits/eir-back.jpg\n1 REFN 1925-04\n2 TYPE album\n1 RIN 88\n\
+         1 NOTE Taken in Oslo\n1 SOUR @S1@\n1 CHAN\n2 DATE 4 JUL 2020\n"
+    ));
+    assert!(written.contains("1 OBJE @M1@\n"));
+
+    let reparsed = gedcom::parse(written.chars());
+    let (individual, original) = (&reparsed.individuals[0], &data.individuals[0]);
+    assert_eq!(individual.media, original.media);
+    assert_eq!(individual.events(), original.events());
+    assert_eq!(reparsed.families[0].media, data.families[0].media);
+    assert_eq!(reparsed.sources[0].media, data.sources[0].media);
+    for (media, original) in reparsed.multimedia.iter().zip(&data.multimedia) {
+        assert_eq!(media.files, original.files);
+        assert_eq!(media.user_references, original.user_references);
+        assert_eq!(media.notes, original.notes);
+        assert_eq!(media.last_updated, original.last_updated);
+    }
+}
@@ -0,0 +1,126 @@
+//! An individual keeps every `NAME`, with its type, pieces, notes, sources and variations.
+use gedcom::types::{
+    Individual, Name, NamePieces, NameType, NameVariant, NoteStructure, SourceCitation,
+};
+use gedcom::writer::GedcomWriter;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n\
+    1 NAME Hanako /Yamada/\n2 TYPE birth\n2 NICK Hana\n2 NOTE Registered at the\n3 CONT village office\n\
+    2 SOUR @S1@\n3 PAGE Koseki, entry 12\n2 FONE はなこ /やまだ/\n3 TYPE kana\n3 GIVN はなこ\n\
+    2 ROMN Hanako /Yamada/\n3 TYPE hepburn\n\
+    1 NAME Hanako /Suzuki/\n2 TYPE married\n2 GIVN Hanako\n2 SURN Suzuki\n\
+    1 NAME Hana\n2 TYPE stage name\n0 TRLR\n";
+
+#[test]
+fn keeps_every_name() {
+    let data = gedcom::parse(SOURCE.chars());
+    let individual = &data.individuals[0];
+    assert_eq!(individual.names.len(), 3);
+    assert_eq!(individual.name(), individual.names.first());
+
+    let birth = &individual.names[0];
+    assert_eq!(birth.name_type, Some(NameType::Birth));
+    assert_eq!(birth.nickname.as_deref(), Some("Hana"));
+    assert_eq!(
+        birth.notes,
+        vec![NoteStructure::Text(
+            "Registered at the\nvillage office".to_string()
+        )]
+    );
+    assert_eq!(
+        birth.citations,
+        vec![SourceCitation {
+            xref: "@S1@".to_string(),
+            page: Some("Koseki, entry 12".to_string()),
+            quality: None,
+            notes: Vec::new(),
+        }]
+    );
+    assert_eq!(
+        birth.phonetic,
+        vec![NameVariant {
+            name: Name {
+                value: Some("はなこ /やまだ/".to_string()),
+                given: Some("はなこ".to_string()),
+                ..Name::default()
+            },
+            variant_type: Some("kana".to_string()),
+        }]
+    );
+    assert_eq!(birth.romanized[0].variant_type.as_deref(), Some("hepburn"));
+
+    assert_eq!(individual.names[1].name_type, Some(NameType::Married));
+    // GEDCOM 5.5.1 lets the type be any text
+    assert_eq!(
+        individual.names[2].name_type,
+        Some(NameType::Other(Some("stage name".to_string())))
+    );
+}
+
+#[test]
+fn takes_the_pieces_of_a_name_from_its_slashes() {
+    let name = |value: &str| Name {
+        value: Some(value.to_string()),
+        ..Name::default()
+    };
+    assert_eq!(
+        name("John /Smith/ Jr.").pieces(),
+        NamePieces {
+            given: Some("John"),
+            surname: Some("Smith"),
+            suffix: Some("Jr."),
+        }
+    );
+    assert_eq!(
+        name("/Smith/").pieces(),
+        NamePieces {
+            given: None,
+            surname: Some("Smith"),
+            suffix: None,
+        }
+    );
+    // without slashes, the whole name is given
+    assert_eq!(name("Hana").pieces().given, Some("Hana"));
+    assert_eq!(name("Hana").pieces().surname, None);
+
+    // pieces on their own lines come first
+    let mut tagged = name("Mary Ann /Smith/");
+    tagged.given = Some("Mary".to_string());
+    assert_eq!(tagged.pieces().given, Some("Mary"));
+    assert_eq!(tagged.pieces().surname, Some("Smith"));
+}
+
+#[test]
+fn reads_the_name_types_of_gedcom7() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME Jo /Lee/\n2 TYPE PROFESSIONAL\n\
+        1 NAME Joey /Lee/\n2 TYPE OTHER\n3 PHRASE Childhood name\n0 TRLR\n";
+    let data = gedcom::parse(source.chars());
+    let names = &data.individuals[0].names;
+    assert_eq!(names[0].name_type, Some(NameType::Professional));
+    assert_eq!(
+        names[1].name_type,
+        Some(NameType::Other(Some("Childhood name".to_string())))
+    );
+}
+
+#[test]
+fn writes_every_name() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    let mut individual = Individual::new(Some("@I2@".to_string()));
+    // a record without a node is written from its typed data
+    individual.names = data.individuals.remove(0).names;
+    data.add_individual(individual);
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains(
+        "1 NAME Hanako /Yamada/\n2 TYPE birth\n2 NICK Hana\n2 NOTE Registered at the\n\
+         3 CONT village office\n2 SOUR @S1@\n3 PAGE Koseki, entry 12\n\
+         2 FONE はなこ /やまだ/\n3 TYPE kana\n3 GIVN はなこ\n2 ROMN Hanako /Yamada/\n3 TYPE hepburn\n"
+    ));
+    assert!(written.contains("1 NAME Hana\n2 TYPE stage name\n"));
+
+    let reparsed = gedcom::parse(written.chars());
+    assert_eq!(reparsed.individuals[0].names, data.individuals[0].names);
+}
@@ -0,0 +1,69 @@
+//! Every line is kept in a tree of nodes, beneath the typed records built from it.
+use gedcom::parser::Parser;
+use gedcom::GedcomNode;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Eva /Strand/\n\
+    2 _RUFNAME Eva\n1 BIRT\n2 DATE 1901\n2 _PHOTO birth.jpg\n3 _SIZE large\n1 ASSO @I2@\n\
+    2 RELA Godfather\n0 @X1@ _PLACE Tromsø\n1 _MAP here\n0 TRLR\n";
+
+#[test]
+fn reads_lines_into_a_tree() {
+    let mut parser = Parser::new(SOURCE.chars());
+    let nodes: Vec<GedcomNode> = parser.nodes().map(Result::unwrap).collect();
+    let tags: Vec<&str> = nodes.iter().map(|node| node.tag.as_str()).collect();
+    assert_eq!(tags, vec!["HEAD", "INDI", "_PLACE", "TRLR"]);
+
+    let individual = &nodes[1];
+    assert_eq!(
+        (
+            individual.level,
+            individual.xref.as_deref(),
+            individual.line
+        ),
+        (0, Some("@I1@"), 4)
+    );
+    let birth = individual.child("BIRT").unwrap();
+    assert_eq!(birth.level, 1);
+    let photo = birth.child("_PHOTO").unwrap();
+    assert!(photo.is_custom());
+    assert_eq!(photo.value.as_deref(), Some("birth.jpg"));
+    assert_eq!(photo.children[0].tag, "_SIZE");
+    assert_eq!(photo.children[0].level, 3);
+    assert_eq!(photo.children[0].line, 10);
+
+    let mut lines = 0;
+    individual.walk(&mut |_| lines += 1);
+    assert_eq!(lines, 9);
+}
+
+#[test]
+fn keeps_every_line_on_the_typed_records() {
+    let data = gedcom::parse(SOURCE.chars());
+    let node = data.individuals[0].node.as_ref().unwrap();
+    // the lines the typed parser skips are still in the node
+    let association = node.child("ASSO").unwrap();
+    assert_eq!(association.value.as_deref(), Some("@I2@"));
+    assert_eq!(
+        association.child("RELA").unwrap().value.as_deref(),
+        Some("Godfather")
+    );
+    let name = node.children_with_tag("NAME").next().unwrap();
+    assert_eq!(name.children[0].tag, "_RUFNAME");
+
+    // as are unhandled records
+    let place = &data.custom_data[0];
+    assert_eq!(place.tag, "_PLACE");
+    assert_eq!(place.value.as_deref(), Some("Tromsø"));
+    assert_eq!(place.children[0].tag, "_MAP");
+}
+
+#[test]
+fn builds_nodes_by_hand() {
+    let mut node = GedcomNode::new(0, "INDI");
+    node.push_child("BIRT", None)
+        .push_child("DATE", Some("1901".to_string()));
+    let date = &node.child("BIRT").unwrap().children[0];
+    assert_eq!((date.level, date.tag.as_str()), (2, "DATE"));
+    // nodes built by hand have no line
+    assert_eq!(date.line, 0);
+}
@@ -0,0 +1,111 @@
+//! Notes are kept on every structure that may have them, written in place or shared.
+use gedcom::types::{event::HasEvents, NoteStructure};
+use gedcom::writer::GedcomWriter;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Liv /Dahl/\n2 NOTE @N2@\n\
+    1 BIRT\n2 DATE 1850\n2 SOUR @S1@\n3 PAGE 14\n3 NOTE Hard to read\n1 NOTE Emigrated with her\n\
+    2 CONT two brothers in 18\n2 CONC 82.\n1 NOTE @N1@\n0 @F1@ FAM\n1 HUSB @I1@\n1 NOTE @N1@\n\
+    0 @S1@ SOUR\n1 TITL Parish register\n1 NOTE Microfilm only\n0 @R1@ REPO\n1 NAME Archive\n\
+    1 NOTE Closed on Mondays\n0 @N1@ NOTE The family is\n1 CONT also in the 1900 census.\n\
+    0 @N2@ NOTE Spelled Dal in some records\n0 TRLR\n";
+
+#[test]
+fn keeps_notes_on_every_structure() {
+    let data = gedcom::parse(SOURCE.chars());
+    let individual = &data.individuals[0];
+    assert_eq!(
+        individual.notes,
+        vec![
+            NoteStructure::Text("Emigrated with her\ntwo brothers in 1882.".to_string()),
+            NoteStructure::Shared("@N1@".to_string()),
+        ]
+    );
+    assert_eq!(
+        individual.names[0].notes,
+        vec![NoteStructure::Shared("@N2@".to_string())]
+    );
+    let birth = &individual.events()[0];
+    assert_eq!(
+        birth.citations[0].notes,
+        vec![NoteStructure::Text("Hard to read".to_string())]
+    );
+    assert_eq!(
+        data.families[0].notes,
+        vec![NoteStructure::Shared("@N1@".to_string())]
+    );
+    assert_eq!(
+        data.sources[0].notes,
+        vec![NoteStructure::Text("Microfilm only".to_string())]
+    );
+    assert_eq!(
+        data.repositories[0].notes,
+        vec![NoteStructure::Text("Closed on Mondays".to_string())]
+    );
+    assert_eq!(data.notes.len(), 2);
+}
+
+#[test]
+fn resolves_the_text_of_notes() {
+    let data = gedcom::parse(SOURCE.chars());
+    let notes = &data.individuals[0].notes;
+    assert_eq!(
+        data.note_text(&notes[0]),
+        Some("Emigrated with her\ntwo brothers in 1882.")
+    );
+    assert_eq!(
+        data.note_text(&notes[1]),
+        Some("The family is\nalso in the 1900 census.")
+    );
+    assert_eq!(
+        data.note("@N2@").map(|note| note.value.as_str()),
+        Some("Spelled Dal in some records")
+    );
+    // a pointer to a missing record has no text
+    let missing = NoteStructure::Shared("@N9@".to_string());
+    assert_eq!(data.note_text(&missing), None);
+}
+
+#[test]
+fn reads_the_shared_notes_of_gedcom7() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 SNOTE @N1@\n1 SNOTE @VOID@\n\
+        1 NOTE Kept in place\n0 @N1@ SNOTE Shared\n0 TRLR\n";
+    let data = gedcom::parse(source.chars());
+    let notes = &data.individuals[0].notes;
+    // a void pointer is dropped
+    assert_eq!(
+        notes,
+        &vec![
+            NoteStructure::Shared("@N1@".to_string()),
+            NoteStructure::Text("Kept in place".to_string()),
+        ]
+    );
+    assert_eq!(data.note_text(&notes[0]), Some("Shared"));
+}
+
+#[test]
+fn writes_notes_of_edited_records() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    data.individuals[0].node = None;
+    data.families[0].node = None;
+    data.sources[0].node = None;
+    data.repositories[0].node = None;
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("1 NAME Liv /Dahl/\n2 NOTE @N2@\n"));
+    assert!(written.contains("2 SOUR @S1@\n3 PAGE 14\n3 NOTE Hard to read\n"));
+    assert!(
+        written.contains("1 NOTE Emigrated with her\n2 CONT two brothers in 1882.\n1 NOTE @N1@\n")
+    );
+    assert!(written.contains("0 @R1@ REPO\n1 NAME Archive\n1 NOTE Closed on Mondays\n"));
+
+    let reparsed = gedcom::parse(written.chars());
+    let (individual, original) = (&reparsed.individuals[0], &data.individuals[0]);
+    assert_eq!(individual.notes, original.notes);
+    assert_eq!(individual.names, original.names);
+    assert_eq!(individual.events(), original.events());
+    assert_eq!(reparsed.families[0].notes, data.families[0].notes);
+    assert_eq!(reparsed.sources[0].notes, data.sources[0].notes);
+    assert_eq!(reparsed.repositories[0].notes, data.repositories[0].notes);
+}
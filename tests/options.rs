@@ -0,0 +1,190 @@
+//! Strict parsing rejects what lenient parsing recovers from, and each option can be set
+//! on its own.
+use gedcom::parser::{Parser, ParserOptions, ValuePolicy};
+use gedcom::types::{CustomData, Gender, Pedigree};
+use gedcom::{DiagnosticKind, GedcomData, ParseError, Severity};
+
+const HEAD: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n";
+
+/// The line, kind and severity of a diagnostic
+type Reported = (u32, DiagnosticKind, Severity);
+
+/// Parses `records` after a 5.5.1 header, returning the data along with the diagnostics
+fn parse(records: &str, options: ParserOptions) -> Result<(GedcomData, Vec<Reported>), ParseError> {
+    let source = format!("{HEAD}{records}0 TRLR\n");
+    let mut parser = Parser::with_options(source.chars(), options);
+    let data = parser.try_parse()?;
+    let diagnostics = parser
+        .take_diagnostics()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.line, diagnostic.kind, diagnostic.severity))
+        .collect();
+    Ok((data, diagnostics))
+}
+
+#[test]
+fn values_outside_of_the_grammar_are_errors() {
+    let cases = [
+        (
+            "0 @I1@ INDI\n1 BIRT\n2 DATE sometime in spring\n",
+            6,
+            "DATE",
+        ),
+        ("0 @I1@ INDI\n1 DEAT\n2 AGE about forty\n", 6, "AGE"),
+        (
+            "0 @I1@ INDI\n1 BIRT\n2 PLAC Oslo\n3 MAP\n4 LATI 59.91\n4 LONG E10.75\n",
+            8,
+            "LATI",
+        ),
+        ("0 @SUBN1@ SUBN\n1 ANCE many\n", 5, "ANCE"),
+        ("0 @SUBN1@ SUBN\n1 ORDI maybe\n", 5, "ORDI"),
+    ];
+    for (records, line, tag) in cases {
+        let (_, diagnostics) = parse(records, ParserOptions::lenient()).unwrap();
+        assert_eq!(
+            diagnostics,
+            vec![(line, DiagnosticKind::UnrecognizedValue, Severity::Error)],
+            "{}",
+            tag
+        );
+
+        let error = parse(records, ParserOptions::strict()).unwrap_err();
+        assert!(matches!(error, ParseError::Nonconforming(_)), "{}", tag);
+        assert_eq!((error.line(), error.tag()), (line, Some(tag)));
+    }
+}
+
+#[test]
+fn strict_parsing_rejects_unknown_tags() {
+    let records = "0 @S1@ SOUR\n1 FOO bar\n1 _FOO bar\n";
+
+    let (data, diagnostics) = parse(records, ParserOptions::lenient()).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (5, DiagnosticKind::InvalidTag, Severity::Error),
+            (6, DiagnosticKind::SkippedCustomTag, Severity::Warning),
+        ]
+    );
+    assert_eq!(data.sources.len(), 1);
+
+    let error = parse(records, ParserOptions::strict()).unwrap_err();
+    assert_eq!(
+        (error.line(), error.tag(), error.xref()),
+        (5, Some("FOO"), Some("@S1@"))
+    );
+
+    // user-defined tags are allowed by the specification, and only reported
+    let (_, diagnostics) = parse("0 @S1@ SOUR\n1 _FOO bar\n", ParserOptions::strict()).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![(5, DiagnosticKind::SkippedCustomTag, Severity::Warning)]
+    );
+
+    let error = parse("0 FOO bar\n", ParserOptions::strict()).unwrap_err();
+    assert_eq!((error.line(), error.tag()), (4, Some("FOO")));
+}
+
+#[test]
+fn strict_parsing_accepts_tags_the_parser_skips() {
+    let records = "0 @I1@ INDI\n1 RIN 12\n0 @F1@ FAM\n1 CHAN\n2 DATE 1 JAN 2000\n";
+    let (_, diagnostics) = parse(records, ParserOptions::strict()).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (5, DiagnosticKind::UnhandledTag, Severity::Warning),
+            (7, DiagnosticKind::UnhandledTag, Severity::Warning),
+        ]
+    );
+
+    for fixture in ["gramps", "rootsmagic", "ftm", "reunion"] {
+        let source = std::fs::read_to_string(format!("./tests/fixtures/{fixture}.ged")).unwrap();
+        let mut parser = Parser::with_options(source.chars(), ParserOptions::strict());
+        assert!(parser.try_parse().is_ok(), "{}", fixture);
+    }
+}
+
+#[test]
+fn keeps_a_header_time_without_a_date_when_not_an_error() {
+    let source = "0 HEAD\n1 TIME 12:00\n1 DATE 1 JAN 2000\n0 TRLR\n";
+    let error = gedcom::try_parse(source.chars()).unwrap_err();
+    assert_eq!(error, ParseError::TimeWithoutDate { line: 2 });
+
+    let mut parser = Parser::with_options(source.chars(), ParserOptions::lenient());
+    let data = parser.try_parse().unwrap();
+    assert_eq!(data.header.time.as_deref(), Some("12:00"));
+    assert_eq!(parser.diagnostics()[0].kind, DiagnosticKind::InvalidTag);
+}
+
+#[test]
+fn keeps_unknown_tags_when_asked() {
+    let records = "0 @I1@ INDI\n1 ANCI @U1@\n0 @F1@ FAM\n1 ASSO @I3@\n";
+    let (data, _) = parse(records, ParserOptions::default()).unwrap();
+    assert!(data.individuals[0].custom_data.is_empty());
+
+    let (data, diagnostics) = parse(records, ParserOptions::lenient()).unwrap();
+    assert_eq!(
+        data.individuals[0].custom_data,
+        vec![CustomData {
+            tag: "ANCI".to_string(),
+            value: "@U1@".to_string(),
+        }]
+    );
+    assert_eq!(data.families[0].custom_data.len(), 1);
+    // they are reported either way
+    assert_eq!(diagnostics.len(), 2);
+}
+
+#[test]
+fn keeps_the_first_of_two_husbands_when_not_an_error() {
+    let records = "0 @F1@ FAM\n1 HUSB @I1@\n1 HUSB @I2@\n";
+    let error = parse(records, ParserOptions::default()).unwrap_err();
+    assert!(matches!(error, ParseError::DuplicateSpouse { .. }));
+
+    let (data, diagnostics) = parse(records, ParserOptions::lenient()).unwrap();
+    assert_eq!(data.families[0].individual1.as_deref(), Some("@I1@"));
+    assert_eq!(
+        diagnostics,
+        vec![(6, DiagnosticKind::DuplicateSpouse, Severity::Error)]
+    );
+}
+
+#[test]
+fn treats_unrecognized_values_as_asked() {
+    let records = "0 @I1@ INDI\n1 SEX Q\n1 FAMC @F1@\n2 PEDI cousin\n";
+    let with = |policy: ValuePolicy| ParserOptions {
+        unrecognized_pedigree: policy,
+        unrecognized_sex: policy,
+        ..ParserOptions::default()
+    };
+
+    let error = parse(records, with(ValuePolicy::Error)).unwrap_err();
+    assert!(matches!(error, ParseError::UnknownSex { line: 5, .. }));
+
+    let (data, diagnostics) = parse(records, with(ValuePolicy::Warn)).unwrap();
+    let individual = &data.individuals[0];
+    assert_eq!(individual.sex, Gender::Unknown);
+    assert_eq!(individual.families[0].pedigree(), None);
+    assert_eq!(
+        diagnostics,
+        vec![
+            (5, DiagnosticKind::UnrecognizedValue, Severity::Error),
+            (7, DiagnosticKind::UnrecognizedValue, Severity::Error),
+        ]
+    );
+
+    let (data, diagnostics) = parse(records, with(ValuePolicy::Ignore)).unwrap();
+    assert_eq!(data.individuals[0].sex, Gender::Unknown);
+    assert_eq!(diagnostics, vec![]);
+
+    // a known pedigree is kept whatever the policy
+    let (data, _) = parse(
+        "0 @I1@ INDI\n1 FAMC @F1@\n2 PEDI foster\n",
+        with(ValuePolicy::Error),
+    )
+    .unwrap();
+    assert_eq!(
+        data.individuals[0].families[0].pedigree(),
+        Some(&Pedigree::Foster)
+    );
+}
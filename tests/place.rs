@@ -0,0 +1,107 @@
+//! Places keep their text and are split into jurisdictions, coordinates and variations.
+use gedcom::parser::Parser;
+use gedcom::types::{
+    event::HasEvents, Event, EventType, Individual, Jurisdiction, Place, PlaceVariant,
+};
+use gedcom::writer::GedcomWriter;
+use gedcom::DiagnosticKind;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 PLAC\n2 FORM City, County, State, Country\n\
+    0 @I1@ INDI\n1 NAME Yuna /Kim/\n1 BIRT\n2 PLAC 서울, 대한민국\n3 FORM City, Country\n\
+    3 FONE Seoul, Daehan Minguk\n4 TYPE hangul\n3 ROMN Sŏul, Taehan Min'guk\n4 TYPE mcr\n\
+    3 MAP\n4 LATI N37.5665\n4 LONG E126.978\n1 RESI\n2 PLAC Provo, , Utah, USA\n\
+    3 MAP\n4 LATI S12.5\n4 LONG W77.03\n1 DEAT\n2 PLAC Busan\n3 MAP\n4 LATI 35.1\n0 TRLR\n";
+
+#[test]
+fn splits_places_into_jurisdictions() {
+    let data = gedcom::parse(SOURCE.chars());
+    let header_form = data.header.place_form.as_deref();
+    assert_eq!(header_form, Some("City, County, State, Country"));
+
+    let places = data.individuals[0].places();
+    // a place with its own form uses it rather than the header's
+    assert_eq!(
+        places[0].jurisdictions(header_form),
+        vec![
+            Jurisdiction {
+                name: "서울",
+                level: Some("City"),
+            },
+            Jurisdiction {
+                name: "대한민국",
+                level: Some("Country"),
+            },
+        ]
+    );
+    let resi = places[1].jurisdictions(header_form);
+    assert_eq!(resi.len(), 4);
+    assert_eq!(
+        resi[1],
+        Jurisdiction {
+            name: "",
+            level: Some("County"),
+        }
+    );
+    assert_eq!(resi[2].level, Some("State"));
+    // without a form, the jurisdictions have no levels
+    assert_eq!(places[2].jurisdictions(None)[0].level, None);
+}
+
+#[test]
+fn parses_coordinates_and_variations() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(SOURCE.chars()).unwrap();
+    let places = data.individuals[0].places();
+
+    assert_eq!(places[0].latitude, Some(37.5665));
+    assert_eq!(places[0].longitude, Some(126.978));
+    assert_eq!(places[1].latitude, Some(-12.5));
+    assert_eq!(places[1].longitude, Some(-77.03));
+    assert_eq!(
+        places[0].phonetic,
+        vec![PlaceVariant {
+            value: "Seoul, Daehan Minguk".to_string(),
+            variant_type: Some("hangul".to_string()),
+        }]
+    );
+    assert_eq!(places[0].romanized[0].value, "Sŏul, Taehan Min'guk");
+
+    // a latitude without its hemisphere is dropped
+    assert_eq!(places[2].latitude, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnrecognizedValue);
+    assert_eq!(diagnostics[0].line, 26);
+}
+
+#[test]
+fn writes_places_of_new_records() {
+    let mut parser = Parser::new(SOURCE.chars());
+    let mut data = parser.try_parse().unwrap();
+    let mut individual = Individual::new(Some("@I2@".to_string()));
+    let mut death = Event::new(EventType::Death);
+    death.place = Some(Place {
+        latitude: Some(35.1796),
+        longitude: Some(-129.0756),
+        ..Place::new("Busan, South Korea")
+    });
+    individual.add_event(death);
+    data.add_individual(individual);
+
+    let mut output = Vec::new();
+    GedcomWriter::with_options(&mut output, parser.writer_options())
+        .write_data(&data)
+        .unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written
+        .contains("1 DEAT\n2 PLAC Busan, South Korea\n3 MAP\n4 LATI N35.1796\n4 LONG W129.0756\n"));
+    assert!(written.contains("1 PLAC\n2 FORM City, County, State, Country\n"));
+
+    let reparsed = gedcom::parse(written.chars());
+    assert_eq!(
+        reparsed.individuals[0].places(),
+        data.individuals[0].places()
+    );
+    assert_eq!(
+        reparsed.individuals[1].places(),
+        data.individuals[1].places()
+    );
+}
@@ -0,0 +1,109 @@
+//! Files can be parsed from any reader, or any iterator of chars.
+use std::io::{self, BufReader, Read};
+
+use gedcom::parser::Parser;
+use gedcom::ParseError;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Siv /Aas/\n\
+    0 @I2@ INDI\n1 NAME Are /Aas/\n0 TRLR\n";
+
+/// A reader giving out `data`, then failing with an error of `kind`
+struct Failing {
+    data: &'static [u8],
+    kind: io::ErrorKind,
+}
+
+impl Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.data.is_empty() {
+            return Err(io::Error::new(self.kind, "connection lost"));
+        }
+        let len = buf.len().min(self.data.len()).min(10);
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn parses_from_a_reader() {
+    let from_reader = Parser::from_reader(SOURCE.as_bytes()).try_parse().unwrap();
+    let from_str = gedcom::parse(SOURCE.chars());
+    assert_eq!(format!("{:?}", from_reader), format!("{:?}", from_str));
+
+    let file = std::fs::File::open("./tests/fixtures/sample.ged").unwrap();
+    let from_file = Parser::from_reader(BufReader::new(file))
+        .try_parse()
+        .unwrap();
+    let sample = std::fs::read_to_string("./tests/fixtures/sample.ged").unwrap();
+    assert_eq!(
+        format!("{:?}", from_file),
+        format!("{:?}", gedcom::parse(sample.chars()))
+    );
+}
+
+#[test]
+fn parses_from_any_iterator_of_chars() {
+    let chars: Vec<char> = SOURCE.chars().collect();
+    let data = Parser::new(chars.into_iter()).try_parse().unwrap();
+    assert_eq!(data.individuals.len(), 2);
+}
+
+#[test]
+fn fails_with_the_read_error() {
+    let reader = BufReader::new(Failing {
+        data: &SOURCE.as_bytes()[..60],
+        kind: io::ErrorKind::ConnectionReset,
+    });
+    let error = Parser::from_reader(reader).try_parse().unwrap_err();
+    match &error {
+        ParseError::Io {
+            line,
+            kind,
+            message,
+        } => {
+            assert_eq!((*line, *kind), (6, io::ErrorKind::ConnectionReset));
+            assert_eq!(message, "connection lost");
+        }
+        other => panic!("expected an I/O error, found {:?}", other),
+    }
+    assert_eq!(
+        error.to_string(),
+        "line 6: could not read input: connection lost"
+    );
+
+    // the records read before the error are still yielded
+    let reader = BufReader::new(Failing {
+        data: &SOURCE.as_bytes()[..60],
+        kind: io::ErrorKind::ConnectionReset,
+    });
+    let mut parser = Parser::from_reader(reader);
+    let records: Vec<_> = parser.records().collect();
+    assert!(records[0].is_ok());
+    assert!(matches!(records.last(), Some(Err(ParseError::Io { .. }))));
+}
+
+#[test]
+fn retries_interrupted_reads() {
+    struct Interrupting<R> {
+        inner: R,
+        interrupt: bool,
+    }
+    impl<R: Read> Read for Interrupting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(7);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    let reader = BufReader::new(Interrupting {
+        inner: SOURCE.as_bytes(),
+        interrupt: false,
+    });
+    let data = Parser::from_reader(reader).try_parse().unwrap();
+    assert_eq!(data.individuals.len(), 2);
+}
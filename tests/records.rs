@@ -0,0 +1,133 @@
+//! Individual, repository, source and submitter records are parsed along with their substructures.
+use gedcom::types::{Association, EventType, GedcomDate, RepoCitation, UserReference};
+use gedcom::writer::GedcomWriter;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 SUBM @U1@\n\
+    0 @U1@ SUBM\n1 NAME Per Lund\n1 ADDR Storgata 1\n2 CONT 0150 Oslo\n2 CITY Oslo\n\
+    2 CTRY Norway\n1 PHON +47 555 0100\n\
+    0 @R1@ REPO\n1 NAME National Archives\n1 ADDR Sognsveien 221\n2 CITY Oslo\n\
+    1 NOTE Open on weekdays\n\
+    0 @S1@ SOUR\n1 DATA\n2 EVEN BIRT, DEAT\n3 DATE FROM 1820 TO 1880\n3 PLAC Aker\n\
+    2 AGNC Aker parish\n1 AUTH Aker parish priests\n1 TITL Aker church book \n2 CONC 1820-1880\n\
+    1 ABBR Aker CB\n1 PUBL Digitised 2004\n1 TEXT Født den 3die\n2 CONT Marts\n\
+    1 REPO @R1@\n2 CALN Ministerialbok 1\n0 TRLR\n";
+
+#[test]
+fn parses_repositories() {
+    let data = gedcom::parse(SOURCE.chars());
+    assert_eq!(data.repositories.len(), 1);
+    let repository = &data.repositories[0];
+    assert_eq!(repository.xref.as_deref(), Some("@R1@"));
+    assert_eq!(repository.name.as_deref(), Some("National Archives"));
+    let address = repository.address.as_ref().unwrap();
+    assert_eq!(address.value.as_deref(), Some("Sognsveien 221"));
+    assert_eq!(address.city.as_deref(), Some("Oslo"));
+    assert_eq!(
+        data.note_text(&repository.notes[0]),
+        Some("Open on weekdays")
+    );
+}
+
+#[test]
+fn parses_sources() {
+    let data = gedcom::parse(SOURCE.chars());
+    assert_eq!(data.sources.len(), 1);
+    let source = &data.sources[0];
+    assert_eq!(source.xref.as_deref(), Some("@S1@"));
+    assert_eq!(source.title.as_deref(), Some("Aker church book 1820-1880"));
+    assert_eq!(source.author.as_deref(), Some("Aker parish priests"));
+    assert_eq!(source.abbreviation.as_deref(), Some("Aker CB"));
+    assert_eq!(source.publication.as_deref(), Some("Digitised 2004"));
+    assert_eq!(source.text.as_deref(), Some("Født den 3die\nMarts"));
+    assert_eq!(
+        source.repo_citations,
+        vec![RepoCitation {
+            xref: "@R1@".to_string(),
+            call_number: Some("Ministerialbok 1".to_string()),
+        }]
+    );
+
+    assert_eq!(source.data.agency.as_deref(), Some("Aker parish"));
+    let recorded = &source.data.events()[0];
+    assert_eq!(
+        recorded.event,
+        EventType::SourceData("BIRT, DEAT".to_string())
+    );
+    assert_eq!(recorded.date, Some(GedcomDate::parse("FROM 1820 TO 1880")));
+    assert_eq!(recorded.place.as_ref().unwrap().value, "Aker");
+}
+
+#[test]
+fn parses_submitters() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(SOURCE.chars()).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(data.submitters.len(), 1);
+    let submitter = &data.submitters[0];
+    assert_eq!(submitter.xref.as_deref(), Some("@U1@"));
+    assert_eq!(submitter.name.as_deref(), Some("Per Lund"));
+    assert_eq!(submitter.phone.as_deref(), Some("+47 555 0100"));
+    let address = submitter.address.as_ref().unwrap();
+    assert_eq!(address.value.as_deref(), Some("Storgata 1\n0150 Oslo"));
+    assert_eq!(address.country.as_deref(), Some("Norway"));
+    assert_eq!(data.header.submitter_tag.as_deref(), Some("@U1@"));
+}
+
+#[test]
+fn parses_the_substructures_of_individuals() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 RESN privacy\n\
+        1 NAME Ole /Berg/\n1 ASSO @I2@\n2 RELA Godfather\n2 NOTE At the baptism\n1 ALIA @I3@\n\
+        1 REFN 1042\n2 TYPE card\n1 SOUR @S1@\n2 PAGE p. 12\n2 QUAY 3\n0 TRLR\n";
+    let (mut data, diagnostics) = gedcom::parse_with_diagnostics(source.chars()).unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    let individual = &data.individuals[0];
+    assert_eq!(individual.restriction.as_deref(), Some("privacy"));
+    assert_eq!(individual.associations.len(), 1);
+    let Association {
+        xref,
+        relation,
+        notes,
+        ..
+    } = &individual.associations[0];
+    assert_eq!(
+        (xref.as_str(), relation.as_deref(), notes.len()),
+        ("@I2@", Some("Godfather"), 1)
+    );
+    assert_eq!(individual.aliases, vec!["@I3@".to_string()]);
+    assert_eq!(
+        individual.user_references,
+        vec![UserReference {
+            number: "1042".to_string(),
+            reference_type: Some("card".to_string()),
+        }]
+    );
+    let citation = &individual.citations[0];
+    assert_eq!(citation.xref, "@S1@");
+    assert_eq!(citation.quality.as_deref(), Some("3"));
+
+    // they are written back when the record is edited
+    data.individuals[0].names.clear();
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains(
+        "0 @I1@ INDI\n1 RESN privacy\n1 ASSO @I2@\n2 RELA Godfather\n2 NOTE At the baptism\n\
+        1 ALIA @I3@\n1 REFN 1042\n2 TYPE card\n1 SOUR @S1@\n2 PAGE p. 12\n2 QUAY 3\n0 TRLR\n"
+    ));
+}
+
+#[test]
+fn parses_the_number_of_children_of_families() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @F1@ FAM\n1 HUSB @I1@\n1 NCHI 3\n\
+        0 @F2@ FAM\n1 NCHI several\n0 TRLR\n";
+    let (mut data, diagnostics) = gedcom::parse_with_diagnostics(source.chars()).unwrap();
+    assert_eq!(data.families[0].num_children, Some(3));
+    assert_eq!(data.families[1].num_children, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 8);
+
+    data.families[0].num_children = Some(4);
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("0 @F1@ FAM\n1 HUSB @I1@\n1 NCHI 4\n"));
+}
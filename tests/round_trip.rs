@@ -0,0 +1,128 @@
+//! Parsing a file and writing it back out must not change the records left unedited.
+use gedcom::parser::Parser;
+use gedcom::types::Record;
+use gedcom::writer::GedcomWriter;
+use gedcom::GedcomData;
+
+fn read_fixture(name: &str) -> Vec<u8> {
+    std::fs::read(format!("./tests/fixtures/{}", name)).unwrap()
+}
+
+/// Parses `bytes`, lets `edit` change the data, and writes it back the way it was read
+fn rewrite(bytes: &[u8], edit: impl FnOnce(&mut GedcomData)) -> String {
+    let mut parser = Parser::from_reader(bytes);
+    let mut data = parser.try_parse().unwrap();
+    edit(&mut data);
+
+    let mut output = Vec::new();
+    GedcomWriter::with_options(&mut output, parser.writer_options())
+        .write_data(&data)
+        .unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn assert_round_trip(name: &str) {
+    let original = read_fixture(name);
+    let written = rewrite(&original, |_| {});
+    assert_eq!(written, String::from_utf8(original).unwrap(), "{}", name);
+}
+
+#[test]
+fn ancestry_with_byte_order_mark_and_crlf() {
+    assert_round_trip("ancestry.ged");
+}
+
+#[test]
+fn familysearch_with_unicode_and_continued_note() {
+    assert_round_trip("familysearch.ged");
+}
+
+#[test]
+fn rootsmagic_with_custom_records() {
+    assert_round_trip("rootsmagic.ged");
+}
+
+#[test]
+fn gramps_with_escaped_at_sign() {
+    assert_round_trip("gramps.ged");
+}
+
+#[test]
+fn family_tree_maker_with_stray_whitespace_and_no_final_newline() {
+    assert_round_trip("ftm.ged");
+}
+
+#[test]
+fn reunion_with_carriage_returns() {
+    assert_round_trip("reunion.ged");
+}
+
+#[test]
+fn charset_is_rewritten_to_utf8() {
+    let original = String::from_utf8(read_fixture("sample.ged")).unwrap();
+    let written = rewrite(original.as_bytes(), |_| {});
+    assert_eq!(
+        written,
+        original.replace("1 CHAR ASCII\n", "1 CHAR UTF-8\n")
+    );
+}
+
+#[test]
+fn only_edited_records_change() {
+    let original = String::from_utf8(read_fixture("familysearch.ged")).unwrap();
+    let written = rewrite(original.as_bytes(), |data| {
+        let name = &mut data.individuals[1].names[0];
+        name.value = Some("Carmen /Ruiz Torres/".to_string());
+    });
+
+    let original_lines: Vec<&str> = original.lines().collect();
+    let written_lines: Vec<&str> = written.lines().collect();
+    assert_eq!(original_lines.len(), written_lines.len());
+    let changed: Vec<(&str, &str)> = original_lines
+        .iter()
+        .zip(&written_lines)
+        .filter(|(before, after)| before != after)
+        .map(|(before, after)| (*before, *after))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![("1 NAME Carmen /Ruiz/", "1 NAME Carmen /Ruiz Torres/")]
+    );
+}
+
+#[test]
+fn new_records_go_before_the_trailer() {
+    let original = String::from_utf8(read_fixture("reunion.ged")).unwrap();
+    let written = rewrite(original.as_bytes(), |data| {
+        let mut parser = Parser::new("0 @I3@ INDI\r1 NAME Astrid /Lindqvist/\r".chars());
+        match parser.next_record().unwrap() {
+            Some(Record::Individual(mut individual)) => {
+                // a record without a node is new
+                individual.node = None;
+                data.add_individual(individual);
+            }
+            other => panic!("expected an individual, found {:?}", other),
+        }
+    });
+
+    let trailer = original.len() - "0 TRLR\r".len();
+    assert_eq!(
+        written,
+        format!(
+            "{}0 @I3@ INDI\r1 NAME Astrid /Lindqvist/\r0 TRLR\r",
+            &original[..trailer]
+        )
+    );
+}
+
+#[test]
+fn a_record_edited_back_is_written_as_read() {
+    let original = String::from_utf8(read_fixture("gramps.ged")).unwrap();
+    let written = rewrite(original.as_bytes(), |data| {
+        let name = &mut data.individuals[0].names[0];
+        let value = name.value.take();
+        name.value = Some("Someone /Else/".to_string());
+        name.value = value;
+    });
+    assert_eq!(written, original);
+}
@@ -0,0 +1,87 @@
+//! Records can be read one at a time, without building the whole tree.
+use gedcom::parser::Parser;
+use gedcom::types::Record;
+use gedcom::{GedcomData, ParseError};
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Siv /Aas/\n\
+    0 @F1@ FAM\n1 WIFE @I1@\n0 @S1@ SOUR\n1 TITL Census\n0 @R1@ REPO\n1 NAME Archive\n\
+    0 @U1@ SUBM\n1 NAME Siv Aas\n0 @N1@ NOTE Shared\n0 @M1@ OBJE\n1 FILE photo.jpg\n\
+    0 @X1@ _PLAC Bergen\n0 TRLR\n";
+
+/// A short name for the kind of a record
+fn kind(record: &Record) -> &'static str {
+    match record {
+        Record::Header(_) => "header",
+        Record::Individual(_) => "individual",
+        Record::Family(_) => "family",
+        Record::Source(_) => "source",
+        Record::Repository(_) => "repository",
+        Record::Submitter(_) => "submitter",
+        Record::Submission(_) => "submission",
+        Record::Note(_) => "note",
+        Record::Media(_) => "media",
+        Record::Custom(_) => "custom",
+    }
+}
+
+#[test]
+fn yields_each_record_in_order() {
+    let mut parser = Parser::new(SOURCE.chars());
+    let kinds: Vec<&str> = parser
+        .records()
+        .map(|record| kind(&record.unwrap()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "header",
+            "individual",
+            "family",
+            "source",
+            "repository",
+            "submitter",
+            "note",
+            "media",
+            "custom",
+        ]
+    );
+    // the trailer ends the records
+    assert!(parser.next_record().unwrap().is_none());
+}
+
+#[test]
+fn builds_the_same_data_as_parsing_all_at_once() {
+    let mut data = GedcomData::default();
+    for record in Parser::new(SOURCE.chars()).records() {
+        data.add_record(record.unwrap());
+    }
+    let parsed = gedcom::parse(SOURCE.chars());
+    assert_eq!(format!("{:?}", data), format!("{:?}", parsed));
+}
+
+#[test]
+fn stops_at_the_first_error() {
+    let source = "0 HEAD\n0 @I1@ INDI\n1 NAME Siv\nX NAME Aas\n0 @I2@ INDI\n0 TRLR\n";
+    let mut parser = Parser::new(source.chars());
+    let records: Vec<Result<Record, ParseError>> = parser.records().collect();
+    assert_eq!(records.len(), 2);
+    assert!(matches!(records[0], Ok(Record::Header(_))));
+    assert!(matches!(
+        records[1],
+        Err(ParseError::InvalidLevel { line: 4, .. })
+    ));
+}
+
+#[test]
+fn collects_diagnostics_as_records_are_read() {
+    let mut parser = Parser::new(SOURCE.chars());
+    let mut records = parser.records();
+    records.nth(7).unwrap().unwrap();
+    assert!(parser.diagnostics().is_empty());
+
+    // the custom record is reported as it is read
+    let custom = parser.next_record().unwrap().unwrap();
+    assert_eq!(kind(&custom), "custom");
+    assert_eq!(parser.diagnostics().len(), 1);
+    assert_eq!(parser.diagnostics()[0].line, 17);
+}
@@ -0,0 +1,69 @@
+//! Submission records, which the header points to.
+use gedcom::types::{NoteStructure, Submission};
+use gedcom::writer::GedcomWriter;
+use gedcom::DiagnosticKind;
+
+const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 SUBM @U1@\n1 SUBN @SUBN1@\n\
+    0 @U1@ SUBM\n1 NAME Ingrid Vik\n0 @SUBN1@ SUBN\n1 SUBM @U1@\n1 FAMF Vik family\n\
+    1 TEMP SLAKE\n1 ANCE 4\n1 DESC 2\n1 ORDI yes\n1 RIN 17\n1 NOTE Sent with the\n2 CONT letter\n\
+    0 @SUBN2@ SUBN\n1 ANCE many\n1 ORDI maybe\n0 TRLR\n";
+
+#[test]
+fn parses_submissions() {
+    let (data, diagnostics) = gedcom::parse_with_diagnostics(SOURCE.chars()).unwrap();
+    assert_eq!(data.submissions.len(), 2);
+    assert_eq!(
+        data.submissions[0],
+        Submission {
+            submitter: Some("@U1@".to_string()),
+            family_file: Some("Vik family".to_string()),
+            temple: Some("SLAKE".to_string()),
+            ancestor_generations: Some(4),
+            descendant_generations: Some(2),
+            ordinance_process: Some(true),
+            record_id: Some("17".to_string()),
+            notes: vec![NoteStructure::Text("Sent with the\nletter".to_string())],
+            node: data.submissions[0].node.clone(),
+            ..Submission::new(Some("@SUBN1@".to_string()))
+        }
+    );
+
+    // values outside of the grammar are dropped
+    let second = &data.submissions[1];
+    assert_eq!(second.ancestor_generations, None);
+    assert_eq!(second.ordinance_process, None);
+    let kinds: Vec<DiagnosticKind> = diagnostics.iter().map(|d| d.kind).collect();
+    assert_eq!(kinds, vec![DiagnosticKind::UnrecognizedValue; 2]);
+}
+
+#[test]
+fn resolves_the_submission_of_the_header() {
+    let data = gedcom::parse(SOURCE.chars());
+    let submission = data.submission().unwrap();
+    assert_eq!(submission.xref.as_deref(), Some("@SUBN1@"));
+    assert_eq!(submission.family_file.as_deref(), Some("Vik family"));
+
+    let without = gedcom::parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 TRLR\n".chars());
+    assert_eq!(without.submission(), None);
+}
+
+#[test]
+fn writes_edited_submissions() {
+    let mut data = gedcom::parse(SOURCE.chars());
+    data.submissions[0].node = None;
+    data.submissions[0].ordinance_process = Some(false);
+
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(&data).unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.contains("1 SUBN @SUBN1@\n"));
+    assert!(written.contains(
+        "0 @SUBN1@ SUBN\n1 SUBM @U1@\n1 FAMF Vik family\n1 TEMP SLAKE\n1 ANCE 4\n1 DESC 2\n\
+         1 ORDI no\n1 RIN 17\n1 NOTE Sent with the\n2 CONT letter\n"
+    ));
+
+    let reparsed = gedcom::parse(written.chars());
+    let submission = reparsed.submission().unwrap();
+    assert_eq!(submission.ordinance_process, Some(false));
+    assert_eq!(submission.notes, data.submissions[0].notes);
+}
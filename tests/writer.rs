@@ -0,0 +1,205 @@
+//! Records written from their typed data are escaped and split the way GEDCOM 5.5.1 wants.
+use gedcom::parser::Parser;
+use gedcom::types::{
+    event::HasEvents, Event, EventType, GedcomDate, Gender, Individual, Name, NoteStructure,
+    RepoCitation, Source,
+};
+use gedcom::writer::{GedcomWriter, MAX_LINE_LENGTH};
+use gedcom::GedcomData;
+
+/// Writes `data`, returning the text of the file
+fn write(data: &GedcomData) -> String {
+    let mut output = Vec::new();
+    GedcomWriter::new(&mut output).write_data(data).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+/// Data with a single new individual having the given events
+fn individual_with(events: Vec<Event>) -> GedcomData {
+    let mut individual = Individual::new(Some("@I1@".to_string()));
+    for event in events {
+        individual.add_event(event);
+    }
+    let mut data = GedcomData::default();
+    data.add_individual(individual);
+    data
+}
+
+#[test]
+fn keeps_calendar_escapes_wherever_they_are() {
+    let dates = [
+        "@#DJULIAN@ 1700",
+        "ABT @#DJULIAN@ 1700",
+        "BET @#DJULIAN@ 1750 AND @#DJULIAN@ 1751",
+        "FROM @#DFRENCH R@ 1 VEND 2 TO @#DGREGORIAN@ 1800",
+    ];
+    let events = dates
+        .iter()
+        .map(|date| {
+            let mut event = Event::new(EventType::Residence);
+            event.date = Some(GedcomDate::parse(date));
+            event
+        })
+        .collect();
+    let data = individual_with(events);
+
+    let written = write(&data);
+    for date in dates {
+        assert!(written.contains(&format!("2 DATE {date}\n")), "{}", date);
+    }
+    let reparsed = gedcom::parse(written.chars());
+    let events = reparsed.individuals[0].events();
+    assert!(events
+        .iter()
+        .all(|event| event.date.as_ref().unwrap().is_valid()));
+    assert_eq!(events, data.individuals[0].events());
+}
+
+#[test]
+fn writes_an_unknown_sex_only_when_it_was_read() {
+    let mut data = individual_with(Vec::new());
+    assert!(!write(&data).contains("SEX"));
+
+    data.individuals[0].sex = Gender::Female;
+    assert!(write(&data).contains("1 SEX F\n"));
+
+    // an edited record keeps the `SEX U` it was read with
+    let mut data =
+        gedcom::parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 SEX U\n0 TRLR\n".chars());
+    data.individuals[0].names.push(Name {
+        value: Some("Eli /Sand/".to_string()),
+        ..Name::default()
+    });
+    assert!(write(&data).contains("0 @I1@ INDI\n1 SEX U\n1 NAME Eli /Sand/\n"));
+}
+
+#[test]
+fn splits_long_values_over_conc_and_cont_lines() {
+    let sentence = "The parish register was damaged by water in the winter of 1861, ";
+    let text = format!("{}\nTranscribed by the @ home society.", sentence.repeat(8));
+    let mut source = Source::new(Some("@S1@".to_string()));
+    source.text = Some(text.clone());
+    let mut data = GedcomData::default();
+    data.add_source(source);
+
+    let written = write(&data);
+    assert!(written
+        .lines()
+        .all(|line| line.chars().count() < MAX_LINE_LENGTH));
+    let text_lines: Vec<&str> = written
+        .lines()
+        .skip_while(|line| !line.starts_with("1 TEXT "))
+        .take_while(|line| !line.starts_with("0 "))
+        .collect();
+    assert_eq!(text_lines.len(), 4);
+    assert!(text_lines[1].starts_with("2 CONC "));
+    assert!(text_lines[2].starts_with("2 CONC "));
+    assert_eq!(text_lines[3], "2 CONT Transcribed by the @@ home society.");
+    // words are not split across lines, so no spaces are lost at either end
+    assert!(text_lines[1].ends_with(|c: char| c != ' '));
+
+    let reparsed = gedcom::parse(written.chars());
+    assert_eq!(reparsed.sources[0].text.as_deref(), Some(text.as_str()));
+}
+
+#[test]
+fn escapes_at_signs_but_not_pointers() {
+    let mut source = Source::new(Some("@S1@".to_string()));
+    source.title = Some("Letters to info@example.org".to_string());
+    source.add_repo_citation(RepoCitation {
+        xref: "@R1@".to_string(),
+        call_number: Some("Box @12".to_string()),
+    });
+    let mut data = GedcomData::default();
+    data.add_source(source);
+
+    let written = write(&data);
+    assert!(written.contains(
+        "0 @S1@ SOUR\n1 TITL Letters to info@@example.org\n1 REPO @R1@\n2 CALN Box @@12\n"
+    ));
+    let reparsed = gedcom::parse(written.chars());
+    assert_eq!(
+        reparsed.sources[0].title.as_deref(),
+        Some("Letters to info@example.org")
+    );
+    assert_eq!(
+        reparsed.sources[0].repo_citations,
+        data.sources[0].repo_citations
+    );
+}
+
+#[test]
+fn generates_a_header_and_trailer() {
+    let written = write(&individual_with(Vec::new()));
+    let header: Vec<&str> = written
+        .lines()
+        .take_while(|line| *line != "0 @I1@ INDI")
+        .collect();
+    assert_eq!(header[0], "0 HEAD");
+    let version = format!("2 VERS {}", env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        &header[1..4],
+        ["1 SOUR GEDCOM", &version, "2 NAME rust-gedcom"]
+    );
+    assert!(header[4].starts_with("1 DATE "));
+    assert!(header[5].starts_with("2 TIME "));
+    assert_eq!(
+        &header[6..],
+        [
+            "1 GEDC",
+            "2 VERS 5.5.1",
+            "2 FORM LINEAGE-LINKED",
+            "1 CHAR UTF-8"
+        ]
+    );
+    assert!(written.ends_with("0 @I1@ INDI\n0 TRLR\n"));
+
+    // the date is one the parser reads back
+    let reparsed = gedcom::parse(written.chars());
+    assert!(reparsed.header.date.unwrap().is_valid());
+}
+
+#[test]
+fn keeps_the_program_that_wrote_the_file_in_a_generated_header() {
+    let source = "0 HEAD\n1 SOUR PAF\n2 VERS 5.2\n2 CORP Intellectual Reserve\n3 PHON 555-0100\n\
+        2 DATA Ancestral File\n3 DATE 1 JAN 1998\n1 GEDC\n2 VERS 5.5.1\n2 FORM LINEAGE-LINKED\n\
+        1 CHAR UTF-8\n2 VERS 1.0\n0 TRLR\n";
+    let mut data = gedcom::parse(source.chars());
+    data.header.language = Some("English".to_string());
+
+    let written = write(&data);
+    assert!(written.starts_with(
+        "0 HEAD\n1 SOUR PAF\n2 VERS 5.2\n2 CORP Intellectual Reserve\n3 PHON 555-0100\n\
+        2 DATA Ancestral File\n3 DATE 1 JAN 1998\n1 DATE "
+    ));
+    assert!(written.contains("1 CHAR UTF-8\n2 VERS 1.0\n1 LANG English\n"));
+    let reparsed = gedcom::parse(written.chars());
+    assert_eq!(reparsed.header.source, data.header.source);
+}
+
+#[test]
+fn writes_edited_records_of_gedcom7_as_gedcom7() {
+    let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME Ada /Lind/\n\
+        0 @N1@ SNOTE Shared\n0 TRLR\n";
+    let mut parser = Parser::new(source.chars());
+    let mut data = parser.try_parse().unwrap();
+    data.individuals[0]
+        .notes
+        .push(NoteStructure::Shared("@N1@".to_string()));
+    data.individuals[0].names[0].value = Some(format!("Ada {} /Lind/", "Maria ".repeat(50)));
+
+    let mut output = Vec::new();
+    GedcomWriter::with_options(&mut output, parser.writer_options())
+        .write_data(&data)
+        .unwrap();
+    let written = String::from_utf8(output).unwrap();
+    assert!(written.starts_with("0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME Ada Maria"));
+    assert!(written.contains("\n1 SNOTE @N1@\n"));
+    assert!(!written.contains("CONC"));
+
+    // the same goes for a header generated in place of the one read
+    let written = write(&data);
+    assert!(written.contains("1 GEDC\n2 VERS 7.0\n"));
+    assert!(!written.contains("CHAR"));
+    assert!(written.contains("\n1 SNOTE @N1@\n"));
+}